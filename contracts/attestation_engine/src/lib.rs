@@ -1,12 +1,29 @@
 #![no_std]
-use shared_utils::{BatchError, BatchMode, BatchProcessor, BatchResultVoid, Pausable, RateLimiter};
+use shared_utils::{
+    BatchError, BatchMode, BatchProcessor, BatchResultVoid, Pausable, RateLimiter, SafeMath,
+    Storage,
+};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
-    IntoVal, Map, String, Symbol, TryIntoVal, Val, Vec,
+    IntoVal, Map, String, Symbol, TryFromVal, TryIntoVal, Val, Vec,
 };
 
 const CURRENT_VERSION: u32 = 1;
 
+/// Upper bound on `get_health_metrics_batch`. Every entry costs a
+/// cross-contract `get_commitment` call, so this stays well below the
+/// general batch limit.
+pub const MAX_HEALTH_METRICS_BATCH: u32 = 10;
+
+/// Bounds on an attestation's `data` map, so recorders cannot bloat storage.
+pub const MAX_ATTESTATION_DATA_ENTRIES: u32 = 16;
+pub const MAX_ATTESTATION_DATA_KEY_LEN: u32 = 32;
+pub const MAX_ATTESTATION_DATA_VALUE_LEN: u32 = 256;
+
+/// Flat compliance bonus once recorded fees reach `rules.min_fee_threshold`.
+/// Kept small so it cannot offset more than half a violation.
+pub const FEE_THRESHOLD_BONUS: i32 = 10;
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -44,6 +61,14 @@ pub enum AttestationError {
     AlreadyMigrated = 13,
     /// Invalid version for migration
     InvalidVersion = 14,
+    /// Too many commitments requested in one batch read
+    BatchTooLarge = 15,
+    /// Attestation data has too many entries or an oversized key/value
+    AttestationDataTooLarge = 16,
+    /// Commitment is no longer active (settled, violated or exited)
+    CommitmentNotActive = 17,
+    /// Health metrics were finalized at settlement; no further records accepted
+    MetricsFinalized = 18,
 }
 
 // ============================================================================
@@ -85,6 +110,12 @@ pub enum DataKey {
     CollectedFees(Address),
     /// Contract version for migrations
     Version,
+    /// Last computed compliance inputs (commitment_id -> ComplianceCache)
+    ComplianceCache(String),
+    /// Set when an attestation is recorded after the cache was built (commitment_id -> bool)
+    ScoreDirty(String),
+    /// Score frozen by `finalize` at settlement; metrics are closed (commitment_id -> u32)
+    FinalScore(String),
 }
 
 #[contracttype]
@@ -133,6 +164,8 @@ pub struct Commitment {
     pub expires_at: u64,
     pub current_value: i128,
     pub status: String, // "active", "settled", "violated", "early_exit"
+    pub label: String,
+    pub tags: Vec<String>,
 }
 
 // Import Commitment types from commitment_core (define locally for cross-contract calls)
@@ -144,12 +177,21 @@ pub struct HealthMetrics {
     pub current_value: i128,
     pub initial_value: i128,
     pub drawdown_percent: i128,
+    pub max_drawdown_percent: i128, // worst drawdown recorded so far
     pub fees_generated: i128,
     pub volatility_exposure: i128,
     pub last_attestation: u64,
     pub compliance_score: u32, // 0-100
 }
 
+/// Compliance inputs that only change when an attestation is recorded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplianceCache {
+    pub score: u32, // 0-100
+    pub violation_count: u32,
+}
+
 #[contract]
 pub struct AttestationEngineContract;
 
@@ -350,10 +392,12 @@ impl AttestationEngineContract {
         read_version(&e)
     }
 
-    /// Update admin (admin-only).
+    /// Update admin (admin-only). Emits `AdminChanged` with the previous and new admin.
     pub fn set_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), AttestationError> {
         require_admin(&e, &caller)?;
         e.storage().instance().set(&DataKey::Admin, &new_admin);
+        e.events()
+            .publish((Symbol::new(&e, "AdminChanged"), caller), new_admin);
         Ok(())
     }
 
@@ -370,6 +414,10 @@ impl AttestationEngineContract {
     }
 
     /// Migrate storage from a previous version to CURRENT_VERSION (admin-only).
+    ///
+    /// `HealthMetrics` written before the worst-drawdown tracking are not
+    /// walked here; they are backfilled as they are read (see
+    /// `read_health_metrics`), so no entry is missed.
     pub fn migrate(e: Env, caller: Address, from_version: u32) -> Result<(), AttestationError> {
         require_admin(&e, &caller)?;
 
@@ -407,8 +455,38 @@ impl AttestationEngineContract {
 
     /// Get stored health metrics for a commitment (without recalculation)
     pub fn get_stored_health_metrics(e: Env, commitment_id: String) -> Option<HealthMetrics> {
-        let key = DataKey::HealthMetrics(commitment_id);
-        e.storage().persistent().get(&key)
+        Self::read_health_metrics(&e, &commitment_id)
+    }
+
+    /// Read stored health metrics, backfilling entries from before the
+    /// worst-drawdown tracking: a missing `max_drawdown_percent` is taken
+    /// from the last `drawdown_percent`, never below it, and a negative
+    /// `volatility_exposure` reads as 0.
+    fn read_health_metrics(e: &Env, commitment_id: &String) -> Option<HealthMetrics> {
+        let raw = e
+            .storage()
+            .persistent()
+            .get::<_, Map<Symbol, Val>>(&DataKey::HealthMetrics(commitment_id.clone()))?;
+        let field = |name: &str| -> Option<Val> { raw.get(Symbol::new(e, name)) };
+        let int = |name: &str| -> Option<i128> {
+            field(name).and_then(|v| i128::try_from_val(e, &v).ok())
+        };
+        let drawdown_percent = int("drawdown_percent")?;
+        Some(HealthMetrics {
+            commitment_id: commitment_id.clone(),
+            current_value: int("current_value")?,
+            initial_value: int("initial_value")?,
+            drawdown_percent,
+            max_drawdown_percent: int("max_drawdown_percent")
+                .unwrap_or(0)
+                .max(drawdown_percent),
+            fees_generated: int("fees_generated")?,
+            volatility_exposure: int("volatility_exposure")?.max(0),
+            last_attestation: field("last_attestation")
+                .and_then(|v| u64::try_from_val(e, &v).ok())?,
+            compliance_score: field("compliance_score")
+                .and_then(|v| u32::try_from_val(e, &v).ok())?,
+        })
     }
 
     /// Store health metrics for a commitment
@@ -462,6 +540,21 @@ impl AttestationEngineContract {
         }
     }
 
+    /// Check the data map against the MAX_ATTESTATION_DATA_* bounds
+    fn attestation_data_within_limits(data: &Map<String, String>) -> bool {
+        if data.len() > MAX_ATTESTATION_DATA_ENTRIES {
+            return false;
+        }
+        for (key, value) in data.iter() {
+            if key.len() > MAX_ATTESTATION_DATA_KEY_LEN
+                || value.len() > MAX_ATTESTATION_DATA_VALUE_LEN
+            {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Check if commitment exists in core contract
     fn commitment_exists(e: &Env, commitment_id: &String) -> bool {
         let commitment_core: Address = match e.storage().instance().get(&DataKey::CoreContract) {
@@ -493,21 +586,18 @@ impl AttestationEngineContract {
     /// Update health metrics after an attestation
     fn update_health_metrics(e: &Env, commitment_id: &String, attestation: &Attestation) {
         // Get or create health metrics
-        let key = DataKey::HealthMetrics(commitment_id.clone());
-        let mut metrics: HealthMetrics =
-            e.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or_else(|| HealthMetrics {
-                    commitment_id: commitment_id.clone(),
-                    current_value: 0,
-                    initial_value: 0,
-                    drawdown_percent: 0,
-                    fees_generated: 0,
-                    volatility_exposure: 0,
-                    last_attestation: 0,
-                    compliance_score: 100,
-                });
+        let mut metrics: HealthMetrics = Self::read_health_metrics(e, commitment_id)
+            .unwrap_or_else(|| HealthMetrics {
+                commitment_id: commitment_id.clone(),
+                current_value: 0,
+                initial_value: 0,
+                drawdown_percent: 0,
+                max_drawdown_percent: 0,
+                fees_generated: 0,
+                volatility_exposure: 0,
+                last_attestation: 0,
+                compliance_score: 100,
+            });
 
         // Update last_attestation timestamp
         metrics.last_attestation = attestation.timestamp;
@@ -523,11 +613,26 @@ impl AttestationEngineContract {
             if let Some(fee_str) = attestation.data.get(fee_amount_key) {
                 // Parse fee amount from string
                 if let Some(fee_amount) = Self::parse_i128_from_string(e, &fee_str) {
+                    let fees_before = metrics.fees_generated;
                     metrics.fees_generated = metrics
                         .fees_generated
                         .checked_add(fee_amount)
                         .unwrap_or(metrics.fees_generated);
 
+                    // Flat bonus the first time cumulative fees reach the threshold
+                    let min_fee_threshold = Self::read_min_fee_threshold(e, commitment_id);
+                    if min_fee_threshold > 0
+                        && fees_before < min_fee_threshold
+                        && metrics.fees_generated >= min_fee_threshold
+                    {
+                        metrics.compliance_score = core::cmp::min(
+                            100,
+                            metrics
+                                .compliance_score
+                                .saturating_add(FEE_THRESHOLD_BONUS as u32),
+                        );
+                    }
+
                     // Update global total fees analytics
                     let total_fees: i128 =
                         e.storage().instance().get(&DataKey::TotalFees).unwrap_or(0);
@@ -541,6 +646,8 @@ impl AttestationEngineContract {
             if let Some(drawdown_str) = attestation.data.get(drawdown_percent_key) {
                 if let Some(drawdown_val) = Self::parse_i128_from_string(e, &drawdown_str) {
                     metrics.drawdown_percent = drawdown_val;
+                    metrics.max_drawdown_percent =
+                        core::cmp::max(metrics.max_drawdown_percent, drawdown_val);
                 }
             }
         } else if attestation.attestation_type == violation {
@@ -571,7 +678,54 @@ impl AttestationEngineContract {
         }
 
         // Store updated metrics
-        e.storage().persistent().set(&key, &metrics);
+        Self::store_health_metrics(e, &metrics);
+
+        // Any new attestation invalidates the cached compliance score
+        e.storage()
+            .persistent()
+            .set(&DataKey::ScoreDirty(commitment_id.clone()), &true);
+    }
+
+    /// The commitment's `rules.min_fee_threshold` from core (0 if unreadable)
+    fn read_min_fee_threshold(e: &Env, commitment_id: &String) -> i128 {
+        let Some(commitment_core) = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::CoreContract)
+        else {
+            return 0;
+        };
+        let mut args = Vec::new(e);
+        args.push_back(commitment_id.clone().into_val(e));
+        match e.try_invoke_contract::<Commitment, soroban_sdk::Error>(
+            &commitment_core,
+            &Symbol::new(e, "get_commitment"),
+            args,
+        ) {
+            Ok(Ok(commitment)) => commitment.rules.min_fee_threshold,
+            _ => 0,
+        }
+    }
+
+    /// Return the cached compliance inputs, recomputing them if an
+    /// attestation was recorded since they were built
+    fn cached_compliance(e: &Env, commitment_id: &String) -> ComplianceCache {
+        let cache_key = DataKey::ComplianceCache(commitment_id.clone());
+        let dirty_key = DataKey::ScoreDirty(commitment_id.clone());
+        if !e.storage().persistent().has(&dirty_key) {
+            if let Some(cache) = e.storage().persistent().get(&cache_key) {
+                return cache;
+            }
+        }
+
+        let attestations = Self::get_attestations(e.clone(), commitment_id.clone());
+        let cache = ComplianceCache {
+            score: Self::compute_compliance_score(e, commitment_id, &attestations),
+            violation_count: Self::count_violations(e, &attestations),
+        };
+        e.storage().persistent().set(&cache_key, &cache);
+        e.storage().persistent().remove(&dirty_key);
+        cache
     }
 
     /// Parse i128 from String (optimized implementation)
@@ -668,6 +822,12 @@ impl AttestationEngineContract {
             return Err(AttestationError::InvalidCommitmentId);
         }
 
+        // 4b. Metrics closed at settlement take no further attestations
+        if Self::is_finalized(e.clone(), commitment_id.clone()) {
+            e.storage().instance().remove(&DataKey::ReentrancyGuard);
+            return Err(AttestationError::MetricsFinalized);
+        }
+
         // 5. Validate commitment exists in core contract
         if !Self::commitment_exists(&e, &commitment_id) {
             e.storage().instance().remove(&DataKey::ReentrancyGuard);
@@ -685,6 +845,10 @@ impl AttestationEngineContract {
             e.storage().instance().remove(&DataKey::ReentrancyGuard);
             return Err(AttestationError::InvalidAttestationData);
         }
+        if !Self::attestation_data_within_limits(&data) {
+            e.storage().instance().remove(&DataKey::ReentrancyGuard);
+            return Err(AttestationError::AttestationDataTooLarge);
+        }
 
         // 7b. Collect attestation verification fee if configured
         let fee_amount: i128 = e
@@ -801,6 +965,36 @@ impl AttestationEngineContract {
             .unwrap_or_else(|| Vec::new(&e))
     }
 
+    /// Get the attestations of one type for a commitment
+    ///
+    /// # Arguments
+    /// * `commitment_id` - The commitment to read
+    /// * `attestation_type` - e.g. "drawdown"
+    /// * `start` - Position of the first stored attestation to scan
+    /// * `limit` - Maximum number of stored attestations to scan
+    ///
+    /// # Returns
+    /// The matches within that window, oldest first. The window is over all
+    /// stored attestations, so a page may hold fewer than `limit` matches;
+    /// advance `start` by `limit` to continue.
+    pub fn get_attestations_by_type(
+        e: Env,
+        commitment_id: String,
+        attestation_type: String,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Attestation> {
+        let page: Vec<Attestation> =
+            Storage::paginate_index(&e, &DataKey::Attestations(commitment_id), start, limit);
+        let mut matching = Vec::new(&e);
+        for attestation in page.iter() {
+            if attestation.attestation_type == attestation_type {
+                matching.push_back(attestation);
+            }
+        }
+        matching
+    }
+
     /// Get attestation count for a commitment
     pub fn get_attestation_count(e: Env, commitment_id: String) -> u64 {
         let key = DataKey::AttestationCounter(commitment_id);
@@ -833,17 +1027,9 @@ impl AttestationEngineContract {
         let initial_value = commitment.amount; // Using amount as initial value
         let current_value = commitment.current_value;
 
-        // Calculate drawdown percentage: ((initial - current) / initial) * 100
-        // Handle zero initial value to prevent division by zero
-        let drawdown_percent = if initial_value > 0 {
-            let diff = initial_value.checked_sub(current_value).unwrap_or(0);
-            diff.checked_mul(100)
-                .unwrap_or(0)
-                .checked_div(initial_value)
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        // Drawdown is the negated PnL: positive for a loss, negative for a
+        // gain, 0 when there is no initial value
+        let drawdown_percent = -SafeMath::pnl_percent(initial_value, current_value);
 
         // Sum fees from fee attestations
         // Extract fee_amount from data map where key is "fee_amount"
@@ -887,7 +1073,13 @@ impl AttestationEngineContract {
         let compliance_score = Self::calculate_compliance_score(e.clone(), commitment_id.clone());
 
         let stored = Self::get_stored_health_metrics(e.clone(), commitment_id.clone());
-        let (fees_generated, volatility_exposure, last_attestation, stored_compliance) = stored
+        let (
+            fees_generated,
+            volatility_exposure,
+            last_attestation,
+            stored_compliance,
+            stored_max_drawdown,
+        ) = stored
             .as_ref()
             .map(|m| {
                 (
@@ -895,15 +1087,17 @@ impl AttestationEngineContract {
                     m.volatility_exposure,
                     m.last_attestation,
                     m.compliance_score,
+                    m.max_drawdown_percent,
                 )
             })
-            .unwrap_or((0, 0, last_attestation, compliance_score));
+            .unwrap_or((0, 0, last_attestation, compliance_score, 0));
 
         HealthMetrics {
             commitment_id,
             current_value,
             initial_value,
             drawdown_percent,
+            max_drawdown_percent: core::cmp::max(stored_max_drawdown, drawdown_percent),
             fees_generated,
             volatility_exposure,
             last_attestation,
@@ -915,6 +1109,27 @@ impl AttestationEngineContract {
         }
     }
 
+    /// Get health metrics for several commitments in one call
+    ///
+    /// # Arguments
+    /// * `commitment_ids` - At most `MAX_HEALTH_METRICS_BATCH` commitment IDs
+    ///
+    /// # Returns
+    /// Metrics in the same order as `commitment_ids`
+    pub fn get_health_metrics_batch(
+        e: Env,
+        commitment_ids: Vec<String>,
+    ) -> Result<Vec<HealthMetrics>, AttestationError> {
+        if commitment_ids.len() > MAX_HEALTH_METRICS_BATCH {
+            return Err(AttestationError::BatchTooLarge);
+        }
+        let mut metrics = Vec::new(&e);
+        for commitment_id in commitment_ids.iter() {
+            metrics.push_back(Self::get_health_metrics(e.clone(), commitment_id));
+        }
+        Ok(metrics)
+    }
+
     /// Verify commitment compliance
     ///
     /// # Arguments
@@ -923,10 +1138,23 @@ impl AttestationEngineContract {
     /// # Returns
     /// `true` if compliant, `false` otherwise
     pub fn verify_compliance(e: Env, commitment_id: String) -> bool {
+        Self::verify_compliance_detailed(e, commitment_id).0
+    }
+
+    /// Verify commitment compliance and report why it failed
+    ///
+    /// # Arguments
+    /// * `commitment_id` - The commitment to verify
+    ///
+    /// # Returns
+    /// `(is_compliant, violation_count, drawdown_exceeded)`; all false/zero if
+    /// the commitment cannot be loaded from the core contract. The score and
+    /// violation count come from the compliance cache; drawdown is always live.
+    pub fn verify_compliance_detailed(e: Env, commitment_id: String) -> (bool, u32, bool) {
         // Get commitment from core contract
         let commitment_core: Address = match e.storage().instance().get(&DataKey::CoreContract) {
             Some(addr) => addr,
-            None => return false,
+            None => return (false, 0, false),
         };
 
         // Get commitment details
@@ -938,31 +1166,28 @@ impl AttestationEngineContract {
             args,
         ) {
             Ok(Ok(val)) => val,
-            _ => return false,
+            _ => return (false, 0, false),
         };
 
         let commitment: Commitment = match commitment_val.try_into_val(&e) {
             Ok(c) => c,
-            Err(_) => return false,
+            Err(_) => return (false, 0, false),
         };
 
-        // Get health metrics
-        let metrics = Self::get_health_metrics(e.clone(), commitment_id);
+        // Score and violation count only move with new attestations
+        let cache = Self::cached_compliance(&e, &commitment_id);
 
-        // Check compliance rules
+        // Check compliance rules against the live value
         let max_loss = commitment.rules.max_loss_percent as i128;
+        let drawdown_percent = -SafeMath::pnl_percent(commitment.amount, commitment.current_value);
 
         // Check if drawdown exceeds max loss
-        if metrics.drawdown_percent > max_loss {
-            return false;
-        }
+        let drawdown_exceeded = drawdown_percent > max_loss;
 
         // Check compliance score threshold (below 50 is non-compliant)
-        if metrics.compliance_score < 50 {
-            return false;
-        }
+        let is_compliant = !drawdown_exceeded && cache.score >= 50;
 
-        true
+        (is_compliant, cache.violation_count, drawdown_exceeded)
     }
 
     /// Record fee generation
@@ -1017,6 +1242,10 @@ impl AttestationEngineContract {
         commitment_id: String,
         drawdown_percent: i128,
     ) -> Result<(), AttestationError> {
+        if Self::is_finalized(e.clone(), commitment_id.clone()) {
+            return Err(AttestationError::MetricsFinalized);
+        }
+
         // Get commitment to check max_loss_percent
         let commitment_core: Address = e
             .storage()
@@ -1033,6 +1262,11 @@ impl AttestationEngineContract {
             .try_into_val(&e)
             .map_err(|_| AttestationError::CommitmentNotFound)?;
 
+        // Drawdowns only make sense while funds are still committed
+        if commitment.status != String::from_str(&e, "active") {
+            return Err(AttestationError::CommitmentNotActive);
+        }
+
         let max_loss = commitment.rules.max_loss_percent as i128;
         let is_compliant = drawdown_percent <= max_loss;
 
@@ -1064,9 +1298,12 @@ impl AttestationEngineContract {
             .persistent()
             .get(&atts_key)
             .unwrap_or_else(|| Vec::new(&e));
-        attestations.push_back(drawdown_attestation);
+        attestations.push_back(drawdown_attestation.clone());
         e.storage().persistent().set(&atts_key, &attestations);
 
+        // Persist drawdown and the worst point seen so far
+        Self::update_health_metrics(&e, &commitment_id, &drawdown_attestation);
+
         // Emit DrawdownRecorded event
         e.events().publish(
             (Symbol::new(&e, "DrawdownRecorded"), commitment_id),
@@ -1076,6 +1313,15 @@ impl AttestationEngineContract {
         Ok(())
     }
 
+    /// Count attestations that are non-compliant or of type "violation"
+    fn count_violations(e: &Env, attestations: &Vec<Attestation>) -> u32 {
+        let violation = String::from_str(e, "violation");
+        attestations
+            .iter()
+            .filter(|att| !att.is_compliant || att.attestation_type == violation)
+            .count() as u32
+    }
+
     /// Convert i128 to String (helper function)
     fn i128_to_string(e: &Env, value: i128) -> String {
         if value == 0 {
@@ -1117,6 +1363,26 @@ impl AttestationEngineContract {
 
     /// Calculate compliance score (0-100)
     ///
+    /// The result is cached per commitment and only recomputed after a new
+    /// attestation, drawdown or fee is recorded (see `is_score_dirty`), so
+    /// value or time changes alone do not move it.
+    ///
+    /// Once health metrics are stored for the commitment (any `attest`), the
+    /// stored score is returned. `attest` maintains it starting from 100:
+    /// - -10/-20/-30 per low/medium/high severity violation
+    /// - +1 per compliant non-violation attestation
+    /// - +`FEE_THRESHOLD_BONUS` once, when cumulative recorded fees first
+    ///   reach `min_fee_threshold`
+    /// - capped at 100
+    ///
+    /// Without stored metrics the score is recomputed as:
+    /// - start at 100
+    /// - -20 per violation (non-compliant or "violation" attestation)
+    /// - -1 per percentage point of drawdown beyond `max_loss_percent`
+    /// - +`FEE_THRESHOLD_BONUS` if summed `fee_amount`s reach `min_fee_threshold`
+    /// - +10 while the commitment is within its duration
+    /// - clamped to [0, 100]
+    ///
     /// # Formal Verification
     /// **Preconditions:**
     /// - `commitment_id` exists
@@ -1125,7 +1391,7 @@ impl AttestationEngineContract {
     /// - Returns value in range [0, 100]
     /// - Score decreases with violations
     /// - Score decreases if drawdown exceeds threshold
-    /// - Pure function (no state changes)
+    /// - Only writes the compliance cache
     ///
     /// **Invariants Maintained:**
     /// - Score always in valid range [0, 100]
@@ -1134,13 +1400,91 @@ impl AttestationEngineContract {
     /// - SP-4: State consistency (read-only)
     /// - SP-3: Arithmetic safety
     pub fn calculate_compliance_score(e: Env, commitment_id: String) -> u32 {
-        // First check if we have stored metrics with a compliance score
-        let metrics_key = DataKey::HealthMetrics(commitment_id.clone());
-        if let Some(stored_metrics) = e
+        if let Some(score) = Self::get_final_score(e.clone(), commitment_id.clone()) {
+            return score;
+        }
+        Self::cached_compliance(&e, &commitment_id).score
+    }
+
+    /// Close a commitment's health metrics at settlement (core contract only)
+    ///
+    /// Freezes the current compliance score, which `calculate_compliance_score`
+    /// returns from then on, and rejects further `attest` / `record_*` calls
+    /// with `MetricsFinalized`. Only the engine's own stored metrics are read
+    /// (a commitment never attested keeps the starting score of 100), so core
+    /// can call this while settling without being re-entered.
+    ///
+    /// # Returns
+    /// The final compliance score
+    pub fn finalize(
+        e: Env,
+        caller: Address,
+        commitment_id: String,
+    ) -> Result<u32, AttestationError> {
+        caller.require_auth();
+        let commitment_core: Address = e
             .storage()
+            .instance()
+            .get(&DataKey::CoreContract)
+            .ok_or(AttestationError::NotInitialized)?;
+        if caller != commitment_core {
+            return Err(AttestationError::Unauthorized);
+        }
+        if Self::is_finalized(e.clone(), commitment_id.clone()) {
+            return Err(AttestationError::MetricsFinalized);
+        }
+
+        let score = Self::get_stored_health_metrics(e.clone(), commitment_id.clone())
+            .map(|metrics| metrics.compliance_score)
+            .unwrap_or(100);
+        e.storage()
             .persistent()
-            .get::<DataKey, HealthMetrics>(&metrics_key)
-        {
+            .set(&DataKey::FinalScore(commitment_id.clone()), &score);
+
+        e.events().publish(
+            (Symbol::new(&e, "MetricsFinalized"), commitment_id),
+            (score, e.ledger().timestamp()),
+        );
+        Ok(score)
+    }
+
+    /// Whether a commitment's health metrics were closed by `finalize`
+    pub fn is_finalized(e: Env, commitment_id: String) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::FinalScore(commitment_id))
+    }
+
+    /// Get the score frozen by `finalize`, if the metrics are closed
+    pub fn get_final_score(e: Env, commitment_id: String) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::FinalScore(commitment_id))
+    }
+
+    /// Whether the compliance score will be recomputed on the next read
+    ///
+    /// # Returns
+    /// `true` if no score has been cached yet or an attestation was recorded
+    /// since the last computation
+    pub fn is_score_dirty(e: Env, commitment_id: String) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::ScoreDirty(commitment_id.clone()))
+            || !e
+                .storage()
+                .persistent()
+                .has(&DataKey::ComplianceCache(commitment_id))
+    }
+
+    /// Compute the compliance score from scratch
+    fn compute_compliance_score(
+        e: &Env,
+        commitment_id: &String,
+        attestations: &Vec<Attestation>,
+    ) -> u32 {
+        // First check if we have stored metrics with a compliance score
+        if let Some(stored_metrics) = Self::read_health_metrics(e, commitment_id) {
             return stored_metrics.compliance_score;
         }
 
@@ -1149,27 +1493,19 @@ impl AttestationEngineContract {
 
         // Call get_commitment on commitment_core contract
         // Using Symbol::new() for function name longer than 9 characters
-        let mut args = Vec::new(&e);
-        args.push_back(commitment_id.clone().into_val(&e));
+        let mut args = Vec::new(e);
+        args.push_back(commitment_id.clone().into_val(e));
         let commitment_val: Val =
-            e.invoke_contract(&commitment_core, &Symbol::new(&e, "get_commitment"), args);
+            e.invoke_contract(&commitment_core, &Symbol::new(e, "get_commitment"), args);
 
         // Convert Val to Commitment
-        let commitment: Commitment = commitment_val.try_into_val(&e).unwrap();
-
-        // Get all attestations
-        let attestations = Self::get_attestations(e.clone(), commitment_id.clone());
+        let commitment: Commitment = commitment_val.try_into_val(e).unwrap();
 
         // Base score: 100
         let mut score: i32 = 100;
 
         // Count violations: -20 per violation
-        let violation_count = attestations
-            .iter()
-            .filter(|att| {
-                !att.is_compliant || att.attestation_type == String::from_str(&e, "violation")
-            })
-            .count() as i32;
+        let violation_count = Self::count_violations(e, attestations) as i32;
         score = score
             .checked_sub(violation_count.checked_mul(20).unwrap_or(0))
             .unwrap_or(0);
@@ -1179,53 +1515,31 @@ impl AttestationEngineContract {
         let current_value = commitment.current_value;
         let max_loss_percent = commitment.rules.max_loss_percent as i128;
 
-        if initial_value > 0 {
-            let drawdown_percent = {
-                let diff = initial_value.checked_sub(current_value).unwrap_or(0);
-                diff.checked_mul(100)
-                    .unwrap_or(0)
-                    .checked_div(initial_value)
-                    .unwrap_or(0)
-            };
-
-            if drawdown_percent > max_loss_percent {
-                let over_threshold = drawdown_percent.checked_sub(max_loss_percent).unwrap_or(0);
-                score = score.checked_sub(over_threshold as i32).unwrap_or(0);
-            }
+        let drawdown_percent = -SafeMath::pnl_percent(initial_value, current_value);
+        if drawdown_percent > max_loss_percent {
+            let over_threshold = drawdown_percent.checked_sub(max_loss_percent).unwrap_or(0);
+            score = score.checked_sub(over_threshold as i32).unwrap_or(0);
         }
 
-        // Calculate fee generation vs expectations: +1 per % of expected fees
+        // Fee generation: flat bonus once fees reach the commitment's threshold
         let min_fee_threshold = commitment.rules.min_fee_threshold;
-        // Get fees from health metrics (which sums from attestations)
-        // We'll calculate this from the attestations directly
-        let total_fees: i128 = 0;
-        let fee_key = String::from_str(&e, "fee_amount");
-
+        let fee_generation = String::from_str(e, "fee_generation");
+        let fee_key = String::from_str(e, "fee_amount");
+        let mut total_fees: i128 = 0;
         for att in attestations.iter() {
-            if att.attestation_type == String::from_str(&e, "fee_generation") {
-                // Extract fee from data map
-                // Since Map<String, String> stores strings, we need to parse
-                // For this implementation, we'll use a simplified approach:
-                // If fee_amount exists in data, we'll try to extract it
-                // In production, fees should be stored as i128 in a separate field
-                if let Some(_fee_str) = att.data.get(fee_key.clone()) {
-                    // Parse would be needed here - for now, we'll use 0
-                    // This is acceptable as fee tracking requires proper implementation
-                    // of the attest() function to store fees correctly
+            if att.attestation_type == fee_generation {
+                if let Some(fee) = att
+                    .data
+                    .get(fee_key.clone())
+                    .and_then(|fee_str| Self::parse_i128_from_string(e, &fee_str))
+                {
+                    total_fees = total_fees.saturating_add(fee);
                 }
             }
         }
 
-        // Only add fee bonus if we have fees and a threshold
-        if min_fee_threshold > 0 && total_fees > 0 {
-            let fee_percent = total_fees
-                .checked_mul(100)
-                .unwrap_or(0)
-                .checked_div(min_fee_threshold)
-                .unwrap_or(0);
-            // Cap the bonus to prevent excessive score inflation
-            let bonus = if fee_percent > 100 { 100 } else { fee_percent };
-            score = score.checked_add(bonus as i32).unwrap_or(100);
+        if min_fee_threshold > 0 && total_fees >= min_fee_threshold {
+            score = score.saturating_add(FEE_THRESHOLD_BONUS);
         }
 
         // Duration adherence: +10 if on track
@@ -1260,7 +1574,7 @@ impl AttestationEngineContract {
 
         // Emit compliance score update event
         e.events().publish(
-            (symbol_short!("ScoreUpd"), commitment_id),
+            (symbol_short!("ScoreUpd"), commitment_id.clone()),
             (score as u32, e.ledger().timestamp()),
         );
 
@@ -1443,6 +1757,26 @@ impl AttestationEngineContract {
                 }
             }
 
+            // Closed metrics take no further attestations
+            if Self::is_finalized(e.clone(), params.commitment_id.clone()) {
+                if mode == BatchMode::Atomic {
+                    e.storage().instance().remove(&DataKey::ReentrancyGuard);
+                    errors.push_back(BatchError {
+                        index: i,
+                        error_code: AttestationError::MetricsFinalized as u32,
+                        context: String::from_str(&e, "metrics_finalized"),
+                    });
+                    return BatchResultVoid::failure(&e, errors);
+                } else {
+                    errors.push_back(BatchError {
+                        index: i,
+                        error_code: AttestationError::MetricsFinalized as u32,
+                        context: String::from_str(&e, "metrics_finalized"),
+                    });
+                    continue;
+                }
+            }
+
             // Validate attestation type
             if !Self::is_valid_attestation_type(&e, &params.attestation_type) {
                 if mode == BatchMode::Atomic {
@@ -1482,6 +1816,24 @@ impl AttestationEngineContract {
                     continue;
                 }
             }
+            if !Self::attestation_data_within_limits(&params.data) {
+                if mode == BatchMode::Atomic {
+                    e.storage().instance().remove(&DataKey::ReentrancyGuard);
+                    errors.push_back(BatchError {
+                        index: i,
+                        error_code: AttestationError::AttestationDataTooLarge as u32,
+                        context: String::from_str(&e, "data_too_large"),
+                    });
+                    return BatchResultVoid::failure(&e, errors);
+                } else {
+                    errors.push_back(BatchError {
+                        index: i,
+                        error_code: AttestationError::AttestationDataTooLarge as u32,
+                        context: String::from_str(&e, "data_too_large"),
+                    });
+                    continue;
+                }
+            }
 
             // Create attestation record
             let attestation = Attestation {
@@ -1761,5 +2113,5 @@ fn require_valid_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) -> Result<(), Attest
 
 #[cfg(all(test, feature = "benchmark"))]
 mod benchmarks;
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
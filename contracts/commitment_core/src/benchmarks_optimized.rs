@@ -101,11 +101,12 @@ fn benchmark_commitment_id_generation() {
     let env = Env::default();
     env.budget().reset_unlimited();
     
+    let owner = Address::generate(&env);
     let cpu_before = env.budget().cpu_instruction_cost();
     
     // Generate 100 commitment IDs
     for i in 0..100 {
-        let _id = CommitmentCoreContract::generate_commitment_id(&env, i);
+        let _id = CommitmentCoreContract::generate_commitment_id(&env, &owner, i);
     }
     
     let cpu_after = env.budget().cpu_instruction_cost();
@@ -232,7 +233,7 @@ fn benchmark_settle_function() {
     let cpu_before = env.budget().cpu_instruction_cost();
     let mem_before = env.budget().memory_bytes_cost();
     
-    client.settle(&commitment_id);
+    client.settle(&commitment_id, &owner);
     
     let cpu_after = env.budget().cpu_instruction_cost();
     let mem_after = env.budget().memory_bytes_cost();
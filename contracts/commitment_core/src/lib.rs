@@ -1,11 +1,48 @@
 #![no_std]
 
-use shared_utils::{emit_error_event, Pausable, RateLimiter, SafeMath, TimeUtils, Validation};
+use shared_utils::{
+    emit_error_event, fee_from_bps, Events, Pausable, RateLimiter, RateMode, SafeMath, TimeUtils,
+    Validation, ALL_WEEKDAYS_MASK, BPS_MAX, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, Address, Env,
-    IntoVal, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Default upper bound on `rules.duration_days` (~10 years)
+pub const DEFAULT_MAX_DURATION_DAYS: u32 = 3650;
+
+/// Account nobody holds keys for; the admin is set to this on `renounce_admin`.
+const BURN_ADDRESS: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+/// Size bounds for owner-set labels and tags (bytes / entries)
+pub const MAX_LABEL_LEN: u32 = 64;
+pub const MAX_TAGS: u32 = 5;
+pub const MAX_TAG_LEN: u32 = 32;
+
+/// Upper bound on contributors to a shared commitment; settlement pays each
+pub const MAX_SHARED_CONTRIBUTORS: u32 = 10;
+
+/// Upper bound on ids per `get_commitments` call
+pub const MAX_BULK_LOOKUP: u32 = 50;
+
+/// Most recent timelock actions inspected when looking for an approval
+pub const MAX_TIMELOCK_SCAN: u32 = 20;
+
+/// Upper bound on ids per `settle_batch` call; each settlement makes several
+/// cross-contract calls
+pub const MAX_SETTLE_BATCH: u32 = 20;
+
+/// `ActionType` discriminants in the timelock contract
+const TIMELOCK_ADMIN_CHANGE: u32 = 0;
+const TIMELOCK_PARAMETER_CHANGE: u32 = 1;
+
+/// `SettlementReason` discriminants in the NFT contract
+const NFT_SETTLED_MATURED: u32 = 1;
+const NFT_SETTLED_EARLY_EXIT: u32 = 3;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -27,13 +64,48 @@ pub enum CommitmentError {
     NotExpired = 15,
     ValueUpdateViolation = 16,
     NotAuthorizedUpdater = 17,
+    InsufficientAllowance = 18,
+    AdminChangeNotApproved = 19,
+    InvalidWasmHash = 20,
+    InvalidVersion = 21,
+    AlreadyMigrated = 22,
+    NoViolation = 23,
+    InvalidPenaltySplit = 24,
+    RewardAlreadyClaimed = 25,
+    InvalidNftContract = 26,
+    ComplianceScoreTooLow = 27,
+    NoPendingAdmin = 28,
+    InsufficientInsurance = 29,
+    InvalidCalendar = 30,
+    SlippageExceeded = 31,
+    LabelTooLarge = 32,
+    UpdateTooSoon = 33,
+    GuardResetNotApproved = 34,
+    AssetNotAllowed = 35,
+    OracleNotConfigured = 36,
+    SwapRouterNotConfigured = 37,
+    SwapOutputTooLow = 38,
+    CommitmentFrozen = 39,
+    PenaltyWaiverNotApproved = 40,
+    HoldPeriodActive = 41,
+    ArithmeticOverflow = 42,
+    InvalidContributors = 43,
+    InvalidEarlyExitPenalty = 44,
+    BatchTooLarge = 45,
+    InsufficientAttestations = 46,
+    PoolNotApproved = 47,
+    InvalidStatusTransition = 48,
+    InvalidAutoAllocation = 49,
+    NothingToClaim = 50,
 }
 
 impl CommitmentError {
     /// Human-readable message for debugging and error events.
     pub fn message(&self) -> &'static str {
         match self {
-            CommitmentError::InvalidDuration => "Invalid duration: must be greater than zero",
+            CommitmentError::InvalidDuration => {
+                "Invalid duration: must be greater than zero and within the maximum"
+            }
             CommitmentError::InvalidMaxLossPercent => "Invalid max loss: must be 0-100",
             CommitmentError::InvalidCommitmentType => "Invalid commitment type",
             CommitmentError::InvalidAmount => "Invalid amount: must be greater than zero",
@@ -50,6 +122,55 @@ impl CommitmentError {
             CommitmentError::NotExpired => "Commitment has not expired yet",
             CommitmentError::ValueUpdateViolation => "Commitment has  value update voilation",
             CommitmentError::NotAuthorizedUpdater => "Commitment has not auth updater",
+            CommitmentError::InsufficientAllowance => "Insufficient allowance for contract",
+            CommitmentError::AdminChangeNotApproved => {
+                "Admin change not approved by an executed timelock action"
+            }
+            CommitmentError::InvalidWasmHash => "Invalid WASM hash",
+            CommitmentError::InvalidVersion => "Invalid version for migration",
+            CommitmentError::AlreadyMigrated => "Contract already migrated to current version",
+            CommitmentError::NoViolation => "Commitment has no rule violation",
+            CommitmentError::InvalidPenaltySplit => "Invalid penalty split: bps must sum to 10000",
+            CommitmentError::RewardAlreadyClaimed => "Reward already claimed",
+            CommitmentError::InvalidNftContract => "Invalid NFT contract: probe call failed",
+            CommitmentError::ComplianceScoreTooLow => {
+                "Compliance score below minimum for allocation"
+            }
+            CommitmentError::NoPendingAdmin => "No pending admin proposal",
+            CommitmentError::InsufficientInsurance => {
+                "Insurance fund cannot cover the safe commitment floor"
+            }
+            CommitmentError::InvalidCalendar => {
+                "Invalid calendar: at least one weekday must allow settlement"
+            }
+            CommitmentError::SlippageExceeded => "Pool returned fewer shares than min_shares",
+            CommitmentError::LabelTooLarge => "Label or tags exceed size limits",
+            CommitmentError::UpdateTooSoon => "Value update too soon after the previous one",
+            CommitmentError::GuardResetNotApproved => {
+                "Guard reset not approved by an executed timelock action"
+            }
+            CommitmentError::AssetNotAllowed => "Asset is not on the allow-list",
+            CommitmentError::OracleNotConfigured => "Price oracle not configured",
+            CommitmentError::SwapRouterNotConfigured => "Swap router not configured",
+            CommitmentError::SwapOutputTooLow => "Swap returned less than min_out",
+            CommitmentError::CommitmentFrozen => "Commitment is frozen",
+            CommitmentError::PenaltyWaiverNotApproved => {
+                "Penalty waiver not approved by an executed timelock action"
+            }
+            CommitmentError::HoldPeriodActive => "Minimum hold period has not elapsed",
+            CommitmentError::ArithmeticOverflow => "Arithmetic overflow",
+            CommitmentError::InvalidContributors => {
+                "Invalid contributors: 1-10 unique addresses with positive amounts"
+            }
+            CommitmentError::InvalidEarlyExitPenalty => "Invalid early exit penalty: must be 0-100",
+            CommitmentError::BatchTooLarge => "Too many ids in one call",
+            CommitmentError::InsufficientAttestations => "Too few attestations recorded to settle",
+            CommitmentError::PoolNotApproved => "Target pool not approved by the owner",
+            CommitmentError::InvalidStatusTransition => "Illegal commitment status transition",
+            CommitmentError::InvalidAutoAllocation => {
+                "Auto-allocation would breach the reserve ratio"
+            }
+            CommitmentError::NothingToClaim => "Nothing to claim",
         }
     }
 }
@@ -95,7 +216,156 @@ pub struct Commitment {
     pub created_at: u64,
     pub expires_at: u64,
     pub current_value: i128,
-    pub status: String, // "active", "settled", "violated", "early_exit"
+    pub status: String,    // "active", "settled", "violated", "early_exit"
+    pub label: String,     // owner-chosen, empty when unset (see `set_label`)
+    pub tags: Vec<String>, // owner-chosen (see `set_tags`)
+}
+
+/// Where a commitment is in its lifecycle, as reported by `get_lifecycle_phase`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LifecyclePhase {
+    Active,    // before expiry
+    InGrace,   // expired, within `grace_period_days` of expiry
+    PastGrace, // expired and past the grace period, not yet settled
+    Settled,
+    EarlyExited,
+    Violated,
+}
+
+/// Values of `Commitment.status`. Only `Active` may change, and only to one
+/// of the terminal states; see `require_transition`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CommitmentStatus {
+    Active,
+    Settled,
+    EarlyExit,
+    Violated,
+}
+
+impl CommitmentStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            CommitmentStatus::Active => "active",
+            CommitmentStatus::Settled => "settled",
+            CommitmentStatus::EarlyExit => "early_exit",
+            CommitmentStatus::Violated => "violated",
+        }
+    }
+
+    fn from_status(e: &Env, status: &String) -> Self {
+        [
+            CommitmentStatus::Active,
+            CommitmentStatus::Settled,
+            CommitmentStatus::EarlyExit,
+            CommitmentStatus::Violated,
+        ]
+        .into_iter()
+        .find(|candidate| *status == String::from_str(e, candidate.as_str()))
+        .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "status"))
+    }
+}
+
+/// Panic unless `from -> to` is a legal status change: Active to Settled,
+/// EarlyExit or Violated. Terminal states never change again.
+fn require_transition(e: &Env, from: CommitmentStatus, to: CommitmentStatus) {
+    let legal = from == CommitmentStatus::Active
+        && matches!(
+            to,
+            CommitmentStatus::Settled | CommitmentStatus::EarlyExit | CommitmentStatus::Violated
+        );
+    if !legal {
+        fail(e, CommitmentError::InvalidStatusTransition, "status");
+    }
+}
+
+/// Move `commitment` to `to` after checking the transition is legal.
+/// Does not persist the commitment.
+fn transition_status(e: &Env, commitment: &mut Commitment, to: CommitmentStatus) {
+    require_transition(e, CommitmentStatus::from_status(e, &commitment.status), to);
+    commitment.status = String::from_str(e, to.as_str());
+}
+
+/// One-call summary of the contract's configuration and aggregates.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractInfo {
+    pub admin: Address,
+    pub nft_contract: Address,
+    pub version: u32,
+    pub total_commitments: u64,
+    pub tvl: i128,
+    pub paused: bool,
+}
+
+/// Funds a commitment currently has deployed in a pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationRecord {
+    pub pool: Address,
+    pub amount: i128,
+    pub at: u64, // last time the allocation changed
+}
+
+/// What a commitment paid out when it closed via `settle` or `early_exit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReceipt {
+    pub commitment_id: String,
+    pub amount_paid: i128, // tokens transferred to the owner
+    pub settled_at: u64,
+    pub final_status: String, // "settled" or "early_exit"
+}
+
+/// Asset a commitment's settlement proceeds are swapped into before payout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutAssetConfig {
+    pub asset: Address,
+    pub min_out: i128, // least `asset` the swap may return
+}
+
+/// Owner-chosen label and tags for a commitment ("retirement", "house fund").
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitmentLabel {
+    pub label: String,
+    pub tags: Vec<String>,
+}
+
+/// How early exit penalties are divided between treasury and rewards pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PenaltySplit {
+    pub treasury_bps: u32,
+    pub rewards_bps: u32,
+}
+
+/// Mirror of the timelock contract's `QueuedAction` for cross-contract decoding.
+/// `action_type` is the raw `ActionType` discriminant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockAction {
+    pub id: u64,
+    pub action_type: u32,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub data: String,
+    pub queued_at: u64,
+    pub executable_at: u64,
+    pub executed: bool,
+    pub cancelled: bool,
+}
+
+/// Mirror of the price oracle's `PriceData` for cross-contract decoding.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub updated_at: u64,
+    pub decimals: u32,
+    pub confidence: i128,
 }
 
 #[contracttype]
@@ -103,16 +373,97 @@ pub struct Commitment {
 pub enum DataKey {
     Admin,
     NftContract,
-    Commitment(String),        // commitment_id -> Commitment
-    OwnerCommitments(Address), // owner -> Vec<commitment_id>
-    TotalCommitments,          // counter
-    ReentrancyGuard,           // reentrancy protection flag
-    TotalValueLocked,          // aggregate value locked across active commitments
-    AuthorizedUpdaters,        // whitelist of authorized updaters
+    Commitment(String),                  // commitment_id -> Commitment
+    OwnerCommitments(Address),           // owner -> Vec<commitment_id>
+    OwnerActiveCount(Address),           // owner -> number of commitments still "active"
+    TotalCommitments,                    // counter
+    ReentrancyGuard,                     // reentrancy protection flag
+    TotalValueLocked,                    // aggregate value locked across active commitments
+    AuthorizedUpdaters,                  // whitelist of authorized updaters
+    NftToCommitment(u32),                // nft_token_id -> commitment_id
+    MaxDurationDays,                     // upper bound on rules.duration_days
+    Timelock,                            // optional timelock contract gating admin changes
+    ConsumedTimelockAction(u64),         // timelock action ids already used for an admin change
+    Version,                             // on-chain storage layout version
+    PenaltySplit,                        // PenaltySplit for early exit penalties
+    Treasury,                            // treasury share recipient of penalties
+    RewardsPool,                         // rewards pool share recipient of penalties
+    RewardIndex(Address),                // asset -> accumulated rewards per weight (scaled)
+    RewardWeight(Address),               // asset -> total amount * duration of accruing commitments
+    RewardCarry(Address),                // asset -> rewards received while nothing was accruing
+    RewardEntry(String),                 // commitment_id -> reward index when it started accruing
+    RewardAccrued(String),               // commitment_id -> rewards earned up to settlement
+    RewardClaimed(String),               // commitment_id -> reward claimed flag
+    Allocations(String),                 // commitment_id -> Vec<AllocationRecord>
+    AttestationEngine,                   // attestation engine used for compliance checks
+    MinAllocScore,                       // minimum compliance score required to allocate
+    SettlementReceipt(String),           // commitment_id -> SettlementReceipt
+    PendingAdmin,                        // candidate proposed via propose_admin
+    InsuranceFund(Address),              // asset -> balance backing the "safe" payout floor
+    SettlementSelfWindow, // seconds after expiry during which only the owner may settle
+    SettlementCalendar,   // weekday skip mask for expiry (TimeUtils::weekday bits)
+    MinUpdateInterval,    // minimum seconds between update_value calls per commitment
+    MinHoldSeconds,       // minimum seconds after created_at before early_exit
+    LastUpdatedAt(String), // commitment_id -> timestamp of the last throttled value update
+    AllowedAsset(Address), // asset -> allow-listed for new commitments
+    PayoutHook(String),   // commitment_id -> contract receiving settlement proceeds
+    PriceOracle,          // oracle used by check_and_mark_violation
+    EntryPrice(String),   // commitment_id -> oracle price the value was last anchored to
+    OwnerValue(Address),  // owner -> sum of current_value over their active commitments
+    SwapRouter,           // DEX contract converting settlement proceeds
+    PayoutAsset(String),  // commitment_id -> PayoutAssetConfig
+    Frozen(String),       // commitment_id -> value updates and allocations blocked
+    AssetTvl(Address),    // asset -> value locked across active commitments in it
+    IdempotencyKey(Address, BytesN<32>), // (owner, key) -> commitment_id
+    PenaltyWaived(String), // commitment_id -> exit charges no penalty
+    Shares(String),       // commitment_id -> Vec<(contributor, amount)> of a shared commitment
+    MinAttestations,      // attestations the engine must hold before settle (0 = unchecked)
+    CommitmentIdAt(u64),  // creation counter -> commitment_id
+}
+
+/// Storage keys added once `DataKey` reached the 50-variant limit of a
+/// `#[contracttype]` enum.
+#[contracttype]
+#[derive(Clone)]
+pub enum ExtDataKey {
+    AllowedPools(String), // commitment_id -> Vec<Address> the owner approved for allocate
+    TotalSettledValue,    // cumulative settlement amount paid out by settle
+    AutoAllocation,       // (allocate_bps, reserve_bps) for create_commitment_auto_alloc
+    AssetCommitmentCount(Address), // asset -> number of commitments created in it
+    AssetCommitmentAt(Address, u32), // (asset, index) -> commitment_id (persistent)
+    SharedClaim(String, Address), // (commitment_id, contributor) -> payout held after a failed transfer
+}
+
+/// Where `create_commitment_internal` pulls the committed amount from.
+enum Funding {
+    /// Direct `transfer` from the owner
+    Transfer,
+    /// `transfer_from` against the owner's allowance
+    Allowance,
+    /// Direct `transfer` from each contributor of a shared commitment
+    Shared(Vec<(Address, i128)>),
 }
 
 // ─── Token helpers ────────────────────────────────────────────────────────────
 
+/// Write `n` in decimal into `buf` from `start`; returns the end index.
+fn write_decimal(buf: &mut [u8; 32], start: usize, mut n: u64) -> usize {
+    let mut digits = [0u8; 20];
+    let mut digit_count = 0;
+    loop {
+        digits[digit_count] = (n % 10) as u8 + b'0';
+        n /= 10;
+        digit_count += 1;
+        if n == 0 {
+            break;
+        }
+    }
+    for j in 0..digit_count {
+        buf[start + j] = digits[digit_count - 1 - j];
+    }
+    start + digit_count
+}
+
 /// Transfer assets from owner to contract.
 fn transfer_assets(e: &Env, from: &Address, to: &Address, asset_address: &Address, amount: i128) {
     let token_client = token::Client::new(e, asset_address);
@@ -126,36 +477,503 @@ fn transfer_assets(e: &Env, from: &Address, to: &Address, asset_address: &Addres
     token_client.transfer(from, to, &amount);
 }
 
+/// Pull assets from owner to contract using a prior SEP-41 allowance.
+fn transfer_assets_from(e: &Env, from: &Address, asset_address: &Address, amount: i128) {
+    let token_client = token::Client::new(e, asset_address);
+    let spender = e.current_contract_address();
+
+    let balance = token_client.balance(from);
+    if balance < amount {
+        log!(e, "Insufficient balance: {} < {}", balance, amount);
+        fail(
+            e,
+            CommitmentError::InsufficientBalance,
+            "transfer_assets_from",
+        );
+    }
+
+    let allowance = token_client.allowance(from, &spender);
+    if allowance < amount {
+        log!(e, "Insufficient allowance: {} < {}", allowance, amount);
+        fail(
+            e,
+            CommitmentError::InsufficientAllowance,
+            "transfer_assets_from",
+        );
+    }
+
+    token_client.transfer_from(&spender, from, &spender, &amount);
+}
+
+/// Deliver settlement proceeds for `commitment`.
+///
+/// Proceeds go to the owner, or to their payout hook if one is set. With a
+/// payout asset configured, the swap router converts them on the way and
+/// sends the output to that recipient; fewer than `min_out` tokens back
+/// reverts the settlement. A hook is then told about what it received through
+/// `on_settlement(commitment_id, amount)`; a failing hook reverts the
+/// settlement so proceeds are never stranded unannounced.
+fn pay_out(e: &Env, commitment: &Commitment, amount: i128) {
+    let contract_address = e.current_contract_address();
+    let token_client = token::Client::new(e, &commitment.asset_address);
+    let hook = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::PayoutHook(commitment.commitment_id.clone()));
+    let recipient = hook.clone().unwrap_or(commitment.owner.clone());
+
+    let payout = e
+        .storage()
+        .instance()
+        .get::<_, PayoutAssetConfig>(&DataKey::PayoutAsset(commitment.commitment_id.clone()))
+        .filter(|payout| payout.asset != commitment.asset_address);
+    let paid = match payout {
+        None => {
+            token_client.transfer(&contract_address, &recipient, &amount);
+            amount
+        }
+        Some(payout) => {
+            let router = e
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::SwapRouter)
+                .unwrap_or_else(|| {
+                    set_reentrancy_guard(e, false);
+                    fail(e, CommitmentError::SwapRouterNotConfigured, "settle")
+                });
+            token_client.transfer(&contract_address, &router, &amount);
+            let mut args = Vec::new(e);
+            args.push_back(commitment.asset_address.into_val(e));
+            args.push_back(payout.asset.into_val(e));
+            args.push_back(amount.into_val(e));
+            args.push_back(payout.min_out.into_val(e));
+            args.push_back(recipient.into_val(e));
+            let amount_out: i128 = e.invoke_contract(&router, &Symbol::new(e, "swap"), args);
+            if amount_out < payout.min_out {
+                set_reentrancy_guard(e, false);
+                fail(e, CommitmentError::SwapOutputTooLow, "settle");
+            }
+            e.events().publish(
+                (
+                    symbol_short!("PaySwap"),
+                    commitment.commitment_id.clone(),
+                    payout.asset,
+                ),
+                (amount, amount_out),
+            );
+            amount_out
+        }
+    };
+
+    if let Some(hook) = hook {
+        let mut args = Vec::new(e);
+        args.push_back(commitment.commitment_id.clone().into_val(e));
+        args.push_back(paid.into_val(e));
+        e.invoke_contract::<()>(&hook, &Symbol::new(e, "on_settlement"), args);
+    }
+}
+
+/// Split `amount` across a shared commitment's contributors pro rata to what
+/// each put in; the first contributor (the owner) takes the rounding
+/// remainder. Returns false, paying nothing, if the commitment is not shared.
+///
+/// Each transfer is attempted on its own: a contributor whose transfer fails
+/// (e.g. a de-authorized trustline) has their portion held for
+/// `claim_shared_payout` instead of reverting the payout for everyone else.
+fn pay_out_shares(e: &Env, commitment: &Commitment, amount: i128) -> bool {
+    let shares = match e
+        .storage()
+        .instance()
+        .get::<_, Vec<(Address, i128)>>(&DataKey::Shares(commitment.commitment_id.clone()))
+    {
+        Some(shares) => shares,
+        None => return false,
+    };
+
+    let token_client = token::Client::new(e, &commitment.asset_address);
+    let mut remainder = amount;
+    for (contributor, contributed) in shares.iter().skip(1) {
+        let portion = SafeMath::div(SafeMath::mul(amount, contributed), commitment.amount);
+        pay_share(
+            e,
+            &token_client,
+            &commitment.commitment_id,
+            &contributor,
+            portion,
+        );
+        remainder -= portion;
+    }
+    pay_share(
+        e,
+        &token_client,
+        &commitment.commitment_id,
+        &commitment.owner,
+        remainder,
+    );
+    true
+}
+
+/// Transfer one contributor's portion, holding it as a claim if the transfer fails.
+fn pay_share(
+    e: &Env,
+    token_client: &token::Client,
+    commitment_id: &String,
+    contributor: &Address,
+    portion: i128,
+) {
+    if portion <= 0 {
+        return;
+    }
+    let sent = matches!(
+        token_client.try_transfer(&e.current_contract_address(), contributor, &portion),
+        Ok(Ok(()))
+    );
+    if sent {
+        return;
+    }
+
+    let key = ExtDataKey::SharedClaim(commitment_id.clone(), contributor.clone());
+    let held = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+    e.storage()
+        .instance()
+        .set(&key, &SafeMath::add(held, portion));
+    e.events().publish(
+        (symbol_short!("ShareHeld"), commitment_id.clone()),
+        (contributor.clone(), portion),
+    );
+}
+
 /// Call the NFT contract mint function.
 fn call_nft_mint(
     e: &Env,
     nft_contract: &Address,
     owner: &Address,
     commitment_id: &String,
-    duration_days: u32,
-    max_loss_percent: u32,
-    commitment_type: &String,
+    rules: &CommitmentRules,
     initial_amount: i128,
     asset_address: &Address,
 ) -> u32 {
     let mut args = Vec::new(e);
     args.push_back(owner.clone().into_val(e));
     args.push_back(commitment_id.clone().into_val(e));
-    args.push_back(duration_days.into_val(e));
-    args.push_back(max_loss_percent.into_val(e));
-    args.push_back(commitment_type.clone().into_val(e));
+    args.push_back(rules.duration_days.into_val(e));
+    args.push_back(rules.max_loss_percent.into_val(e));
+    args.push_back(rules.commitment_type.clone().into_val(e));
     args.push_back(initial_amount.into_val(e));
     args.push_back(asset_address.clone().into_val(e));
+    args.push_back(rules.early_exit_penalty.into_val(e));
 
     e.invoke_contract::<u32>(nft_contract, &Symbol::new(e, "mint"), args)
 }
 
+/// Mirror a new commitment value onto its NFT.
+///
+/// Best effort: a failing NFT call must not block revaluation (and with it
+/// violation detection), so errors are ignored.
+fn sync_nft_value(e: &Env, token_id: u32, value: i128) {
+    let Some(nft_contract) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::NftContract)
+    else {
+        return;
+    };
+    let mut args = Vec::new(e);
+    args.push_back(e.current_contract_address().into_val(e));
+    args.push_back(token_id.into_val(e));
+    args.push_back(value.into_val(e));
+    let _ = e.try_invoke_contract::<(), soroban_sdk::Error>(
+        &nft_contract,
+        &Symbol::new(e, "update_current_value"),
+        args,
+    );
+}
+
+/// Current holder of a commitment's NFT, if it is no longer the owner.
+///
+/// A released NFT can be sold on, and settlement then belongs to the buyer.
+/// Best effort: a failing `owner_of` leaves the owner as the payee.
+fn moved_nft_holder(e: &Env, commitment: &Commitment) -> Option<Address> {
+    let nft_contract = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::NftContract)?;
+    let mut args = Vec::new(e);
+    args.push_back(commitment.nft_token_id.into_val(e));
+    match e.try_invoke_contract::<Address, soroban_sdk::Error>(
+        &nft_contract,
+        &Symbol::new(e, "owner_of"),
+        args,
+    ) {
+        Ok(Ok(holder)) if holder != commitment.owner => Some(holder),
+        _ => None,
+    }
+}
+
+/// Fresh oracle price for `asset`; panics (reverting the call) if it is
+/// missing or stale.
+fn read_oracle_price(e: &Env, oracle: &Address, asset: &Address) -> i128 {
+    let mut args = Vec::new(e);
+    args.push_back(asset.into_val(e));
+    args.push_back(Option::<u64>::None.into_val(e));
+    let data: OraclePrice = e.invoke_contract(oracle, &Symbol::new(e, "get_price_valid"), args);
+    data.price
+}
+
+/// Anchor a new commitment's value to the current oracle price, if an oracle
+/// is configured and has a usable price. Best effort: creation never fails
+/// because of the oracle.
+fn record_entry_price(e: &Env, commitment: &Commitment) {
+    let Some(oracle) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::PriceOracle)
+    else {
+        return;
+    };
+    let mut args = Vec::new(e);
+    args.push_back(commitment.asset_address.into_val(e));
+    args.push_back(Option::<u64>::None.into_val(e));
+    if let Ok(Ok(data)) = e.try_invoke_contract::<OraclePrice, soroban_sdk::Error>(
+        &oracle,
+        &Symbol::new(e, "get_price_valid"),
+        args,
+    ) {
+        if data.price > 0 {
+            e.storage().instance().set(
+                &DataKey::EntryPrice(commitment.commitment_id.clone()),
+                &data.price,
+            );
+        }
+    }
+}
+
+/// Enforce `MinUpdateInterval` between value writes to the same commitment
+/// and record this write's time. Every path that revalues a commitment
+/// (`update_value` and the oracle check) goes through here. With no interval
+/// configured nothing is checked or stored.
+fn require_update_interval(e: &Env, commitment_id: &String, context: &str) {
+    let min_interval = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::MinUpdateInterval)
+        .unwrap_or(0);
+    if min_interval == 0 {
+        return;
+    }
+    let now = e.ledger().timestamp();
+    let last_key = DataKey::LastUpdatedAt(commitment_id.clone());
+    if let Some(last_updated_at) = e.storage().instance().get::<_, u64>(&last_key) {
+        if now < last_updated_at.saturating_add(min_interval) {
+            fail(e, CommitmentError::UpdateTooSoon, context);
+        }
+    }
+    e.storage().instance().set(&last_key, &now);
+}
+
+/// Set an active commitment's value, keeping TVL and the NFT in step.
+fn revalue(e: &Env, commitment: &mut Commitment, new_value: i128) {
+    let old_value = commitment.current_value;
+    commitment.current_value = new_value;
+    set_commitment(e, commitment);
+    sync_nft_value(e, commitment.nft_token_id, new_value);
+
+    let current_tvl = e
+        .storage()
+        .instance()
+        .get::<_, i128>(&DataKey::TotalValueLocked)
+        .unwrap_or(0);
+    e.storage().instance().set(
+        &DataKey::TotalValueLocked,
+        &(current_tvl - old_value + new_value),
+    );
+    track_owner_value(e, &commitment.owner, new_value - old_value);
+    track_asset_tvl(e, &commitment.asset_address, new_value - old_value);
+    Events::emit_value_updated(e, &commitment.commitment_id, old_value, new_value, false);
+}
+
+/// Move an active commitment to "violated": its value leaves TVL, it stops
+/// accruing rewards and the NFT is flagged.
+fn apply_violation(e: &Env, commitment: &mut Commitment, caller: &Address) {
+    let old_status = commitment.status.clone();
+    transition_status(e, commitment, CommitmentStatus::Violated);
+    set_commitment(e, commitment);
+    retire_reward_weight(e, commitment, false);
+    track_owner_active(e, &commitment.owner, false);
+
+    let current_tvl = e
+        .storage()
+        .instance()
+        .get::<_, i128>(&DataKey::TotalValueLocked)
+        .unwrap_or(0);
+    e.storage().instance().set(
+        &DataKey::TotalValueLocked,
+        &(current_tvl - commitment.current_value),
+    );
+    track_owner_value(e, &commitment.owner, -commitment.current_value);
+    track_asset_tvl(e, &commitment.asset_address, -commitment.current_value);
+
+    sync_nft_value(e, commitment.nft_token_id, commitment.current_value);
+    notify_nft_violated(e, commitment.nft_token_id);
+
+    Events::emit_status_changed(
+        e,
+        &commitment.commitment_id,
+        &old_status,
+        &commitment.status,
+    );
+    e.events().publish(
+        (
+            symbol_short!("MarkViol"),
+            commitment.commitment_id.clone(),
+            caller.clone(),
+        ),
+        (commitment.current_value, e.ledger().timestamp()),
+    );
+}
+
+fn read_owner_active_count(e: &Env, owner: &Address) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::OwnerActiveCount(owner.clone()))
+        .unwrap_or(0)
+}
+
+/// Record a commitment opening (`true`) or leaving "active" (`false`).
+fn track_owner_active(e: &Env, owner: &Address, opened: bool) {
+    let count = read_owner_active_count(e, owner);
+    let count = if opened {
+        count.saturating_add(1)
+    } else {
+        count.saturating_sub(1)
+    };
+    e.storage()
+        .instance()
+        .set(&DataKey::OwnerActiveCount(owner.clone()), &count);
+}
+
+fn read_owner_value(e: &Env, owner: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get::<_, i128>(&DataKey::OwnerValue(owner.clone()))
+        .unwrap_or(0)
+}
+
+/// Apply `delta` to the owner's aggregate active value.
+fn track_owner_value(e: &Env, owner: &Address, delta: i128) {
+    let value = SafeMath::add(read_owner_value(e, owner), delta);
+    e.storage()
+        .instance()
+        .set(&DataKey::OwnerValue(owner.clone()), &value);
+}
+
+fn read_asset_tvl(e: &Env, asset: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get::<_, i128>(&DataKey::AssetTvl(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Apply `delta` to the asset's TVL; called wherever `TotalValueLocked` moves.
+fn track_asset_tvl(e: &Env, asset: &Address, delta: i128) {
+    let value = SafeMath::add(read_asset_tvl(e, asset), delta);
+    e.storage()
+        .instance()
+        .set(&DataKey::AssetTvl(asset.clone()), &value);
+}
+
+/// Append `commitment_id` to the asset's index. Entries live in persistent
+/// storage, one per key, so the index does not grow the instance entry that
+/// every call loads.
+fn index_asset_commitment(e: &Env, asset: &Address, commitment_id: &String) {
+    let count_key = ExtDataKey::AssetCommitmentCount(asset.clone());
+    let index = e
+        .storage()
+        .instance()
+        .get::<_, u32>(&count_key)
+        .unwrap_or(0);
+    let entry_key = ExtDataKey::AssetCommitmentAt(asset.clone(), index);
+    e.storage().persistent().set(&entry_key, commitment_id);
+    e.storage()
+        .persistent()
+        .extend_ttl(&entry_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    e.storage().instance().set(&count_key, &(index + 1));
+}
+
+fn require_not_frozen(e: &Env, commitment_id: &String, context: &str) {
+    if e.storage()
+        .instance()
+        .has(&DataKey::Frozen(commitment_id.clone()))
+    {
+        fail(e, CommitmentError::CommitmentFrozen, context);
+    }
+}
+
+/// Tell the NFT contract a commitment was marked violated.
+///
+/// Best effort, like `sync_nft_value`: core's own status is authoritative and
+/// must still change if the NFT call fails.
+fn notify_nft_violated(e: &Env, token_id: u32) {
+    let Some(nft_contract) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::NftContract)
+    else {
+        return;
+    };
+    let mut args = Vec::new(e);
+    args.push_back(e.current_contract_address().into_val(e));
+    args.push_back(token_id.into_val(e));
+    let _ = e.try_invoke_contract::<(), soroban_sdk::Error>(
+        &nft_contract,
+        &Symbol::new(e, "mark_violated"),
+        args,
+    );
+}
+
+/// Sanity-check that `nft_contract` answers the NFT `total_supply` getter so a
+/// wrong address fails at init instead of bricking every later `settle`.
+fn require_nft_contract(e: &Env, nft_contract: &Address) {
+    let result = e.try_invoke_contract::<u32, soroban_sdk::Error>(
+        nft_contract,
+        &Symbol::new(e, "total_supply"),
+        Vec::new(e),
+    );
+    if !matches!(result, Ok(Ok(_))) {
+        fail(e, CommitmentError::InvalidNftContract, "initialize");
+    }
+}
+
 // ─── Storage helpers ──────────────────────────────────────────────────────────
 
+/// Decoded field by field so commitments stored before `label` and `tags`
+/// were added still load, with both empty.
 fn read_commitment(e: &Env, commitment_id: &String) -> Option<Commitment> {
-    e.storage()
+    let raw = e
+        .storage()
         .instance()
-        .get::<_, Commitment>(&DataKey::Commitment(commitment_id.clone()))
+        .get::<_, Map<Symbol, Val>>(&DataKey::Commitment(commitment_id.clone()))?;
+    let field = |name: &str| -> Option<Val> { raw.get(Symbol::new(e, name)) };
+    let label = field("label")
+        .and_then(|v| String::try_from_val(e, &v).ok())
+        .unwrap_or(String::from_str(e, ""));
+    let tags = field("tags")
+        .and_then(|v| Vec::<String>::try_from_val(e, &v).ok())
+        .unwrap_or(Vec::new(e));
+    Some(Commitment {
+        commitment_id: field("commitment_id").and_then(|v| String::try_from_val(e, &v).ok())?,
+        owner: field("owner").and_then(|v| Address::try_from_val(e, &v).ok())?,
+        nft_token_id: field("nft_token_id").and_then(|v| u32::try_from_val(e, &v).ok())?,
+        rules: field("rules").and_then(|v| CommitmentRules::try_from_val(e, &v).ok())?,
+        amount: field("amount").and_then(|v| i128::try_from_val(e, &v).ok())?,
+        asset_address: field("asset_address").and_then(|v| Address::try_from_val(e, &v).ok())?,
+        created_at: field("created_at").and_then(|v| u64::try_from_val(e, &v).ok())?,
+        expires_at: field("expires_at").and_then(|v| u64::try_from_val(e, &v).ok())?,
+        current_value: field("current_value").and_then(|v| i128::try_from_val(e, &v).ok())?,
+        status: field("status").and_then(|v| String::try_from_val(e, &v).ok())?,
+        label,
+        tags,
+    })
 }
 
 fn set_commitment(e: &Env, commitment: &Commitment) {
@@ -165,6 +983,12 @@ fn set_commitment(e: &Env, commitment: &Commitment) {
     );
 }
 
+fn extend_commitment_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 fn has_commitment(e: &Env, commitment_id: &String) -> bool {
     e.storage()
         .instance()
@@ -206,22 +1030,213 @@ fn require_admin(e: &Env, caller: &Address) {
     }
 }
 
-fn require_authorized_updater(e: &Env, caller: &Address) {
-    caller.require_auth();
-    let updaters: Vec<Address> = e
-        .storage()
-        .instance()
-        .get::<_, Vec<Address>>(&DataKey::AuthorizedUpdaters)
-        .unwrap_or(Vec::new(e));
-    if !updaters.contains(caller) {
-        fail(e, CommitmentError::NotAuthorizedUpdater, "Unauthorized");
-    }
+/// If a timelock is configured, require an executed, unused AdminChange action
+/// calling `function` for `new_admin` and mark it consumed.
+fn require_timelock_admin_change(e: &Env, function: &str, new_admin: &Address) {
+    require_timelock_action(
+        e,
+        TIMELOCK_ADMIN_CHANGE,
+        new_admin,
+        function,
+        None,
+        CommitmentError::AdminChangeNotApproved,
+        "require_timelock_admin_change",
+    );
 }
 
-fn add_authorized_updater(e: &Env, updater: &Address) {
-    let mut updaters: Vec<Address> = e
-        .storage()
-        .instance()
+/// If a timelock is configured, require an executed, unused action of
+/// `action_type` calling `function` on `target` and mark it consumed; fails
+/// with `err` otherwise. An approval for one function never authorizes another.
+/// When `required_arg` is given, the action's args must also include it.
+/// Only the `MAX_TIMELOCK_SCAN` most recently queued actions are inspected, so
+/// the approval must be used before that many newer actions pile up on top.
+fn require_timelock_action(
+    e: &Env,
+    action_type: u32,
+    target: &Address,
+    function: &str,
+    required_arg: Option<&String>,
+    err: CommitmentError,
+    context: &str,
+) {
+    let timelock = match e.storage().instance().get::<_, Address>(&DataKey::Timelock) {
+        Some(timelock) => timelock,
+        None => return,
+    };
+
+    let action_ids: Vec<u64> =
+        e.invoke_contract(&timelock, &Symbol::new(e, "get_all_actions"), Vec::new(e));
+    let function = Symbol::new(e, function);
+
+    // Most recent actions first, bounded so cross-contract calls stay capped
+    let len = action_ids.len();
+    for i in (len.saturating_sub(MAX_TIMELOCK_SCAN)..len).rev() {
+        let action_id = action_ids.get(i).unwrap();
+        if e.storage()
+            .instance()
+            .has(&DataKey::ConsumedTimelockAction(action_id))
+        {
+            continue;
+        }
+
+        let mut args = Vec::new(e);
+        args.push_back(action_id.into_val(e));
+        let action: TimelockAction =
+            e.invoke_contract(&timelock, &Symbol::new(e, "get_action"), args);
+
+        if action.action_type == action_type
+            && action.target == *target
+            && action.function == function
+            && action.executed
+            && !action.cancelled
+            && required_arg.is_none_or(|arg| action_args_contain(e, &action.args, arg))
+        {
+            e.storage()
+                .instance()
+                .set(&DataKey::ConsumedTimelockAction(action_id), &true);
+            return;
+        }
+    }
+
+    fail(e, err, context);
+}
+
+/// Whether a timelock action's args include the string `arg`.
+fn action_args_contain(e: &Env, args: &Vec<Val>, arg: &String) -> bool {
+    args.iter()
+        .any(|val| String::try_from_val(e, &val).is_ok_and(|s| s == *arg))
+}
+
+fn read_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::Version)
+        .unwrap_or(0)
+}
+
+fn require_valid_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) {
+    let zero = BytesN::from_array(e, &[0; 32]);
+    if *wasm_hash == zero {
+        fail(e, CommitmentError::InvalidWasmHash, "upgrade");
+    }
+}
+
+fn require_authorized_updater(e: &Env, caller: &Address) {
+    caller.require_auth();
+    let updaters: Vec<Address> = e
+        .storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKey::AuthorizedUpdaters)
+        .unwrap_or(Vec::new(e));
+    if !updaters.contains(caller) {
+        fail(e, CommitmentError::NotAuthorizedUpdater, "Unauthorized");
+    }
+}
+
+/// Require that the caller is the admin or an authorized updater.
+fn require_admin_or_updater(e: &Env, caller: &Address) {
+    caller.require_auth();
+    let admin = e.storage().instance().get::<_, Address>(&DataKey::Admin);
+    if admin.as_ref() == Some(caller) {
+        return;
+    }
+    let updaters: Vec<Address> = e
+        .storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKey::AuthorizedUpdaters)
+        .unwrap_or(Vec::new(e));
+    if !updaters.contains(caller) {
+        fail(e, CommitmentError::Unauthorized, "require_admin_or_updater");
+    }
+}
+
+/// Amount the insurance fund must add so a "safe" commitment pays out at least
+/// `amount * (100 - max_loss_percent) / 100`. Zero for other types or when
+/// `current_value` is already at or above the floor.
+fn insurance_top_up(e: &Env, commitment: &Commitment) -> i128 {
+    if commitment.rules.commitment_type != String::from_str(e, "safe") {
+        return 0;
+    }
+    let floor = SafeMath::percent(
+        commitment.amount,
+        100 - commitment.rules.max_loss_percent.min(100),
+    );
+    if commitment.current_value >= floor {
+        return 0;
+    }
+    floor - commitment.current_value
+}
+
+fn read_insurance_fund(e: &Env, asset_address: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get::<_, i128>(&DataKey::InsuranceFund(asset_address.clone()))
+        .unwrap_or(0)
+}
+
+/// Reject labels and tags over `MAX_LABEL_LEN` / `MAX_TAGS` / `MAX_TAG_LEN`.
+fn validate_label(e: &Env, label: &CommitmentLabel, context: &str) {
+    let tags_ok = label.tags.len() <= MAX_TAGS && label.tags.iter().all(|t| t.len() <= MAX_TAG_LEN);
+    if label.label.len() > MAX_LABEL_LEN || !tags_ok {
+        fail(e, CommitmentError::LabelTooLarge, context);
+    }
+}
+
+/// The commitment's label and tags, or `None` if neither was ever set.
+fn read_label(e: &Env, commitment_id: &String) -> Option<CommitmentLabel> {
+    let commitment = read_commitment(e, commitment_id)?;
+    if commitment.label.is_empty() && commitment.tags.is_empty() {
+        return None;
+    }
+    Some(CommitmentLabel {
+        label: commitment.label,
+        tags: commitment.tags,
+    })
+}
+
+fn write_label(e: &Env, commitment_id: &String, label: &CommitmentLabel) {
+    if let Some(mut commitment) = read_commitment(e, commitment_id) {
+        commitment.label = label.label.clone();
+        commitment.tags = label.tags.clone();
+        set_commitment(e, &commitment);
+    }
+}
+
+/// Load a commitment and require `owner` to be its authorized owner.
+fn require_commitment_owner(e: &Env, commitment_id: &String, owner: &Address, context: &str) {
+    owner.require_auth();
+    let commitment = read_commitment(e, commitment_id)
+        .unwrap_or_else(|| fail(e, CommitmentError::CommitmentNotFound, context));
+    if commitment.owner != *owner {
+        fail(e, CommitmentError::Unauthorized, context);
+    }
+}
+
+/// Loss of a commitment in percent of its principal.
+///
+/// Value allocated out to pools still belongs to the commitment, so it
+/// counts towards the value; only a real drop is a loss. A zero-amount
+/// commitment has no PnL.
+fn loss_percent(e: &Env, commitment: &Commitment) -> i128 {
+    let allocated = read_allocations(e, &commitment.commitment_id)
+        .iter()
+        .fold(0i128, |total, record| SafeMath::add(total, record.amount));
+    let value = SafeMath::add(commitment.current_value, allocated);
+    -SafeMath::pnl_percent(commitment.amount, value)
+}
+
+/// Whether an active commitment breaches its loss limit.
+///
+/// Expiry is not a violation: a matured commitment within its loss limit
+/// must stay settleable, so only the loss term can lock it as violated.
+fn has_rule_violation(e: &Env, commitment: &Commitment) -> bool {
+    loss_percent(e, commitment) > commitment.rules.max_loss_percent as i128
+}
+
+fn add_authorized_updater(e: &Env, updater: &Address) {
+    let mut updaters: Vec<Address> = e
+        .storage()
+        .instance()
         .get::<_, Vec<Address>>(&DataKey::AuthorizedUpdaters)
         .unwrap_or(Vec::new(e));
     if !updaters.contains(updater) {
@@ -246,6 +1261,238 @@ fn remove_authorized_updater(e: &Env, updater: &Address) {
     }
 }
 
+// ─── Allocation registry helpers ─────────────────────────────────────────────
+
+/// Read the commitment's compliance score from the attestation engine.
+/// Returns `None` when no minimum is configured. A missing engine or a failed
+/// lookup (e.g. no attestation recorded yet) counts as score 0, so a configured
+/// minimum fails closed, as `MinAttestations` does for settlement.
+fn read_compliance_score(e: &Env, commitment_id: &String) -> Option<u32> {
+    let min_score = e
+        .storage()
+        .instance()
+        .get::<_, u32>(&DataKey::MinAllocScore)
+        .unwrap_or(0);
+    if min_score == 0 {
+        return None;
+    }
+    let Some(engine) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::AttestationEngine)
+    else {
+        return Some(0);
+    };
+
+    let mut args = Vec::new(e);
+    args.push_back(commitment_id.clone().into_val(e));
+    let score = match e.try_invoke_contract::<u32, soroban_sdk::Error>(
+        &engine,
+        &Symbol::new(e, "calculate_compliance_score"),
+        args,
+    ) {
+        Ok(Ok(score)) => score,
+        _ => 0,
+    };
+    Some(score)
+}
+
+/// Number of attestations the engine holds for a commitment; 0 when no
+/// engine is configured or it cannot answer.
+fn read_attestation_count(e: &Env, commitment_id: &String) -> u64 {
+    let Some(engine) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::AttestationEngine)
+    else {
+        return 0;
+    };
+    let mut args = Vec::new(e);
+    args.push_back(commitment_id.clone().into_val(e));
+    match e.try_invoke_contract::<u64, soroban_sdk::Error>(
+        &engine,
+        &Symbol::new(e, "get_attestation_count"),
+        args,
+    ) {
+        Ok(Ok(count)) => count,
+        _ => 0,
+    }
+}
+
+/// Tell the attestation engine, if configured, to close a commitment's health
+/// metrics. Best effort: an engine that fails does not block settlement.
+fn finalize_attestation_metrics(e: &Env, commitment_id: &String) {
+    let Some(engine) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::AttestationEngine)
+    else {
+        return;
+    };
+    let mut args = Vec::new(e);
+    args.push_back(e.current_contract_address().into_val(e));
+    args.push_back(commitment_id.clone().into_val(e));
+    let _ = e.try_invoke_contract::<u32, soroban_sdk::Error>(
+        &engine,
+        &Symbol::new(e, "finalize"),
+        args,
+    );
+}
+
+fn write_settlement_receipt(e: &Env, commitment: &Commitment, amount_paid: i128) {
+    let receipt = SettlementReceipt {
+        commitment_id: commitment.commitment_id.clone(),
+        amount_paid,
+        settled_at: e.ledger().timestamp(),
+        final_status: commitment.status.clone(),
+    };
+    e.storage().instance().set(
+        &DataKey::SettlementReceipt(commitment.commitment_id.clone()),
+        &receipt,
+    );
+}
+
+fn read_allowed_pools(e: &Env, commitment_id: &String) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get::<_, Vec<Address>>(&ExtDataKey::AllowedPools(commitment_id.clone()))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Add `pool` to a commitment's approved allocation targets.
+fn write_allowed_pool(e: &Env, commitment_id: &String, pool: &Address) {
+    let mut pools = read_allowed_pools(e, commitment_id);
+    if !pools.contains(pool) {
+        pools.push_back(pool.clone());
+        e.storage()
+            .instance()
+            .set(&ExtDataKey::AllowedPools(commitment_id.clone()), &pools);
+    }
+}
+
+fn read_allocations(e: &Env, commitment_id: &String) -> Vec<AllocationRecord> {
+    e.storage()
+        .instance()
+        .get::<_, Vec<AllocationRecord>>(&DataKey::Allocations(commitment_id.clone()))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Add `delta` (negative to withdraw) to the commitment's record for `pool`,
+/// dropping the record once it reaches zero. Returns the pool's new amount.
+fn record_allocation(e: &Env, commitment_id: &String, pool: &Address, delta: i128) -> i128 {
+    let mut records = read_allocations(e, commitment_id);
+    let now = e.ledger().timestamp();
+
+    let position = records.iter().position(|r| r.pool == *pool);
+    let new_amount = match position {
+        Some(idx) => {
+            let mut record = records.get(idx as u32).unwrap();
+            record.amount += delta;
+            record.at = now;
+            if record.amount == 0 {
+                records.remove(idx as u32);
+            } else {
+                records.set(idx as u32, record.clone());
+            }
+            record.amount
+        }
+        None => {
+            records.push_back(AllocationRecord {
+                pool: pool.clone(),
+                amount: delta,
+                at: now,
+            });
+            delta
+        }
+    };
+
+    e.storage()
+        .instance()
+        .set(&DataKey::Allocations(commitment_id.clone()), &records);
+    new_amount
+}
+
+// ─── Reward helpers ───────────────────────────────────────────────────────────
+
+/// Fixed-point scale of the per-weight reward index.
+const REWARD_INDEX_SCALE: i128 = 1_000_000_000_000;
+
+/// Reward weight of a commitment: `amount * duration_days`.
+fn reward_weight(commitment: &Commitment) -> i128 {
+    commitment.amount * commitment.rules.duration_days as i128
+}
+
+fn read_i128(e: &Env, key: &DataKey) -> i128 {
+    e.storage().instance().get::<_, i128>(key).unwrap_or(0)
+}
+
+/// Start accruing penalty rewards for a newly created commitment.
+fn enroll_reward_weight(e: &Env, commitment: &Commitment) {
+    let asset = &commitment.asset_address;
+    let weight_key = DataKey::RewardWeight(asset.clone());
+    let total_weight = read_i128(e, &weight_key);
+    e.storage()
+        .instance()
+        .set(&weight_key, &(total_weight + reward_weight(commitment)));
+
+    let index = read_i128(e, &DataKey::RewardIndex(asset.clone()));
+    e.storage().instance().set(
+        &DataKey::RewardEntry(commitment.commitment_id.clone()),
+        &index,
+    );
+}
+
+/// Stop accruing rewards for a commitment leaving the active set. When
+/// `keep_accrued` is set (maturity), the rewards earned so far become claimable;
+/// otherwise they are forfeited and credited back to the asset's reward pool
+/// for the commitments still accruing.
+fn retire_reward_weight(e: &Env, commitment: &Commitment, keep_accrued: bool) {
+    let entry_key = DataKey::RewardEntry(commitment.commitment_id.clone());
+    let entry_index = match e.storage().instance().get::<_, i128>(&entry_key) {
+        Some(index) => index,
+        None => return, // never enrolled
+    };
+    e.storage().instance().remove(&entry_key);
+
+    let asset = &commitment.asset_address;
+    let weight = reward_weight(commitment);
+    let weight_key = DataKey::RewardWeight(asset.clone());
+    let total_weight = read_i128(e, &weight_key);
+    e.storage()
+        .instance()
+        .set(&weight_key, &(total_weight - weight));
+
+    let index = read_i128(e, &DataKey::RewardIndex(asset.clone()));
+    let accrued = SafeMath::mul(weight, index - entry_index) / REWARD_INDEX_SCALE;
+    if keep_accrued {
+        e.storage().instance().set(
+            &DataKey::RewardAccrued(commitment.commitment_id.clone()),
+            &accrued,
+        );
+    } else if accrued > 0 {
+        // The tokens stay in the contract; hand them to the remaining weight
+        accrue_rewards(e, asset, accrued);
+    }
+}
+
+/// Credit penalty rewards held by this contract to all accruing commitments in
+/// `asset`, pro-rata by weight. Held over if nothing is accruing yet.
+fn accrue_rewards(e: &Env, asset: &Address, amount: i128) {
+    let carry_key = DataKey::RewardCarry(asset.clone());
+    let total = amount + read_i128(e, &carry_key);
+    let total_weight = read_i128(e, &DataKey::RewardWeight(asset.clone()));
+    if total_weight <= 0 {
+        e.storage().instance().set(&carry_key, &total);
+        return;
+    }
+    e.storage().instance().remove(&carry_key);
+
+    let index_key = DataKey::RewardIndex(asset.clone());
+    let index = read_i128(e, &index_key);
+    let increment = SafeMath::mul(total, REWARD_INDEX_SCALE) / total_weight;
+    e.storage().instance().set(&index_key, &(index + increment));
+}
+
 // ─── Pause helpers (free functions used by the contract impl) ─────────────────
 
 /// Pause the contract. Caller must be admin.
@@ -275,46 +1522,96 @@ impl CommitmentCoreContract {
         // Duration must be > 0
         Validation::require_valid_duration(rules.duration_days);
 
+        // Duration must not exceed the configured maximum
+        if rules.duration_days > Self::get_max_duration_days(e.clone()) {
+            fail(e, CommitmentError::InvalidDuration, "validate_rules");
+        }
+
         // Max loss percent must be between 0 and 100
         Validation::require_valid_percent(rules.max_loss_percent);
 
+        // Early exit penalty must be between 0 and 100, or the exit payout
+        // would go negative
+        Validation::require_valid_percent(rules.early_exit_penalty);
+
         // Commitment type must be valid
         let valid_types = ["safe", "balanced", "aggressive"];
         Validation::require_valid_commitment_type(e, &rules.commitment_type, &valid_types);
     }
 
+    /// Non-panicking counterpart of the create-time checks (amount and
+    /// `validate_rules`), returning the first failure. Keep in sync with
+    /// `create_commitment_internal`.
+    fn check_commitment_params(
+        e: &Env,
+        amount: i128,
+        asset_address: &Address,
+        rules: &CommitmentRules,
+    ) -> Result<(), CommitmentError> {
+        if !e.storage().instance().has(&DataKey::NftContract) {
+            return Err(CommitmentError::NotInitialized);
+        }
+        if amount <= 0 {
+            return Err(CommitmentError::InvalidAmount);
+        }
+        if rules.duration_days == 0 || rules.duration_days > Self::get_max_duration_days(e.clone())
+        {
+            return Err(CommitmentError::InvalidDuration);
+        }
+        if rules.max_loss_percent > 100 {
+            return Err(CommitmentError::InvalidMaxLossPercent);
+        }
+        if rules.early_exit_penalty > 100 {
+            return Err(CommitmentError::InvalidEarlyExitPenalty);
+        }
+        let valid_types = ["safe", "balanced", "aggressive"];
+        if !valid_types
+            .iter()
+            .any(|t| rules.commitment_type == String::from_str(e, t))
+        {
+            return Err(CommitmentError::InvalidCommitmentType);
+        }
+        if !Self::is_asset_allowed(e.clone(), asset_address.clone()) {
+            return Err(CommitmentError::AssetNotAllowed);
+        }
+        Ok(())
+    }
+
     /// Generate unique commitment ID
-    /// Optimized: Uses counter to create unique ID efficiently
-    fn generate_commitment_id(e: &Env, counter: u64) -> String {
-        // OPTIMIZATION: Use counter directly as string to minimize allocations
-        // This is more gas-efficient than string concatenation
-        let mut buf = [0u8; 32];
-        let prefix = b"c_";
-        buf[0] = prefix[0];
-        buf[1] = prefix[1];
+    ///
+    /// `<owner_short>_<counter>`: the first 4 bytes of the SHA-256 of the
+    /// owner's address in hex, then the global counter. The counter alone
+    /// keeps ids unique, including across owners in the same ledger.
+    fn generate_commitment_id(e: &Env, owner: &Address, counter: u64) -> String {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let hash = e.crypto().sha256(&owner.clone().to_xdr(e)).to_array();
 
-        // Convert counter to string representation
-        let mut n = counter;
-        let mut i = 2;
-        if n == 0 {
-            buf[i] = b'0';
-            i += 1;
-        } else {
-            let mut digits = [0u8; 20];
-            let mut digit_count = 0;
-            while n > 0 {
-                digits[digit_count] = (n % 10) as u8 + b'0';
-                n /= 10;
-                digit_count += 1;
-            }
-            // Reverse digits
-            for j in 0..digit_count {
-                buf[i] = digits[digit_count - 1 - j];
-                i += 1;
-            }
+        let mut buf = [0u8; 32];
+        for (j, byte) in hash[..4].iter().enumerate() {
+            buf[2 * j] = HEX[(byte >> 4) as usize];
+            buf[2 * j + 1] = HEX[(byte & 0x0f) as usize];
         }
+        buf[8] = b'_';
+        let len = write_decimal(&mut buf, 9, counter);
+        String::from_bytes(e, &buf[..len])
+    }
 
-        String::from_str(e, core::str::from_utf8(&buf[..i]).unwrap_or("c_0"))
+    /// Id of the commitment created as number `counter`. Commitments from
+    /// before ids carried an owner prefix have no index entry and are
+    /// `c_<counter>`.
+    fn commitment_id_at(e: &Env, counter: u64) -> String {
+        if let Some(commitment_id) = e
+            .storage()
+            .instance()
+            .get::<_, String>(&DataKey::CommitmentIdAt(counter))
+        {
+            return commitment_id;
+        }
+        let mut buf = [0u8; 32];
+        buf[0] = b'c';
+        buf[1] = b'_';
+        let len = write_decimal(&mut buf, 2, counter);
+        String::from_bytes(e, &buf[..len])
     }
 
     /// Initialize the core commitment contract
@@ -324,6 +1621,9 @@ impl CommitmentCoreContract {
             fail(&e, CommitmentError::AlreadyInitialized, "initialize");
         }
 
+        // Make sure the NFT contract is reachable before wiring it in
+        require_nft_contract(&e, &nft_contract);
+
         // Store admin and NFT contract address
         e.storage().instance().set(&DataKey::Admin, &admin);
         e.storage()
@@ -342,6 +1642,10 @@ impl CommitmentCoreContract {
 
         // Initialize paused state (default: not paused)
         e.storage().instance().set(&Pausable::PAUSED_KEY, &false);
+
+        e.storage()
+            .instance()
+            .set(&DataKey::Version, &CURRENT_VERSION);
     }
 
     /// Create a new commitment
@@ -359,6 +1663,7 @@ impl CommitmentCoreContract {
     /// - `rules.duration_days > 0`
     /// - `rules.max_loss_percent <= 100`
     /// - `rules.commitment_type ∈ {"safe", "balanced", "aggressive"}`
+    /// - `is_asset_allowed(asset_address)`
     /// - Contract is initialized
     /// - `reentrancy_guard == false`
     ///
@@ -388,48 +1693,357 @@ impl CommitmentCoreContract {
         asset_address: Address,
         rules: CommitmentRules,
     ) -> String {
-        // Reentrancy protection
-        require_no_reentrancy(&e);
-        set_reentrancy_guard(&e, true);
-
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
+        Self::create_commitment_internal(
+            e,
+            owner,
+            amount,
+            asset_address,
+            rules,
+            Funding::Transfer,
+            None,
+        )
+    }
 
-        // Rate limit: per-owner commitment creation
-        let fn_symbol = symbol_short!("create");
-        RateLimiter::check(&e, &owner, &fn_symbol);
+    /// Dry-run the checks `create_commitment` applies to its inputs.
+    ///
+    /// No auth, transfers or state changes; returns the error creation would
+    /// fail with, so a frontend can check before the user signs. Rate limits
+    /// and the pause flag are not evaluated.
+    pub fn validate_commitment(
+        e: Env,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> Result<(), CommitmentError> {
+        Self::check_commitment_params(&e, amount, &asset_address, &rules)
+    }
 
-        // Validate amount > 0 using shared utilities
-        Validation::require_positive(amount);
+    /// Create a new commitment, pulling the asset with `transfer_from`
+    ///
+    /// Same as `create_commitment`, but instead of a direct `transfer` the
+    /// contract spends an allowance the owner granted it beforehand via the
+    /// SEP-41 `approve`. Lets integrators batch approvals separately from
+    /// commitment creation.
+    pub fn create_commitment_from_allowance(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> String {
+        Self::create_commitment_internal(
+            e,
+            owner,
+            amount,
+            asset_address,
+            rules,
+            Funding::Allowance,
+            None,
+        )
+    }
 
-        // Validate rules
-        Self::validate_rules(&e, &rules);
+    /// Create a commitment and immediately allocate part of it to `pool`.
+    ///
+    /// Same as `create_commitment`, then, under the same reentrancy guard,
+    /// approves `pool` for the commitment and runs the `allocate` logic for
+    /// the `allocate_bps` fraction set with `set_auto_allocation`. The
+    /// allocation shrinks as needed so the contract still holds `reserve_bps`
+    /// of the asset's locked value afterwards. Allocates nothing while
+    /// auto-allocation is unconfigured.
+    pub fn create_commitment_auto_alloc(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+        auto_allocate_pool: Address,
+    ) -> String {
+        Self::create_commitment_internal(
+            e,
+            owner,
+            amount,
+            asset_address,
+            rules,
+            Funding::Transfer,
+            Some(auto_allocate_pool),
+        )
+    }
 
-        // OPTIMIZATION: Read both counters and NFT contract once to minimize storage operations
-        let (current_total, current_tvl, nft_contract) = {
-            let total = e
-                .storage()
-                .instance()
-                .get::<_, u64>(&DataKey::TotalCommitments)
-                .unwrap_or(0);
-            let tvl = e
-                .storage()
-                .instance()
-                .get::<_, i128>(&DataKey::TotalValueLocked)
-                .unwrap_or(0);
-            let nft = e
-                .storage()
-                .instance()
-                .get::<_, Address>(&DataKey::NftContract)
-                .unwrap_or_else(|| {
-                    set_reentrancy_guard(&e, false);
-                    fail(&e, CommitmentError::NotInitialized, "create_commitment")
-                });
-            (total, tvl, nft)
-        };
+    /// Set the share of a new commitment `create_commitment_auto_alloc`
+    /// allocates and the share of the asset's locked value the contract must
+    /// keep holding, both in bps (admin-only). Their sum may not exceed 10000.
+    pub fn set_auto_allocation(e: Env, caller: Address, allocate_bps: u32, reserve_bps: u32) {
+        require_admin(&e, &caller);
+        if allocate_bps.saturating_add(reserve_bps) > 10_000 {
+            fail(
+                &e,
+                CommitmentError::InvalidAutoAllocation,
+                "set_auto_allocation",
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&ExtDataKey::AutoAllocation, &(allocate_bps, reserve_bps));
+    }
 
-        // Generate unique commitment ID using counter
-        let commitment_id = Self::generate_commitment_id(&e, current_total);
+    /// Get `(allocate_bps, reserve_bps)` used by `create_commitment_auto_alloc`.
+    pub fn get_auto_allocation(e: Env) -> (u32, u32) {
+        e.storage()
+            .instance()
+            .get::<_, (u32, u32)>(&ExtDataKey::AutoAllocation)
+            .unwrap_or((0, 10_000))
+    }
+
+    /// Create one commitment funded by several contributors.
+    ///
+    /// Each `(contributor, amount)` authorizes and transfers its amount; the
+    /// commitment is opened for the sum. The first contributor becomes the
+    /// owner and receives the NFT, so only they can settle, exit early or
+    /// manage the commitment, and selling the NFT does not move the other
+    /// contributors' shares. Settlement and early exit pay the proceeds out
+    /// pro rata to the recorded shares instead of to the owner alone,
+    /// bypassing any payout hook or payout asset; a portion whose transfer
+    /// fails is held for `claim_shared_payout`.
+    pub fn create_shared_commitment(
+        e: Env,
+        contributors: Vec<(Address, i128)>,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> String {
+        if contributors.is_empty() || contributors.len() > MAX_SHARED_CONTRIBUTORS {
+            fail(
+                &e,
+                CommitmentError::InvalidContributors,
+                "create_shared_commitment",
+            );
+        }
+        let mut total: i128 = 0;
+        for (i, (contributor, amount)) in contributors.iter().enumerate() {
+            let duplicate = contributors
+                .iter()
+                .take(i)
+                .any(|(other, _)| other == contributor);
+            if amount <= 0 || duplicate {
+                fail(
+                    &e,
+                    CommitmentError::InvalidContributors,
+                    "create_shared_commitment",
+                );
+            }
+            total = SafeMath::add(total, amount);
+        }
+
+        // The owner authorizes inside the create flow
+        let (owner, _) = contributors.get_unchecked(0);
+        for (contributor, _) in contributors.iter().skip(1) {
+            contributor.require_auth();
+        }
+
+        let commitment_id = Self::create_commitment_internal(
+            e.clone(),
+            owner,
+            total,
+            asset_address,
+            rules,
+            Funding::Shared(contributors.clone()),
+            None,
+        );
+        e.events().publish(
+            (symbol_short!("SharedNew"), commitment_id.clone()),
+            contributors.len(),
+        );
+        commitment_id
+    }
+
+    /// Get the contributions backing a shared commitment (empty if not shared).
+    pub fn get_shares(e: Env, commitment_id: String) -> Vec<(Address, i128)> {
+        e.storage()
+            .instance()
+            .get::<_, Vec<(Address, i128)>>(&DataKey::Shares(commitment_id))
+            .unwrap_or(Vec::new(&e))
+    }
+
+    /// Get the shared-commitment payout held for `contributor` after a failed transfer.
+    pub fn get_shared_claim(e: Env, commitment_id: String, contributor: Address) -> i128 {
+        e.storage()
+            .instance()
+            .get::<_, i128>(&ExtDataKey::SharedClaim(commitment_id, contributor))
+            .unwrap_or(0)
+    }
+
+    /// Withdraw the shared-commitment payout held for `contributor`.
+    ///
+    /// Returns the amount transferred; fails with `NothingToClaim` if nothing
+    /// is held.
+    pub fn claim_shared_payout(e: Env, contributor: Address, commitment_id: String) -> i128 {
+        contributor.require_auth();
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        let key = ExtDataKey::SharedClaim(commitment_id.clone(), contributor.clone());
+        let amount = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NothingToClaim, "claim_shared_payout");
+        }
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "claim_shared_payout",
+            )
+        });
+
+        // EFFECTS before the transfer
+        e.storage().instance().remove(&key);
+        token::Client::new(&e, &commitment.asset_address).transfer(
+            &e.current_contract_address(),
+            &contributor,
+            &amount,
+        );
+
+        set_reentrancy_guard(&e, false);
+        e.events().publish(
+            (symbol_short!("ShareClm"), commitment_id),
+            (contributor, amount),
+        );
+        amount
+    }
+
+    /// Create a new commitment carrying an owner-chosen label and tags.
+    ///
+    /// Same as `create_commitment`; the label is size-checked before any
+    /// funds move and can be changed later with `set_label` / `set_tags`.
+    pub fn create_commitment_with_label(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+        label: String,
+        tags: Vec<String>,
+    ) -> String {
+        let label = CommitmentLabel { label, tags };
+        validate_label(&e, &label, "create_commitment_with_label");
+        let commitment_id = Self::create_commitment_internal(
+            e.clone(),
+            owner,
+            amount,
+            asset_address,
+            rules,
+            Funding::Transfer,
+            None,
+        );
+        write_label(&e, &commitment_id, &label);
+        commitment_id
+    }
+
+    /// Create a commitment at most once per `(owner, idempotency_key)`.
+    ///
+    /// Same as `create_commitment`, but a retry carrying a key the owner has
+    /// already used returns the commitment created the first time instead of
+    /// opening (and funding) a second one. Keys are scoped to the owner.
+    pub fn create_commitment_idempotent(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+        idempotency_key: BytesN<32>,
+    ) -> String {
+        let key = DataKey::IdempotencyKey(owner.clone(), idempotency_key);
+        if let Some(existing) = e.storage().instance().get::<_, String>(&key) {
+            owner.require_auth();
+            return existing;
+        }
+        let commitment_id = Self::create_commitment_internal(
+            e.clone(),
+            owner,
+            amount,
+            asset_address,
+            rules,
+            Funding::Transfer,
+            None,
+        );
+        e.storage().instance().set(&key, &commitment_id);
+        commitment_id
+    }
+
+    /// Get the commitment created under an owner's idempotency key, if any.
+    pub fn get_idempotent_commitment(
+        e: Env,
+        owner: Address,
+        idempotency_key: BytesN<32>,
+    ) -> Option<String> {
+        e.storage()
+            .instance()
+            .get::<_, String>(&DataKey::IdempotencyKey(owner, idempotency_key))
+    }
+
+    /// Common create flow; `funding` selects where the amount is pulled from.
+    fn create_commitment_internal(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+        funding: Funding,
+        auto_allocate_pool: Option<Address>,
+    ) -> String {
+        // Owner authorizes the commitment and the asset transfer
+        owner.require_auth();
+
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+
+        // Rate limit: per-owner commitment creation
+        let fn_symbol = symbol_short!("create");
+        RateLimiter::check(&e, &owner, &fn_symbol);
+        // Optional protocol-wide cap across all owners
+        RateLimiter::check_global(&e, &fn_symbol);
+
+        // Validate amount > 0 using shared utilities
+        Validation::require_positive(amount);
+
+        // Validate rules
+        Self::validate_rules(&e, &rules);
+
+        // Only admin-approved assets can be committed
+        if !Self::is_asset_allowed(e.clone(), asset_address.clone()) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::AssetNotAllowed, "create_commitment");
+        }
+
+        // OPTIMIZATION: Read both counters and NFT contract once to minimize storage operations
+        let (current_total, current_tvl, nft_contract) = {
+            let total = e
+                .storage()
+                .instance()
+                .get::<_, u64>(&DataKey::TotalCommitments)
+                .unwrap_or(0);
+            let tvl = e
+                .storage()
+                .instance()
+                .get::<_, i128>(&DataKey::TotalValueLocked)
+                .unwrap_or(0);
+            let nft = e
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::NftContract)
+                .unwrap_or_else(|| {
+                    set_reentrancy_guard(&e, false);
+                    fail(&e, CommitmentError::NotInitialized, "create_commitment")
+                });
+            (total, tvl, nft)
+        };
+
+        // Generate unique commitment ID using counter
+        let commitment_id = Self::generate_commitment_id(&e, &owner, current_total);
 
         // CHECKS: Validate commitment doesn't already exist
         if has_commitment(&e, &commitment_id) {
@@ -440,7 +2054,11 @@ impl CommitmentCoreContract {
         // EFFECTS: Update state before external calls
         // Calculate expiration timestamp using shared utilities
         let current_timestamp = TimeUtils::now(&e);
-        let expires_at = TimeUtils::calculate_expiration(&e, rules.duration_days);
+        let expires_at = TimeUtils::calculate_expiration_with_calendar(
+            &e,
+            rules.duration_days,
+            Self::get_settlement_calendar(e.clone()),
+        );
 
         // Create commitment data
         let commitment = Commitment {
@@ -454,10 +2072,19 @@ impl CommitmentCoreContract {
             expires_at,
             current_value: amount, // Initially same as amount
             status: String::from_str(&e, "active"),
+            label: String::from_str(&e, ""),
+            tags: Vec::new(&e),
         };
 
         // Store commitment data (before external calls)
         set_commitment(&e, &commitment);
+        extend_commitment_ttl(&e);
+        enroll_reward_weight(&e, &commitment);
+        if let Funding::Shared(contributors) = &funding {
+            e.storage()
+                .instance()
+                .set(&DataKey::Shares(commitment_id.clone()), contributors);
+        }
 
         // Update owner's commitment list
         let mut owner_commitments = e
@@ -470,8 +2097,15 @@ impl CommitmentCoreContract {
             &DataKey::OwnerCommitments(owner.clone()),
             &owner_commitments,
         );
+        track_owner_active(&e, &owner, true);
+        track_owner_value(&e, &owner, amount);
+        index_asset_commitment(&e, &asset_address, &commitment_id);
+        track_asset_tvl(&e, &asset_address, amount);
 
         // OPTIMIZATION: Increment both counters using already-read values
+        e.storage()
+            .instance()
+            .set(&DataKey::CommitmentIdAt(current_total), &commitment_id);
         e.storage()
             .instance()
             .set(&DataKey::TotalCommitments, &(current_total + 1));
@@ -480,9 +2114,25 @@ impl CommitmentCoreContract {
             .set(&DataKey::TotalValueLocked, &(current_tvl + amount));
 
         // INTERACTIONS: External calls (token transfer, NFT mint)
-        // Transfer assets from owner to contract
+        // Transfer assets from owner (or each contributor) to contract
         let contract_address = e.current_contract_address();
-        transfer_assets(&e, &owner, &contract_address, &asset_address, amount);
+        match funding {
+            Funding::Transfer => {
+                transfer_assets(&e, &owner, &contract_address, &asset_address, amount)
+            }
+            Funding::Allowance => transfer_assets_from(&e, &owner, &asset_address, amount),
+            Funding::Shared(contributors) => {
+                for (contributor, contributed) in contributors.iter() {
+                    transfer_assets(
+                        &e,
+                        &contributor,
+                        &contract_address,
+                        &asset_address,
+                        contributed,
+                    );
+                }
+            }
+        }
 
         // Mint NFT
         let nft_token_id = call_nft_mint(
@@ -490,9 +2140,7 @@ impl CommitmentCoreContract {
             &nft_contract,
             &owner,
             &commitment_id,
-            rules.duration_days,
-            rules.max_loss_percent,
-            &rules.commitment_type,
+            &rules,
             amount,
             &asset_address,
         );
@@ -502,18 +2150,41 @@ impl CommitmentCoreContract {
         updated_commitment.nft_token_id = nft_token_id;
         set_commitment(&e, &updated_commitment);
 
-        // Clear reentrancy guard
-        set_reentrancy_guard(&e, false);
+        // Index the NFT back to its commitment
+        e.storage()
+            .instance()
+            .set(&DataKey::NftToCommitment(nft_token_id), &commitment_id);
+
+        // Anchor the value to the oracle price for later revaluation
+        record_entry_price(&e, &updated_commitment);
 
         // Emit creation event
-        e.events().publish(
-            (
-                symbol_short!("Created"),
-                commitment_id.clone(),
-                owner.clone(),
-            ),
-            (amount, rules, nft_token_id, e.ledger().timestamp()),
-        );
+        Events::emit_commitment_created(&e, &commitment_id, &owner, amount, &rules, nft_token_id);
+
+        // Deploy the configured fraction while the guard is still held
+        if let Some(pool) = auto_allocate_pool {
+            let (allocate_bps, reserve_bps) = Self::get_auto_allocation(e.clone());
+            let (wanted, reserve) = match (
+                SafeMath::checked_percent_of(amount, allocate_bps),
+                SafeMath::checked_percent_of(read_asset_tvl(&e, &asset_address), reserve_bps),
+            ) {
+                (Ok(wanted), Ok(reserve)) => (wanted, reserve),
+                _ => {
+                    set_reentrancy_guard(&e, false);
+                    fail(&e, CommitmentError::ArithmeticOverflow, "create_commitment")
+                }
+            };
+            // Keep `reserve_bps` of the asset's locked value in hand
+            let spare = SafeMath::sub(Self::get_held_balance(e.clone(), asset_address), reserve);
+            let allocated = wanted.min(spare);
+            if allocated > 0 {
+                write_allowed_pool(&e, &commitment_id, &pool);
+                Self::allocate_guarded(e.clone(), commitment_id.clone(), pool, allocated, None);
+            }
+        }
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
         commitment_id
     }
 
@@ -523,6 +2194,219 @@ impl CommitmentCoreContract {
             .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "get_commitment"))
     }
 
+    /// Get several commitments in one call (at most `MAX_BULK_LOOKUP` ids).
+    ///
+    /// The result lines up with `ids`; unknown ids yield `None` instead of
+    /// failing the whole lookup.
+    pub fn get_commitments(e: Env, ids: Vec<String>) -> Vec<Option<Commitment>> {
+        if ids.len() > MAX_BULK_LOOKUP {
+            fail(&e, CommitmentError::BatchTooLarge, "get_commitments");
+        }
+        let mut commitments = Vec::new(&e);
+        for commitment_id in ids.iter() {
+            commitments.push_back(read_commitment(&e, &commitment_id));
+        }
+        commitments
+    }
+
+    /// Get a commitment's lifecycle phase from its status and the ledger time.
+    ///
+    /// Active commitments are `Active` before `expires_at`, `InGrace` from
+    /// `expires_at` until `grace_period_days` later, and `PastGrace` after.
+    pub fn get_lifecycle_phase(e: Env, commitment_id: String) -> LifecyclePhase {
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "get_lifecycle_phase",
+            )
+        });
+
+        if commitment.status == String::from_str(&e, "settled") {
+            return LifecyclePhase::Settled;
+        }
+        if commitment.status == String::from_str(&e, "early_exit") {
+            return LifecyclePhase::EarlyExited;
+        }
+        if commitment.status == String::from_str(&e, "violated") {
+            return LifecyclePhase::Violated;
+        }
+
+        let now = e.ledger().timestamp();
+        let grace_ends_at = commitment
+            .expires_at
+            .saturating_add(TimeUtils::days_to_seconds(
+                commitment.rules.grace_period_days,
+            ));
+        if now < commitment.expires_at {
+            LifecyclePhase::Active
+        } else if now < grace_ends_at {
+            LifecyclePhase::InGrace
+        } else {
+            LifecyclePhase::PastGrace
+        }
+    }
+
+    /// Get the label and tags attached to a commitment, if any.
+    pub fn get_commitment_label(e: Env, commitment_id: String) -> Option<CommitmentLabel> {
+        read_label(&e, &commitment_id)
+    }
+
+    /// Set a commitment's label (owner-only, at most `MAX_LABEL_LEN` bytes).
+    pub fn set_label(e: Env, commitment_id: String, owner: Address, label: String) {
+        require_commitment_owner(&e, &commitment_id, &owner, "set_label");
+        let mut entry = read_label(&e, &commitment_id).unwrap_or(CommitmentLabel {
+            label: String::from_str(&e, ""),
+            tags: Vec::new(&e),
+        });
+        entry.label = label;
+        validate_label(&e, &entry, "set_label");
+        write_label(&e, &commitment_id, &entry);
+    }
+
+    /// Replace a commitment's tags (owner-only, at most `MAX_TAGS` entries of
+    /// `MAX_TAG_LEN` bytes).
+    pub fn set_tags(e: Env, commitment_id: String, owner: Address, tags: Vec<String>) {
+        require_commitment_owner(&e, &commitment_id, &owner, "set_tags");
+        let mut entry = read_label(&e, &commitment_id).unwrap_or(CommitmentLabel {
+            label: String::from_str(&e, ""),
+            tags: Vec::new(&e),
+        });
+        entry.tags = tags;
+        validate_label(&e, &entry, "set_tags");
+        write_label(&e, &commitment_id, &entry);
+    }
+
+    /// Route a commitment's settlement proceeds to `hook` (owner-only).
+    ///
+    /// The hook contract receives the tokens and an
+    /// `on_settlement(commitment_id, amount)` call when the commitment
+    /// settles. `None` restores the direct transfer to the owner.
+    pub fn set_payout_hook(e: Env, commitment_id: String, owner: Address, hook: Option<Address>) {
+        require_commitment_owner(&e, &commitment_id, &owner, "set_payout_hook");
+        let key = DataKey::PayoutHook(commitment_id.clone());
+        match &hook {
+            Some(hook) => e.storage().instance().set(&key, hook),
+            None => e.storage().instance().remove(&key),
+        }
+        e.events()
+            .publish((Symbol::new(&e, "PayoutHookSet"), commitment_id), hook);
+    }
+
+    /// Get the payout hook set for a commitment, if any.
+    pub fn get_payout_hook(e: Env, commitment_id: String) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PayoutHook(commitment_id))
+    }
+
+    /// Have a commitment's settlement proceeds swapped into `payout_asset`
+    /// (owner-only).
+    ///
+    /// At settlement the swap router converts the proceeds and the settlement
+    /// reverts if it returns fewer than `min_out`. `None`, or the commitment's
+    /// own asset, pays out natively. Requires a configured swap router.
+    pub fn set_payout_asset(
+        e: Env,
+        commitment_id: String,
+        owner: Address,
+        payout_asset: Option<Address>,
+        min_out: i128,
+    ) {
+        require_commitment_owner(&e, &commitment_id, &owner, "set_payout_asset");
+        let key = DataKey::PayoutAsset(commitment_id.clone());
+        match payout_asset {
+            Some(asset) => {
+                if Self::get_swap_router(e.clone()).is_none() {
+                    fail(
+                        &e,
+                        CommitmentError::SwapRouterNotConfigured,
+                        "set_payout_asset",
+                    );
+                }
+                if min_out < 0 {
+                    fail(&e, CommitmentError::InvalidAmount, "set_payout_asset");
+                }
+                let config = PayoutAssetConfig { asset, min_out };
+                e.storage().instance().set(&key, &config);
+                e.events()
+                    .publish((Symbol::new(&e, "PayoutAssetSet"), commitment_id), config);
+            }
+            None => {
+                e.storage().instance().remove(&key);
+                e.events()
+                    .publish((Symbol::new(&e, "PayoutAssetCleared"), commitment_id), ());
+            }
+        }
+    }
+
+    /// Get the payout asset configured for a commitment, if any.
+    pub fn get_payout_asset(e: Env, commitment_id: String) -> Option<PayoutAssetConfig> {
+        e.storage()
+            .instance()
+            .get::<_, PayoutAssetConfig>(&DataKey::PayoutAsset(commitment_id))
+    }
+
+    /// Set the swap router used for payout-asset conversion (admin-only).
+    ///
+    /// The router must implement
+    /// `swap(token_in, token_out, amount_in, min_out, recipient) -> i128`,
+    /// spending `amount_in` of `token_in` already transferred to it and
+    /// sending the output to `recipient`.
+    pub fn set_swap_router(e: Env, caller: Address, router: Address) {
+        require_admin(&e, &caller);
+        e.storage().instance().set(&DataKey::SwapRouter, &router);
+        e.events()
+            .publish((Symbol::new(&e, "SwapRouterSet"), caller), router);
+    }
+
+    /// Get the configured swap router, if any.
+    pub fn get_swap_router(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::SwapRouter)
+    }
+
+    /// Extend the TTL of the storage holding `commitment_id`.
+    ///
+    /// Commitments live in instance storage, so this bumps the instance entry
+    /// (see `TTL_THRESHOLD` / `TTL_EXTEND_TO` in shared_utils). Anyone may
+    /// call it to keep a long-lived commitment from being archived; one that
+    /// outlasts `TTL_EXTEND_TO` relies on `update_value` or this call landing
+    /// at least once per threshold window.
+    pub fn bump_commitment_ttl(e: Env, commitment_id: String) {
+        if !has_commitment(&e, &commitment_id) {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "bump_commitment_ttl",
+            );
+        }
+        extend_commitment_ttl(&e);
+    }
+
+    /// Get the commitment backing an NFT token
+    pub fn get_commitment_by_nft(e: Env, nft_token_id: u32) -> Commitment {
+        let commitment_id = e
+            .storage()
+            .instance()
+            .get::<_, String>(&DataKey::NftToCommitment(nft_token_id))
+            .unwrap_or_else(|| {
+                fail(
+                    &e,
+                    CommitmentError::CommitmentNotFound,
+                    "get_commitment_by_nft",
+                )
+            });
+        read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "get_commitment_by_nft",
+            )
+        })
+    }
+
     /// Get all commitments for an owner
     pub fn get_owner_commitments(e: Env, owner: Address) -> Vec<String> {
         e.storage()
@@ -531,6 +2415,23 @@ impl CommitmentCoreContract {
             .unwrap_or(Vec::new(&e))
     }
 
+    /// Get how many of an owner's commitments are still active.
+    ///
+    /// Maintained on create and on every move out of "active" (settle, early
+    /// exit, violation), so unlike `get_owner_commitments` (full history) it
+    /// drops as commitments close.
+    pub fn get_owner_commitment_count(e: Env, owner: Address) -> u32 {
+        read_owner_active_count(&e, &owner)
+    }
+
+    /// Get the summed `current_value` of an owner's active commitments.
+    ///
+    /// Kept as a running total on every value change and status transition,
+    /// so this is a single read rather than a scan.
+    pub fn get_owner_total_value(e: Env, owner: Address) -> i128 {
+        read_owner_value(&e, &owner)
+    }
+
     /// Get total number of commitments
     pub fn get_total_commitments(e: Env) -> u64 {
         e.storage()
@@ -547,22 +2448,367 @@ impl CommitmentCoreContract {
             .unwrap_or(0)
     }
 
-    /// Get admin address
-    pub fn get_admin(e: Env) -> Address {
+    /// Get the cumulative amount ever paid out by `settle`, across assets.
+    ///
+    /// Unlike TVL this only grows; early exits are not counted.
+    pub fn get_total_settled_value(e: Env) -> i128 {
         e.storage()
             .instance()
-            .get::<_, Address>(&DataKey::Admin)
-            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_admin"))
+            .get::<_, i128>(&ExtDataKey::TotalSettledValue)
+            .unwrap_or(0)
     }
 
-    /// Get NFT contract address
-    pub fn get_nft_contract(e: Env) -> Address {
-        e.storage()
+    /// Get the value locked in active commitments denominated in `asset`.
+    ///
+    /// Moves in step with `get_total_value_locked`, so the per-asset totals
+    /// sum to the global TVL.
+    pub fn get_asset_tvl(e: Env, asset: Address) -> i128 {
+        read_asset_tvl(&e, &asset)
+    }
+
+    /// Page through the ids of commitments created in `asset`, oldest first.
+    ///
+    /// Covers every status; returns at most `limit` ids starting at `start`
+    /// and an empty list once `start` is past the end.
+    pub fn get_commitments_by_asset(e: Env, asset: Address, start: u32, limit: u32) -> Vec<String> {
+        let len = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&ExtDataKey::AssetCommitmentCount(asset.clone()))
+            .unwrap_or(0);
+        let mut ids = Vec::new(&e);
+        for index in start..start.saturating_add(limit).min(len) {
+            if let Some(commitment_id) = e
+                .storage()
+                .persistent()
+                .get::<_, String>(&ExtDataKey::AssetCommitmentAt(asset.clone(), index))
+            {
+                ids.push_back(commitment_id);
+            }
+        }
+        ids
+    }
+
+    /// Rebuild `TotalValueLocked` and every `AssetTvl` from the active
+    /// commitments (admin-only).
+    ///
+    /// Sums `current_value` over every active commitment, overwrites the
+    /// stored totals and returns the global one, emitting `TvlRecomputed` with
+    /// the old and new values. Assets left with no active commitments are
+    /// reset to zero. Commitments live in instance storage, which every call
+    /// loads in full anyway, so a single pass is not paginated.
+    pub fn recompute_tvl(e: Env, caller: Address) -> i128 {
+        require_admin(&e, &caller);
+
+        let total = Self::get_total_commitments(e.clone());
+        let active_status = String::from_str(&e, "active");
+        let mut tvl: i128 = 0;
+        let mut asset_tvl: Map<Address, i128> = Map::new(&e);
+        for counter in 0..total {
+            let commitment_id = Self::commitment_id_at(&e, counter);
+            if let Some(commitment) = read_commitment(&e, &commitment_id) {
+                let asset = commitment.asset_address.clone();
+                let mut value = asset_tvl.get(asset.clone()).unwrap_or(0);
+                if commitment.status == active_status {
+                    tvl = SafeMath::add(tvl, commitment.current_value);
+                    value = SafeMath::add(value, commitment.current_value);
+                }
+                asset_tvl.set(asset, value);
+            }
+        }
+
+        for (asset, value) in asset_tvl.iter() {
+            e.storage()
+                .instance()
+                .set(&DataKey::AssetTvl(asset), &value);
+        }
+        let old_tvl = Self::get_total_value_locked(e.clone());
+        e.storage().instance().set(&DataKey::TotalValueLocked, &tvl);
+        e.events()
+            .publish((Symbol::new(&e, "TvlRecomputed"), caller), (old_tvl, tvl));
+        tvl
+    }
+
+    /// List active commitments due for settlement by `timestamp`.
+    ///
+    /// Scans at most `limit` commitments in creation order starting at the
+    /// `start`-th and returns the ids of those still active with
+    /// `expires_at <= timestamp`. A page may hold fewer than `limit` ids;
+    /// keepers advance `start` by `limit` until it reaches
+    /// `get_total_commitments`.
+    pub fn get_maturing_before(e: Env, timestamp: u64, start: u64, limit: u64) -> Vec<String> {
+        let total = Self::get_total_commitments(e.clone());
+        let end = start.saturating_add(limit).min(total);
+        let active_status = String::from_str(&e, "active");
+
+        let mut due = Vec::new(&e);
+        for counter in start..end {
+            let commitment_id = Self::commitment_id_at(&e, counter);
+            if let Some(commitment) = read_commitment(&e, &commitment_id) {
+                if commitment.status == active_status && commitment.expires_at <= timestamp {
+                    due.push_back(commitment_id);
+                }
+            }
+        }
+        due
+    }
+
+    /// Compare booked value against the tokens actually held for `asset_address`.
+    ///
+    /// Returns `(sum of current_value over active commitments in the asset,
+    /// this contract's token balance)`. Read-only; a gap between the two
+    /// surfaces accounting drift (allocations, penalties kept in-contract,
+    /// unclaimed rewards, insurance deposits or revaluations all show up here).
+    pub fn reconcile(e: Env, asset_address: Address) -> (i128, i128) {
+        let total = Self::get_total_commitments(e.clone());
+        let active_status = String::from_str(&e, "active");
+
+        let mut booked: i128 = 0;
+        for counter in 0..total {
+            let commitment_id = Self::commitment_id_at(&e, counter);
+            if let Some(commitment) = read_commitment(&e, &commitment_id) {
+                if commitment.status == active_status && commitment.asset_address == asset_address {
+                    booked = SafeMath::add(booked, commitment.current_value);
+                }
+            }
+        }
+
+        (booked, Self::get_held_balance(e, asset_address))
+    }
+
+    /// This contract's token balance for `asset`, for comparison against the
+    /// accounting counters off-chain.
+    pub fn get_held_balance(e: Env, asset: Address) -> i128 {
+        token::Client::new(&e, &asset).balance(&e.current_contract_address())
+    }
+
+    /// Get admin address
+    pub fn get_admin(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_admin"))
+    }
+
+    /// Get NFT contract address
+    pub fn get_nft_contract(e: Env) -> Address {
+        e.storage()
             .instance()
             .get::<_, Address>(&DataKey::NftContract)
             .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_nft_contract"))
     }
 
+    /// Transfer the admin role (admin-only).
+    ///
+    /// When a timelock is configured, an executed `AdminChange` action targeting
+    /// `new_admin` must exist on it; each action can authorize one change.
+    pub fn set_admin(e: Env, caller: Address, new_admin: Address) {
+        require_admin(&e, &caller);
+        require_timelock_admin_change(&e, "set_admin", &new_admin);
+
+        e.storage().instance().set(&DataKey::Admin, &new_admin);
+        e.events()
+            .publish((symbol_short!("AdminChg"), caller), new_admin);
+    }
+
+    /// Clear a stuck reentrancy guard (admin-only).
+    ///
+    /// When a timelock is configured, an executed `ParameterChange` action
+    /// calling `reset_reentrancy_guard` on this contract must exist on it;
+    /// each action authorizes one reset.
+    pub fn reset_reentrancy_guard(e: Env, caller: Address) {
+        require_admin(&e, &caller);
+        require_timelock_action(
+            &e,
+            TIMELOCK_PARAMETER_CHANGE,
+            &e.current_contract_address(),
+            "reset_reentrancy_guard",
+            None,
+            CommitmentError::GuardResetNotApproved,
+            "reset_reentrancy_guard",
+        );
+
+        set_reentrancy_guard(&e, false);
+        e.events()
+            .publish((Symbol::new(&e, "GuardReset"), caller), ());
+    }
+
+    /// Waive the early-exit penalty of a commitment (admin-only).
+    ///
+    /// For owners hurt through no fault of their own (e.g. an oracle outage
+    /// dragging the position towards its loss limit): the next `early_exit`
+    /// pays out the full value. Only active commitments can be waived, since
+    /// early exit is closed once a commitment is violated or settled. When a
+    /// timelock is configured this consumes an executed `ParameterChange`
+    /// action calling `waive_penalty` with this commitment id.
+    pub fn waive_penalty(e: Env, caller: Address, commitment_id: String) {
+        require_admin(&e, &caller);
+        let commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "waive_penalty"));
+        if commitment.status != String::from_str(&e, "active") {
+            fail(&e, CommitmentError::NotActive, "waive_penalty");
+        }
+        require_timelock_action(
+            &e,
+            TIMELOCK_PARAMETER_CHANGE,
+            &e.current_contract_address(),
+            "waive_penalty",
+            Some(&commitment_id),
+            CommitmentError::PenaltyWaiverNotApproved,
+            "waive_penalty",
+        );
+
+        e.storage()
+            .instance()
+            .set(&DataKey::PenaltyWaived(commitment_id.clone()), &true);
+        e.events()
+            .publish((symbol_short!("PenWaived"), commitment_id), caller);
+    }
+
+    /// Whether a commitment's early-exit penalty has been waived.
+    pub fn is_penalty_waived(e: Env, commitment_id: String) -> bool {
+        e.storage()
+            .instance()
+            .has(&DataKey::PenaltyWaived(commitment_id))
+    }
+
+    /// Propose `candidate` as the next admin (admin-only).
+    ///
+    /// The change only takes effect once the candidate calls `accept_admin`.
+    /// When a timelock is configured, its approval is consumed here, as with
+    /// `set_admin`. A new proposal replaces any pending one.
+    pub fn propose_admin(e: Env, caller: Address, candidate: Address) {
+        require_admin(&e, &caller);
+        require_timelock_admin_change(&e, "propose_admin", &candidate);
+
+        e.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &candidate);
+        e.events()
+            .publish((symbol_short!("AdminProp"), caller), candidate);
+    }
+
+    /// Finalize a pending admin proposal; `caller` must be the candidate.
+    pub fn accept_admin(e: Env, caller: Address) {
+        caller.require_auth();
+        let candidate = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PendingAdmin)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NoPendingAdmin, "accept_admin"));
+        if caller != candidate {
+            fail(&e, CommitmentError::Unauthorized, "accept_admin");
+        }
+
+        let previous = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "accept_admin"));
+        e.storage().instance().set(&DataKey::Admin, &candidate);
+        e.storage().instance().remove(&DataKey::PendingAdmin);
+        e.events()
+            .publish((symbol_short!("AdminChg"), previous), candidate);
+    }
+
+    /// Withdraw a pending admin proposal (admin-only).
+    pub fn cancel_admin_proposal(e: Env, caller: Address) {
+        require_admin(&e, &caller);
+        if !e.storage().instance().has(&DataKey::PendingAdmin) {
+            fail(&e, CommitmentError::NoPendingAdmin, "cancel_admin_proposal");
+        }
+        e.storage().instance().remove(&DataKey::PendingAdmin);
+        e.events().publish((symbol_short!("AdminCncl"), caller), ());
+    }
+
+    /// Get the admin candidate awaiting `accept_admin`, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PendingAdmin)
+    }
+
+    /// Give up the admin role permanently by setting it to a burn address (admin-only).
+    ///
+    /// After this call no admin-gated function can succeed again.
+    pub fn renounce_admin(e: Env, caller: Address) {
+        require_admin(&e, &caller);
+
+        let burn = Address::from_string(&String::from_str(&e, BURN_ADDRESS));
+        e.storage().instance().set(&DataKey::Admin, &burn);
+        e.events()
+            .publish((symbol_short!("AdminRen"), caller), burn);
+    }
+
+    /// Configure the timelock contract that must approve admin changes (admin-only).
+    ///
+    /// Can only be set once so the admin cannot swap it out to bypass the delay.
+    pub fn set_timelock(e: Env, caller: Address, timelock: Address) {
+        require_admin(&e, &caller);
+        if e.storage().instance().has(&DataKey::Timelock) {
+            fail(&e, CommitmentError::Unauthorized, "set_timelock");
+        }
+        e.storage().instance().set(&DataKey::Timelock, &timelock);
+    }
+
+    /// Get the timelock contract gating admin changes, if any.
+    pub fn get_timelock(e: Env) -> Option<Address> {
+        e.storage().instance().get::<_, Address>(&DataKey::Timelock)
+    }
+
+    /// Get current on-chain version (0 if legacy/uninitialized).
+    pub fn get_version(e: Env) -> u32 {
+        read_version(&e)
+    }
+
+    /// Summarize the contract for integrators in a single call.
+    pub fn get_contract_info(e: Env) -> ContractInfo {
+        ContractInfo {
+            admin: Self::get_admin(e.clone()),
+            nft_contract: Self::get_nft_contract(e.clone()),
+            version: read_version(&e),
+            total_commitments: Self::get_total_commitments(e.clone()),
+            tvl: Self::get_total_value_locked(e.clone()),
+            paused: Pausable::is_paused(&e),
+        }
+    }
+
+    /// Upgrade contract WASM (admin-only).
+    pub fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        require_admin(&e, &caller);
+        require_valid_wasm_hash(&e, &new_wasm_hash);
+        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Migrate storage from a previous version to CURRENT_VERSION (admin-only).
+    pub fn migrate(e: Env, caller: Address, from_version: u32) {
+        require_admin(&e, &caller);
+
+        let stored_version = read_version(&e);
+        if stored_version == CURRENT_VERSION {
+            fail(&e, CommitmentError::AlreadyMigrated, "migrate");
+        }
+        if from_version != stored_version || from_version > CURRENT_VERSION {
+            fail(&e, CommitmentError::InvalidVersion, "migrate");
+        }
+
+        // Ensure essential counters are initialized
+        if !e.storage().instance().has(&DataKey::TotalCommitments) {
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalCommitments, &0u64);
+        }
+        if !e.storage().instance().has(&DataKey::TotalValueLocked) {
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalValueLocked, &0i128);
+        }
+
+        e.storage()
+            .instance()
+            .set(&DataKey::Version, &CURRENT_VERSION);
+    }
+
     /// Update commitment value (called by allocation logic or oracle-fed keeper).
     /// Persists new_value to commitment.current_value and updates TotalValueLocked.
     pub fn update_value(e: Env, caller: Address, commitment_id: String, new_value: i128) {
@@ -577,20 +2823,24 @@ impl CommitmentCoreContract {
         if commitment.status != active_status {
             fail(&e, CommitmentError::NotActive, "update_value");
         }
+        require_not_frozen(&e, &commitment_id, "update_value");
+
+        // Throttle revaluations of the same commitment
+        require_update_interval(&e, &commitment_id, "update_value");
 
         let old_value = commitment.current_value;
         commitment.current_value = new_value;
 
         // Violation detection
-        let loss_percent = if commitment.amount > 0 {
-            (commitment.amount - new_value) * 100 / commitment.amount
-        } else {
-            0
-        };
+        let loss_percent = -SafeMath::pnl_percent(commitment.amount, new_value);
 
         let violated = loss_percent > commitment.rules.max_loss_percent as i128;
         if violated {
-            commitment.status = String::from_str(&e, "violated");
+            let old_status = commitment.status.clone();
+            transition_status(&e, &mut commitment, CommitmentStatus::Violated);
+            retire_reward_weight(&e, &commitment, false);
+            track_owner_active(&e, &commitment.owner, false);
+            Events::emit_status_changed(&e, &commitment_id, &old_status, &commitment.status);
             e.events().publish(
                 (symbol_short!("Violated"), commitment_id.clone()),
                 (
@@ -602,22 +2852,106 @@ impl CommitmentCoreContract {
         }
 
         set_commitment(&e, &commitment);
+        extend_commitment_ttl(&e);
+        sync_nft_value(&e, commitment.nft_token_id, new_value);
+        if violated {
+            notify_nft_violated(&e, commitment.nft_token_id);
+        }
 
-        // Update TVL
+        // Update TVL; a violated commitment leaves it entirely, as in
+        // `apply_violation`
+        let locked_delta = if violated {
+            -old_value
+        } else {
+            new_value - old_value
+        };
         let current_tvl = e
             .storage()
             .instance()
             .get::<_, i128>(&DataKey::TotalValueLocked)
             .unwrap_or(0);
-        e.storage().instance().set(
-            &DataKey::TotalValueLocked,
-            &(current_tvl - old_value + new_value),
-        );
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &(current_tvl + locked_delta));
+        track_owner_value(&e, &commitment.owner, locked_delta);
+        track_asset_tvl(&e, &commitment.asset_address, locked_delta);
 
-        e.events().publish(
-            (symbol_short!("ValUpd"), commitment_id),
-            (old_value, new_value, violated, e.ledger().timestamp()),
-        );
+        Events::emit_value_updated(&e, &commitment_id, old_value, new_value, violated);
+    }
+
+    /// Freeze a commitment under investigation (admin-only).
+    ///
+    /// Blocks value updates (`update_value`, `check_and_mark_violation`) and
+    /// `allocate` for this commitment only; `settle` and `early_exit` stay
+    /// available so the owner can still leave.
+    pub fn freeze_commitment(e: Env, caller: Address, commitment_id: String) {
+        require_admin(&e, &caller);
+        if !has_commitment(&e, &commitment_id) {
+            fail(&e, CommitmentError::CommitmentNotFound, "freeze_commitment");
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::Frozen(commitment_id.clone()), &true);
+        e.events()
+            .publish((symbol_short!("Frozen"), commitment_id), caller);
+    }
+
+    /// Lift a freeze set by `freeze_commitment` (admin-only).
+    pub fn unfreeze_commitment(e: Env, caller: Address, commitment_id: String) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .remove(&DataKey::Frozen(commitment_id.clone()));
+        e.events()
+            .publish((symbol_short!("Unfrozen"), commitment_id), caller);
+    }
+
+    /// Whether a commitment is frozen.
+    pub fn is_commitment_frozen(e: Env, commitment_id: String) -> bool {
+        e.storage().instance().has(&DataKey::Frozen(commitment_id))
+    }
+
+    /// Set the minimum seconds between value updates (`update_value` or an
+    /// oracle revaluation) for the same commitment (admin-only). Zero
+    /// disables the check.
+    pub fn set_min_update_interval(e: Env, caller: Address, seconds: u64) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::MinUpdateInterval, &seconds);
+    }
+
+    /// Get the minimum seconds between value updates.
+    pub fn get_min_update_interval(e: Env) -> u64 {
+        e.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::MinUpdateInterval)
+            .unwrap_or(0)
+    }
+
+    /// Set the minimum seconds a commitment must be held before
+    /// `early_exit` (admin-only). Zero disables the check.
+    pub fn set_min_hold_seconds(e: Env, caller: Address, seconds: u64) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::MinHoldSeconds, &seconds);
+    }
+
+    /// Get the minimum hold period before early exit.
+    pub fn get_min_hold_seconds(e: Env) -> u64 {
+        e.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::MinHoldSeconds)
+            .unwrap_or(0)
+    }
+
+    /// Get when a throttled value update last succeeded for a commitment.
+    /// Only recorded while a minimum update interval is configured.
+    pub fn get_last_updated_at(e: Env, commitment_id: String) -> Option<u64> {
+        e.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::LastUpdatedAt(commitment_id))
     }
 
     /// Check if commitment rules are violated
@@ -647,26 +2981,9 @@ impl CommitmentCoreContract {
             return false; // Already processed
         }
 
-        let current_time = e.ledger().timestamp();
-
-        // Check loss limit violation
-        // Calculate loss percentage using shared utilities, but handle zero-amount
-        // commitments gracefully to avoid panics. A zero-amount commitment cannot
-        // meaningfully violate a loss limit, so we treat its loss percent as 0.
-        let loss_percent = if commitment.amount > 0 {
-            SafeMath::loss_percent(commitment.amount, commitment.current_value)
-        } else {
-            0
-        };
-
-        // Convert max_loss_percent (u32) to i128 for comparison
-        let max_loss = commitment.rules.max_loss_percent as i128;
-        let loss_violated = loss_percent > max_loss;
-
-        // Check duration violation (expired)
-        let duration_violated = current_time >= commitment.expires_at;
-
-        let violated = loss_violated || duration_violated;
+        // Check loss limit and duration violations
+        let duration_violated = e.ledger().timestamp() >= commitment.expires_at;
+        let violated = has_rule_violation(&e, &commitment) || duration_violated;
 
         if violated {
             // Emit violation event
@@ -680,6 +2997,95 @@ impl CommitmentCoreContract {
         violated
     }
 
+    /// Persist the violated status for a commitment whose rules are breached.
+    ///
+    /// Callable by the admin or an authorized updater. Fails with `NoViolation`
+    /// unless the loss limit is breached; an expired commitment within its
+    /// limit is settled instead. The commitment's current value is removed
+    /// from TVL and, being no longer active, it can't be allocated.
+    pub fn mark_violated(e: Env, caller: Address, commitment_id: String) {
+        require_admin_or_updater(&e, &caller);
+
+        let mut commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "mark_violated"));
+
+        let active_status = String::from_str(&e, "active");
+        if commitment.status != active_status {
+            fail(&e, CommitmentError::NotActive, "mark_violated");
+        }
+        if !has_rule_violation(&e, &commitment) {
+            fail(&e, CommitmentError::NoViolation, "mark_violated");
+        }
+
+        apply_violation(&e, &mut commitment, &caller);
+    }
+
+    /// Revalue a commitment from the price oracle, then mark it violated if
+    /// its rules are breached (admin or authorized updater).
+    ///
+    /// The value is scaled by the asset's price move since it was last
+    /// anchored: `current_value * price / entry_price`. Commitments created
+    /// before the oracle was configured are anchored to the price seen on
+    /// their first check and keep their value for that call. A revaluation
+    /// counts as a value update for `MinUpdateInterval`. Fails if the oracle
+    /// has no fresh price. Returns whether the commitment was marked.
+    pub fn check_and_mark_violation(e: Env, caller: Address, commitment_id: String) -> bool {
+        require_admin_or_updater(&e, &caller);
+
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "check_and_mark_violation",
+            )
+        });
+        if commitment.status != String::from_str(&e, "active") {
+            fail(&e, CommitmentError::NotActive, "check_and_mark_violation");
+        }
+        require_not_frozen(&e, &commitment_id, "check_and_mark_violation");
+        let oracle = Self::get_price_oracle(e.clone()).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::OracleNotConfigured,
+                "check_and_mark_violation",
+            )
+        });
+
+        let price = read_oracle_price(&e, &oracle, &commitment.asset_address);
+        let entry_key = DataKey::EntryPrice(commitment_id.clone());
+        if let Some(entry_price) = e.storage().instance().get::<_, i128>(&entry_key) {
+            require_update_interval(&e, &commitment_id, "check_and_mark_violation");
+            let new_value =
+                SafeMath::div(SafeMath::mul(commitment.current_value, price), entry_price);
+            revalue(&e, &mut commitment, new_value);
+        }
+        // Later checks measure the move from this price
+        if price > 0 {
+            e.storage().instance().set(&entry_key, &price);
+        }
+
+        if !has_rule_violation(&e, &commitment) {
+            return false;
+        }
+        apply_violation(&e, &mut commitment, &caller);
+        true
+    }
+
+    /// Set the price oracle used by `check_and_mark_violation` (admin-only).
+    pub fn set_price_oracle(e: Env, caller: Address, oracle: Address) {
+        require_admin(&e, &caller);
+        e.storage().instance().set(&DataKey::PriceOracle, &oracle);
+        e.events()
+            .publish((Symbol::new(&e, "PriceOracleSet"), caller), oracle);
+    }
+
+    /// Get the configured price oracle, if any.
+    pub fn get_price_oracle(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PriceOracle)
+    }
+
     /// Get detailed violation information
     /// Returns a tuple: (has_violations, loss_violated, duration_violated, loss_percent, time_remaining)
     pub fn get_violation_details(e: Env, commitment_id: String) -> (bool, bool, bool, i128, u64) {
@@ -694,12 +3100,7 @@ impl CommitmentCoreContract {
         let current_time = e.ledger().timestamp();
 
         // Calculate loss percentage
-        let loss_amount = commitment.amount - commitment.current_value;
-        let loss_percent = if commitment.amount > 0 {
-            (loss_amount * 100) / commitment.amount
-        } else {
-            0
-        };
+        let loss_percent = loss_percent(&e, &commitment);
 
         // Check loss limit violation
         let max_loss = commitment.rules.max_loss_percent as i128;
@@ -728,21 +3129,141 @@ impl CommitmentCoreContract {
 
     /// Settle commitment at maturity
     ///
+    /// Proceeds go to the owner, unless the NFT has since been transferred:
+    /// then its current holder is paid and has the owner's settlement window.
+    ///
     /// # Reentrancy Protection
     /// Uses checks-effects-interactions pattern with reentrancy guard.
-    pub fn settle(e: Env, commitment_id: String) {
-        // Reentrancy protection
-        require_no_reentrancy(&e);
-        set_reentrancy_guard(&e, true);
+    pub fn settle(e: Env, commitment_id: String, caller: Address) {
+        Self::settle_internal(e, commitment_id, caller, None, false);
+    }
 
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
+    /// Settle a matured commitment on behalf of whoever now holds its NFT.
+    ///
+    /// The NFT can change hands once it is no longer locked, so the holder
+    /// may not be `commitment.owner`. `holder` must authorize and be the
+    /// NFT's current `owner_of`; proceeds go straight to them in the
+    /// commitment asset, bypassing the original owner's payout hook and
+    /// payout asset. The original owner's self-settlement window does not
+    /// apply to the holder. A shared commitment still pays its contributors.
+    pub fn settle_as_nft_holder(e: Env, token_id: u32, holder: Address) {
+        // `holder` authorizes inside `settle_internal` as the caller
+        let commitment = Self::get_commitment_by_nft(e.clone(), token_id);
 
-        // CHECKS: Get and validate commitment
-        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::CommitmentNotFound, "settle")
-        });
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "settle_as_nft_holder"));
+        let mut args = Vec::new(&e);
+        args.push_back(token_id.into_val(&e));
+        let nft_owner: Address =
+            e.invoke_contract(&nft_contract, &Symbol::new(&e, "owner_of"), args);
+        if nft_owner != holder {
+            fail(&e, CommitmentError::Unauthorized, "settle_as_nft_holder");
+        }
+
+        Self::settle_internal(
+            e,
+            commitment.commitment_id,
+            holder.clone(),
+            Some(holder),
+            false,
+        );
+    }
+
+    /// Settle every eligible commitment in `commitment_ids` (at most
+    /// `MAX_SETTLE_BATCH`) for a keeper closing out many at once.
+    ///
+    /// Each commitment goes through the same flow as `settle`; missing,
+    /// inactive, unexpired ones, and ones `caller` may not settle yet, are
+    /// skipped rather than failing the call. The settled tokens are then
+    /// released through the NFT contract's `settle_batch` in one call.
+    /// Returns the ids that were settled.
+    pub fn settle_batch(e: Env, caller: Address, commitment_ids: Vec<String>) -> Vec<String> {
+        caller.require_auth();
+        if commitment_ids.len() > MAX_SETTLE_BATCH {
+            fail(&e, CommitmentError::BatchTooLarge, "settle_batch");
+        }
+
+        let mut settled = Vec::new(&e);
+        let mut token_ids: Vec<u32> = Vec::new(&e);
+        for commitment_id in commitment_ids.iter() {
+            let Some(commitment) = read_commitment(&e, &commitment_id) else {
+                continue;
+            };
+            if !Self::is_settleable(&e, &commitment, &caller) {
+                continue;
+            }
+            Self::settle_internal(e.clone(), commitment_id.clone(), caller.clone(), None, true);
+            settled.push_back(commitment_id);
+            token_ids.push_back(commitment.nft_token_id);
+        }
+
+        if !token_ids.is_empty() {
+            let nft_contract = e
+                .storage()
+                .instance()
+                .get::<_, Address>(&DataKey::NftContract)
+                .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "settle_batch"));
+            let mut args = Vec::new(&e);
+            args.push_back(e.current_contract_address().into_val(&e));
+            args.push_back(token_ids.into_val(&e));
+            e.invoke_contract::<Vec<u32>>(&nft_contract, &Symbol::new(&e, "settle_batch"), args);
+        }
+        settled
+    }
+
+    /// Whether `settle` by `caller` would pass its commitment checks now.
+    fn is_settleable(e: &Env, commitment: &Commitment, caller: &Address) -> bool {
+        let now = e.ledger().timestamp();
+        if commitment.status != String::from_str(e, "active") || now < commitment.expires_at {
+            return false;
+        }
+        let first_party =
+            moved_nft_holder(e, commitment).unwrap_or_else(|| commitment.owner.clone());
+        let self_window = Self::get_settlement_self_window(e.clone());
+        if *caller != first_party && now < commitment.expires_at.saturating_add(self_window) {
+            return false;
+        }
+        let min_attestations = Self::get_min_attestations(e.clone());
+        if min_attestations > 0
+            && read_attestation_count(e, &commitment.commitment_id) < min_attestations as u64
+        {
+            return false;
+        }
+        insurance_top_up(e, commitment) <= read_insurance_fund(e, &commitment.asset_address)
+    }
+
+    /// Shared settle flow. `holder` is set when an NFT holder settles, and
+    /// receives the proceeds in place of the owner's payout route. A plain
+    /// `settle` of a commitment whose NFT has moved pays the current holder
+    /// the same way, so the original owner cannot collect after selling it.
+    /// `batched` is set by `settle_batch`, which has already authorized
+    /// `caller` and releases the NFT itself.
+    fn settle_internal(
+        e: Env,
+        commitment_id: String,
+        caller: Address,
+        holder: Option<Address>,
+        batched: bool,
+    ) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+
+        // CHECKS: Get and validate commitment
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "settle")
+        });
+
+        if !batched {
+            caller.require_auth();
+        }
 
         // Verify commitment is expired
         let current_time = e.ledger().timestamp();
@@ -751,6 +3272,20 @@ impl CommitmentCoreContract {
             fail(&e, CommitmentError::NotExpired, "settle");
         }
 
+        // Once the NFT has been sold on, its holder takes the owner's place
+        let holder = holder.or_else(|| moved_nft_holder(&e, &commitment));
+
+        // The owner (or NFT holder) gets first chance to settle; keepers
+        // only after the window
+        let self_window = Self::get_settlement_self_window(e.clone());
+        let first_party = holder.as_ref().unwrap_or(&commitment.owner);
+        if caller != *first_party
+            && current_time < commitment.expires_at.saturating_add(self_window)
+        {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::Unauthorized, "settle");
+        }
+
         // Verify commitment is active
         let active_status = String::from_str(&e, "active");
         if commitment.status != active_status {
@@ -758,10 +3293,41 @@ impl CommitmentCoreContract {
             fail(&e, CommitmentError::NotActive, "settle");
         }
 
+        // High-assurance deployments require enough attestations on record
+        let min_attestations = Self::get_min_attestations(e.clone());
+        if min_attestations > 0
+            && read_attestation_count(&e, &commitment_id) < min_attestations as u64
+        {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientAttestations, "settle");
+        }
+
+        // "safe" commitments are topped up to their loss floor from the
+        // insurance fund for the asset
+        let top_up = insurance_top_up(&e, &commitment);
+        let fund = read_insurance_fund(&e, &commitment.asset_address);
+        if top_up > fund {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientInsurance, "settle");
+        }
+
         // EFFECTS: Update state before external calls
-        let settlement_amount = commitment.current_value;
-        commitment.status = String::from_str(&e, "settled");
+        let booked_value = commitment.current_value;
+        let settlement_amount = SafeMath::add(booked_value, top_up);
+        let old_status = commitment.status.clone();
+        transition_status(&e, &mut commitment, CommitmentStatus::Settled);
         set_commitment(&e, &commitment);
+        write_settlement_receipt(&e, &commitment, settlement_amount);
+        retire_reward_weight(&e, &commitment, true);
+        track_owner_active(&e, &commitment.owner, false);
+        track_owner_value(&e, &commitment.owner, -booked_value);
+        track_asset_tvl(&e, &commitment.asset_address, -booked_value);
+        if top_up > 0 {
+            e.storage().instance().set(
+                &DataKey::InsuranceFund(commitment.asset_address.clone()),
+                &(fund - top_up),
+            );
+        }
 
         // Decrease total value locked
         let current_tvl = e
@@ -769,16 +3335,32 @@ impl CommitmentCoreContract {
             .instance()
             .get::<_, i128>(&DataKey::TotalValueLocked)
             .unwrap_or(0);
-        let new_tvl = current_tvl - settlement_amount;
+        let new_tvl = current_tvl - booked_value;
         e.storage()
             .instance()
             .set(&DataKey::TotalValueLocked, &new_tvl);
+        let settled_total =
+            SafeMath::add(Self::get_total_settled_value(e.clone()), settlement_amount);
+        e.storage()
+            .instance()
+            .set(&ExtDataKey::TotalSettledValue, &settled_total);
 
         // INTERACTIONS: External calls (token transfer, NFT settlement)
-        // Transfer assets back to owner
+        // Transfer assets to the contributors of a shared commitment, the NFT
+        // holder, or back to owner / their payout hook
         let contract_address = e.current_contract_address();
-        let token_client = token::Client::new(&e, &commitment.asset_address);
-        token_client.transfer(&contract_address, &commitment.owner, &settlement_amount);
+        if !pay_out_shares(&e, &commitment, settlement_amount) {
+            match &holder {
+                Some(holder) if *holder != commitment.owner => {
+                    token::Client::new(&e, &commitment.asset_address).transfer(
+                        &contract_address,
+                        holder,
+                        &settlement_amount,
+                    );
+                }
+                _ => pay_out(&e, &commitment, settlement_amount),
+            }
+        }
 
         // Call NFT contract to mark NFT as settled (pass self as caller for access control)
         let nft_contract = e
@@ -789,19 +3371,29 @@ impl CommitmentCoreContract {
                 set_reentrancy_guard(&e, false);
                 fail(&e, CommitmentError::NotInitialized, "settle")
             });
-        let mut args = Vec::new(&e);
-        args.push_back(contract_address.into_val(&e));
-        args.push_back(commitment.nft_token_id.into_val(&e));
-        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle"), args);
+        // A token already released for trading (inactive) needs no settle call
+        let nft_active = holder.is_none() || {
+            let mut args = Vec::new(&e);
+            args.push_back(commitment.nft_token_id.into_val(&e));
+            e.invoke_contract::<bool>(&nft_contract, &Symbol::new(&e, "is_active"), args)
+        };
+        // Record the payout on the NFT as the value it settled at
+        sync_nft_value(&e, commitment.nft_token_id, settlement_amount);
+        if nft_active && !batched {
+            let mut args = Vec::new(&e);
+            args.push_back(contract_address.into_val(&e));
+            args.push_back(commitment.nft_token_id.into_val(&e));
+            args.push_back(NFT_SETTLED_MATURED.into_val(&e));
+            e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle_with_reason"), args);
+        }
+        finalize_attestation_metrics(&e, &commitment_id);
 
         // Clear reentrancy guard
         set_reentrancy_guard(&e, false);
 
         // Emit settlement event
-        e.events().publish(
-            (symbol_short!("Settled"), commitment_id),
-            (settlement_amount, e.ledger().timestamp()),
-        );
+        Events::emit_status_changed(&e, &commitment_id, &old_status, &commitment.status);
+        Events::emit_commitment_settled(&e, &commitment_id, settlement_amount);
     }
 
     pub fn early_exit(e: Env, commitment_id: String, caller: Address) {
@@ -832,17 +3424,39 @@ impl CommitmentCoreContract {
             fail(&e, CommitmentError::NotActive, "early_exit");
         }
 
-        // EFFECTS: Calculate penalty using shared utilities
-        let penalty_amount = SafeMath::penalty_amount(
-            commitment.current_value,
-            commitment.rules.early_exit_penalty,
-        );
+        // No same-block create-and-exit: hold for at least MinHoldSeconds
+        let min_hold = Self::get_min_hold_seconds(e.clone());
+        if e.ledger().timestamp() < commitment.created_at.saturating_add(min_hold) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::HoldPeriodActive, "early_exit");
+        }
+
+        // EFFECTS: Calculate penalty using shared utilities (zero once waived)
+        let penalty_amount = if Self::is_penalty_waived(e.clone(), commitment_id.clone()) {
+            0
+        } else {
+            let penalty_bps = commitment.rules.early_exit_penalty.saturating_mul(100);
+            match SafeMath::checked_percent_of(commitment.current_value, penalty_bps) {
+                Ok(penalty) => penalty,
+                Err(_) => {
+                    set_reentrancy_guard(&e, false);
+                    fail(&e, CommitmentError::ArithmeticOverflow, "early_exit")
+                }
+            }
+        };
         let returned_amount = SafeMath::sub(commitment.current_value, penalty_amount);
 
         // Update commitment status to early_exit
-        commitment.status = String::from_str(&e, "early_exit");
+        let exited_value = commitment.current_value;
+        let old_status = commitment.status.clone();
+        transition_status(&e, &mut commitment, CommitmentStatus::EarlyExit);
         commitment.current_value = 0; // All value has been distributed
         set_commitment(&e, &commitment);
+        write_settlement_receipt(&e, &commitment, returned_amount);
+        retire_reward_weight(&e, &commitment, false);
+        track_owner_active(&e, &commitment.owner, false);
+        track_owner_value(&e, &commitment.owner, -exited_value);
+        track_asset_tvl(&e, &commitment.asset_address, -exited_value);
 
         // Decrease total value locked by full current value (no longer locked)
         let current_tvl = e
@@ -850,7 +3464,7 @@ impl CommitmentCoreContract {
             .instance()
             .get::<_, i128>(&DataKey::TotalValueLocked)
             .unwrap_or(0);
-        let new_tvl = current_tvl - commitment.current_value;
+        let new_tvl = current_tvl - exited_value;
         e.storage()
             .instance()
             .set(&DataKey::TotalValueLocked, &new_tvl);
@@ -860,10 +3474,15 @@ impl CommitmentCoreContract {
         let contract_address = e.current_contract_address();
         let token_client = token::Client::new(&e, &commitment.asset_address);
 
-        if returned_amount > 0 {
+        if returned_amount > 0 && !pay_out_shares(&e, &commitment, returned_amount) {
             token_client.transfer(&contract_address, &commitment.owner, &returned_amount);
         }
 
+        // Route the penalty to treasury and rewards pool when configured;
+        // otherwise it stays in the contract.
+        let (treasury_amount, rewards_amount) =
+            Self::distribute_penalty(&e, &token_client, &contract_address, penalty_amount);
+
         // Call NFT contract to update NFT status (mark as inactive/early_exited)
         let nft_contract = e
             .storage()
@@ -874,36 +3493,137 @@ impl CommitmentCoreContract {
                 fail(&e, CommitmentError::NotInitialized, "early_exit")
             });
 
-        // Call settle on NFT to mark it as inactive (pass self as caller for access control)
+        // Record what the owner actually received, then settle the NFT as an
+        // early exit, which it accepts before expiry (pass self as caller for
+        // access control)
+        sync_nft_value(&e, commitment.nft_token_id, returned_amount);
         let core_address = e.current_contract_address();
         let mut args = Vec::new(&e);
         args.push_back(core_address.into_val(&e));
         args.push_back(commitment.nft_token_id.into_val(&e));
-        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle"), args);
+        args.push_back(NFT_SETTLED_EARLY_EXIT.into_val(&e));
+        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle_with_reason"), args);
+        finalize_attestation_metrics(&e, &commitment_id);
 
         // Clear reentrancy guard
         set_reentrancy_guard(&e, false);
 
         // Emit early exit event with detailed information
+        Events::emit_status_changed(&e, &commitment_id, &old_status, &commitment.status);
         e.events().publish(
             (
                 symbol_short!("EarlyExt"),
                 commitment_id.clone(),
                 caller.clone(),
             ),
-            (penalty_amount, returned_amount, e.ledger().timestamp()),
+            (
+                penalty_amount,
+                returned_amount,
+                treasury_amount,
+                rewards_amount,
+                e.ledger().timestamp(),
+            ),
         );
     }
 
+    /// Split `penalty_amount` per the configured `PenaltySplit` and transfer the
+    /// portions. Without a rewards pool address the rewards share stays in this
+    /// contract for matured commitments to `claim_reward`. Returns
+    /// `(treasury_amount, rewards_amount)`, both 0 if no split or treasury is
+    /// configured.
+    fn distribute_penalty(
+        e: &Env,
+        token_client: &token::Client,
+        from: &Address,
+        penalty_amount: i128,
+    ) -> (i128, i128) {
+        let split = e
+            .storage()
+            .instance()
+            .get::<_, PenaltySplit>(&DataKey::PenaltySplit);
+        let treasury = e.storage().instance().get::<_, Address>(&DataKey::Treasury);
+        let rewards_pool = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::RewardsPool);
+
+        let (split, treasury) = match (split, treasury) {
+            (Some(split), Some(treasury)) => (split, treasury),
+            _ => return (0, 0),
+        };
+        if penalty_amount <= 0 {
+            return (0, 0);
+        }
+
+        // Rewards pool takes the rounding remainder
+        let treasury_amount = fee_from_bps(penalty_amount, split.treasury_bps);
+        let rewards_amount = penalty_amount - treasury_amount;
+
+        if treasury_amount > 0 {
+            token_client.transfer(from, &treasury, &treasury_amount);
+        }
+        if rewards_amount > 0 {
+            match rewards_pool {
+                Some(rewards_pool) => token_client.transfer(from, &rewards_pool, &rewards_amount),
+                None => accrue_rewards(e, &token_client.address, rewards_amount),
+            }
+        }
+        (treasury_amount, rewards_amount)
+    }
+
     /// Allocate liquidity (called by allocation strategy)
     ///
+    /// `target_pool` must have been approved by the owner via `approve_pool`.
+    ///
     /// # Reentrancy Protection
     /// Uses checks-effects-interactions pattern with reentrancy guard.
     pub fn allocate(e: Env, commitment_id: String, target_pool: Address, amount: i128) {
+        Self::allocate_internal(e, commitment_id, target_pool, amount, None);
+    }
+
+    /// Allocate liquidity to a share-issuing pool with slippage protection.
+    ///
+    /// After transferring `amount`, calls `deposit(depositor, amount) -> i128`
+    /// on `target_pool` and reverts the whole allocation if it reports fewer
+    /// than `min_shares` shares.
+    pub fn allocate_with_min_out(
+        e: Env,
+        commitment_id: String,
+        target_pool: Address,
+        amount: i128,
+        min_shares: i128,
+    ) -> i128 {
+        Self::allocate_internal(e, commitment_id, target_pool, amount, Some(min_shares))
+    }
+
+    /// Shared body of `allocate` and `allocate_with_min_out`. With
+    /// `min_shares` set, the pool's `deposit` is invoked and its share count
+    /// checked; returns the shares issued (0 for a plain transfer).
+    fn allocate_internal(
+        e: Env,
+        commitment_id: String,
+        target_pool: Address,
+        amount: i128,
+        min_shares: Option<i128>,
+    ) -> i128 {
         // Reentrancy protection
         require_no_reentrancy(&e);
         set_reentrancy_guard(&e, true);
+        let shares =
+            Self::allocate_guarded(e.clone(), commitment_id, target_pool, amount, min_shares);
+        set_reentrancy_guard(&e, false);
+        shares
+    }
 
+    /// `allocate` checks, transfer and event; the caller holds the
+    /// reentrancy guard and clears it afterwards.
+    fn allocate_guarded(
+        e: Env,
+        commitment_id: String,
+        target_pool: Address,
+        amount: i128,
+        min_shares: Option<i128>,
+    ) -> i128 {
         // Check if contract is paused
         Pausable::require_not_paused(&e);
 
@@ -928,6 +3648,14 @@ impl CommitmentCoreContract {
             set_reentrancy_guard(&e, false);
             fail(&e, CommitmentError::NotActive, "allocate");
         }
+        if Self::is_commitment_frozen(e.clone(), commitment_id.clone()) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentFrozen, "allocate");
+        }
+        if !read_allowed_pools(&e, &commitment_id).contains(&target_pool) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::PoolNotApproved, "allocate");
+        }
 
         // Verify sufficient balance
         if commitment.current_value < amount {
@@ -935,10 +3663,20 @@ impl CommitmentCoreContract {
             fail(&e, CommitmentError::InsufficientBalance, "allocate");
         }
 
+        // Block allocation for commitments below the compliance threshold
+        if let Some(score) = read_compliance_score(&e, &commitment_id) {
+            if score < Self::get_min_alloc_score(e.clone()) {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::ComplianceScoreTooLow, "allocate");
+            }
+        }
+
         // EFFECTS: Update commitment value before external call
         let mut updated_commitment = commitment;
         updated_commitment.current_value = updated_commitment.current_value - amount;
         set_commitment(&e, &updated_commitment);
+        track_owner_value(&e, &updated_commitment.owner, -amount);
+        record_allocation(&e, &commitment_id, &target_pool, amount);
 
         // INTERACTIONS: External call (token transfer)
         // Transfer assets to target pool
@@ -946,14 +3684,301 @@ impl CommitmentCoreContract {
         let token_client = token::Client::new(&e, &updated_commitment.asset_address);
         token_client.transfer(&contract_address, &target_pool, &amount);
 
-        // Clear reentrancy guard
-        set_reentrancy_guard(&e, false);
+        // Share-issuing pools report what the deposit was worth
+        let shares = match min_shares {
+            Some(min_shares) => {
+                let mut args = Vec::new(&e);
+                args.push_back(contract_address.into_val(&e));
+                args.push_back(amount.into_val(&e));
+                let shares: i128 =
+                    e.invoke_contract(&target_pool, &Symbol::new(&e, "deposit"), args);
+                if shares < min_shares {
+                    set_reentrancy_guard(&e, false);
+                    fail(
+                        &e,
+                        CommitmentError::SlippageExceeded,
+                        "allocate_with_min_out",
+                    );
+                }
+                shares
+            }
+            None => 0,
+        };
 
         // Emit allocation event
         e.events().publish(
             (symbol_short!("Alloc"), commitment_id, target_pool),
             (amount, e.ledger().timestamp()),
         );
+        shares
+    }
+
+    /// Return funds from a pool to a commitment (called by the pool).
+    ///
+    /// The pool must authorize the call and cannot return more than is
+    /// recorded as allocated to it for this commitment, which must still be
+    /// active.
+    pub fn deallocate(e: Env, commitment_id: String, pool: Address, amount: i128) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        pool.require_auth();
+
+        // CHECKS: Validate inputs and allocation
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "deallocate");
+        }
+
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "deallocate")
+        });
+
+        // Closed commitments have no way to pay returned funds back out
+        let active_status = String::from_str(&e, "active");
+        if commitment.status != active_status {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "deallocate");
+        }
+
+        let allocated = read_allocations(&e, &commitment_id)
+            .iter()
+            .find(|r| r.pool == pool)
+            .map(|r| r.amount)
+            .unwrap_or(0);
+        if allocated < amount {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientBalance, "deallocate");
+        }
+
+        // EFFECTS: Update commitment value and registry
+        commitment.current_value += amount;
+        set_commitment(&e, &commitment);
+        track_owner_value(&e, &commitment.owner, amount);
+        record_allocation(&e, &commitment_id, &pool, -amount);
+
+        // INTERACTIONS: Pull funds back from the pool
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &commitment.asset_address);
+        token_client.transfer(&pool, &contract_address, &amount);
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (symbol_short!("Dealloc"), commitment_id, pool),
+            (amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Set the attestation engine consulted for allocation compliance checks.
+    ///
+    /// This function is restricted to the contract admin. Set after deployment
+    /// since the engine is itself initialized with this contract's address.
+    pub fn set_attestation_engine(e: Env, caller: Address, attestation_engine: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::AttestationEngine, &attestation_engine);
+    }
+
+    /// Get the attestation engine address, if configured.
+    pub fn get_attestation_engine(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::AttestationEngine)
+    }
+
+    /// Set the minimum compliance score (0-100) a commitment needs to be allocated.
+    ///
+    /// This function is restricted to the contract admin. 0 disables the check;
+    /// a non-zero minimum blocks allocation until an attestation engine is set.
+    pub fn set_min_alloc_score(e: Env, caller: Address, min_score: u32) {
+        require_admin(&e, &caller);
+        Validation::require_valid_percent(min_score);
+        e.storage()
+            .instance()
+            .set(&DataKey::MinAllocScore, &min_score);
+    }
+
+    /// Get the minimum compliance score required to allocate (0 = disabled).
+    pub fn get_min_alloc_score(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::MinAllocScore)
+            .unwrap_or(0)
+    }
+
+    /// Set how many attestations the engine must hold for a commitment
+    /// before it can settle.
+    ///
+    /// This function is restricted to the contract admin. 0 disables the
+    /// check; without an engine configured the count is taken as 0.
+    pub fn set_min_attestations(e: Env, caller: Address, count: u32) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::MinAttestations, &count);
+    }
+
+    /// Get the attestations required before settlement (0 = disabled).
+    pub fn get_min_attestations(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::MinAttestations)
+            .unwrap_or(0)
+    }
+
+    /// Estimate what `settle` would pay out for an active commitment at maturity.
+    ///
+    /// Returns `(gross, net_after_fees)`: `gross` is the current value plus
+    /// any insurance top-up a "safe" commitment is due, capped at what the
+    /// fund holds. Settlement at maturity charges no fee and no early-exit
+    /// penalty, so `net_after_fees` currently equals `gross`.
+    pub fn project_settlement(e: Env, commitment_id: String) -> (i128, i128) {
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "project_settlement",
+            )
+        });
+        if commitment.status != String::from_str(&e, "active") {
+            fail(&e, CommitmentError::NotActive, "project_settlement");
+        }
+
+        let top_up = insurance_top_up(&e, &commitment)
+            .min(read_insurance_fund(&e, &commitment.asset_address));
+        let gross = SafeMath::add(commitment.current_value, top_up);
+        (gross, gross)
+    }
+
+    /// Get the payout record of a settled or early-exited commitment.
+    pub fn get_settlement_receipt(e: Env, commitment_id: String) -> Option<SettlementReceipt> {
+        e.storage()
+            .instance()
+            .get(&DataKey::SettlementReceipt(commitment_id))
+    }
+
+    /// Set how long after expiry only the owner may call `settle` (admin-only).
+    ///
+    /// Zero (the default) lets anyone settle as soon as a commitment expires.
+    pub fn set_settlement_self_window(e: Env, caller: Address, seconds: u64) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::SettlementSelfWindow, &seconds);
+    }
+
+    /// Get the owner-only settlement window in seconds.
+    pub fn get_settlement_self_window(e: Env) -> u64 {
+        e.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::SettlementSelfWindow)
+            .unwrap_or(0)
+    }
+
+    /// Set the weekdays new commitments must not mature on (admin-only).
+    ///
+    /// Bit `1 << TimeUtils::weekday(..)` marks a non-settlement day; expiries
+    /// landing on one roll forward to the next allowed day. Zero turns
+    /// calendar mode off. Existing commitments keep their `expires_at`.
+    pub fn set_settlement_calendar(e: Env, caller: Address, skip_days_mask: u32) {
+        require_admin(&e, &caller);
+        if skip_days_mask >= ALL_WEEKDAYS_MASK {
+            fail(
+                &e,
+                CommitmentError::InvalidCalendar,
+                "set_settlement_calendar",
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::SettlementCalendar, &skip_days_mask);
+        e.events()
+            .publish((symbol_short!("CalSet"), caller), skip_days_mask);
+    }
+
+    /// Get the non-settlement weekday mask (0 when calendar mode is off).
+    pub fn get_settlement_calendar(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::SettlementCalendar)
+            .unwrap_or(0)
+    }
+
+    /// Fund the insurance buffer for `asset_address` (admin-only).
+    ///
+    /// Transfers `amount` from the admin into the contract. `settle` draws on
+    /// this balance to lift "safe" commitments back to their loss floor.
+    pub fn deposit_insurance(e: Env, caller: Address, asset_address: Address, amount: i128) {
+        require_admin(&e, &caller);
+        if amount <= 0 {
+            fail(&e, CommitmentError::InvalidAmount, "deposit_insurance");
+        }
+
+        let contract_address = e.current_contract_address();
+        transfer_assets(&e, &caller, &contract_address, &asset_address, amount);
+
+        let balance = SafeMath::add(read_insurance_fund(&e, &asset_address), amount);
+        e.storage()
+            .instance()
+            .set(&DataKey::InsuranceFund(asset_address.clone()), &balance);
+        e.events()
+            .publish((symbol_short!("InsDep"), asset_address), (amount, balance));
+    }
+
+    /// Get the insurance fund balance for `asset_address`.
+    pub fn get_insurance_fund(e: Env, asset_address: Address) -> i128 {
+        read_insurance_fund(&e, &asset_address)
+    }
+
+    /// Approve `pool` as an `allocate` target for a commitment (owner-only).
+    ///
+    /// `allocate` and `allocate_with_min_out` only move funds to pools on
+    /// this list; approving a pool twice is a no-op.
+    pub fn approve_pool(e: Env, owner: Address, commitment_id: String, pool: Address) {
+        owner.require_auth();
+        let commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "approve_pool"));
+        if commitment.owner != owner {
+            fail(&e, CommitmentError::Unauthorized, "approve_pool");
+        }
+        write_allowed_pool(&e, &commitment_id, &pool);
+        e.events()
+            .publish((symbol_short!("PoolAppr"), commitment_id), pool);
+    }
+
+    /// Remove `pool` from a commitment's approved allocation targets
+    /// (owner-only). Funds already allocated there are unaffected.
+    pub fn revoke_pool(e: Env, owner: Address, commitment_id: String, pool: Address) {
+        owner.require_auth();
+        let commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "revoke_pool"));
+        if commitment.owner != owner {
+            fail(&e, CommitmentError::Unauthorized, "revoke_pool");
+        }
+        let mut pools = read_allowed_pools(&e, &commitment_id);
+        if let Some(index) = pools.first_index_of(&pool) {
+            pools.remove(index);
+            e.storage()
+                .instance()
+                .set(&ExtDataKey::AllowedPools(commitment_id.clone()), &pools);
+        }
+        e.events()
+            .publish((symbol_short!("PoolRevk"), commitment_id), pool);
+    }
+
+    /// Get the pools the owner approved as allocation targets.
+    pub fn get_allowed_pools(e: Env, commitment_id: String) -> Vec<Address> {
+        read_allowed_pools(&e, &commitment_id)
+    }
+
+    /// Get where a commitment's funds are currently allocated.
+    pub fn get_allocations(e: Env, commitment_id: String) -> Vec<AllocationRecord> {
+        read_allocations(&e, &commitment_id)
     }
 
     /// Configure rate limits for this contract's functions.
@@ -970,6 +3995,28 @@ impl CommitmentCoreContract {
         RateLimiter::set_limit(&e, &function, window_seconds, max_calls);
     }
 
+    /// Configure a protocol-wide rate limit shared by all callers of `function`.
+    ///
+    /// This function is restricted to the contract admin.
+    pub fn set_global_limit(
+        e: Env,
+        caller: Address,
+        function: Symbol,
+        window_seconds: u64,
+        max_calls: u32,
+    ) {
+        require_admin(&e, &caller);
+        RateLimiter::set_global_limit(&e, &function, window_seconds, max_calls);
+    }
+
+    /// Choose fixed or sliding windows for a rate-limited function.
+    ///
+    /// This function is restricted to the contract admin.
+    pub fn set_limit_mode(e: Env, caller: Address, function: Symbol, mode: RateMode) {
+        require_admin(&e, &caller);
+        RateLimiter::set_mode(&e, &function, mode);
+    }
+
     /// Set or clear rate limit exemption for an address.
     ///
     /// This function is restricted to the contract admin.
@@ -978,6 +4025,147 @@ impl CommitmentCoreContract {
         RateLimiter::set_exempt(&e, &address, exempt);
     }
 
+    /// Whether `address` bypasses this contract's rate limits.
+    pub fn is_rate_limit_exempt(e: Env, address: Address) -> bool {
+        RateLimiter::is_exempt(&e, &address)
+    }
+
+    /// Set the maximum allowed commitment duration in days.
+    ///
+    /// This function is restricted to the contract admin.
+    pub fn set_max_duration_days(e: Env, caller: Address, max_duration_days: u32) {
+        require_admin(&e, &caller);
+        if max_duration_days == 0 {
+            fail(
+                &e,
+                CommitmentError::InvalidDuration,
+                "set_max_duration_days",
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::MaxDurationDays, &max_duration_days);
+    }
+
+    /// Get the maximum allowed commitment duration in days.
+    pub fn get_max_duration_days(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::MaxDurationDays)
+            .unwrap_or(DEFAULT_MAX_DURATION_DAYS)
+    }
+
+    /// Set how early exit penalties are split between treasury and rewards pool.
+    ///
+    /// This function is restricted to the contract admin. `treasury_bps` and
+    /// `rewards_bps` must sum to 10000.
+    pub fn set_penalty_split(e: Env, caller: Address, treasury_bps: u32, rewards_bps: u32) {
+        require_admin(&e, &caller);
+        if treasury_bps.checked_add(rewards_bps) != Some(BPS_MAX) {
+            fail(
+                &e,
+                CommitmentError::InvalidPenaltySplit,
+                "set_penalty_split",
+            );
+        }
+        let split = PenaltySplit {
+            treasury_bps,
+            rewards_bps,
+        };
+        e.storage().instance().set(&DataKey::PenaltySplit, &split);
+        e.events().publish(
+            (symbol_short!("PenSplit"), caller),
+            (treasury_bps, rewards_bps),
+        );
+    }
+
+    /// Get the configured penalty split, if any.
+    pub fn get_penalty_split(e: Env) -> Option<PenaltySplit> {
+        e.storage()
+            .instance()
+            .get::<_, PenaltySplit>(&DataKey::PenaltySplit)
+    }
+
+    /// Set the treasury and rewards pool addresses that receive penalty shares.
+    ///
+    /// This function is restricted to the contract admin. With `rewards_pool`
+    /// set to `None` the rewards share is kept for `claim_reward`.
+    pub fn set_penalty_recipients(
+        e: Env,
+        caller: Address,
+        treasury: Address,
+        rewards_pool: Option<Address>,
+    ) {
+        require_admin(&e, &caller);
+        e.storage().instance().set(&DataKey::Treasury, &treasury);
+        match rewards_pool {
+            Some(rewards_pool) => e
+                .storage()
+                .instance()
+                .set(&DataKey::RewardsPool, &rewards_pool),
+            None => e.storage().instance().remove(&DataKey::RewardsPool),
+        }
+    }
+
+    /// Get the reward a commitment can claim (0 until it has settled).
+    pub fn get_claimable_reward(e: Env, commitment_id: String) -> i128 {
+        if e.storage()
+            .instance()
+            .has(&DataKey::RewardClaimed(commitment_id.clone()))
+        {
+            return 0;
+        }
+        read_i128(&e, &DataKey::RewardAccrued(commitment_id))
+    }
+
+    /// Claim the penalty-funded reward earned by a commitment held to maturity.
+    ///
+    /// The reward is a pro-rata share, by `amount * duration_days`, of the
+    /// penalties retained while the commitment was active, rounded down. Can be
+    /// claimed once, after `settle`. Returns the amount paid.
+    pub fn claim_reward(e: Env, commitment_id: String, owner: Address) -> i128 {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        owner.require_auth();
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "claim_reward")
+        });
+        if commitment.owner != owner {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::Unauthorized, "claim_reward");
+        }
+        if commitment.status != String::from_str(&e, "settled") {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidStatus, "claim_reward");
+        }
+
+        let claimed_key = DataKey::RewardClaimed(commitment_id.clone());
+        if e.storage().instance().has(&claimed_key) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::RewardAlreadyClaimed, "claim_reward");
+        }
+
+        // EFFECTS
+        let reward = read_i128(&e, &DataKey::RewardAccrued(commitment_id.clone()));
+        e.storage().instance().set(&claimed_key, &true);
+
+        // INTERACTIONS
+        if reward > 0 {
+            let token_client = token::Client::new(&e, &commitment.asset_address);
+            token_client.transfer(&e.current_contract_address(), &owner, &reward);
+        }
+
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (symbol_short!("RwdClaim"), commitment_id, owner),
+            (reward, e.ledger().timestamp()),
+        );
+        reward
+    }
+
     pub fn add_updater(e: Env, caller: Address, updater: Address) {
         require_admin(&e, &caller);
         add_authorized_updater(&e, &updater);
@@ -994,6 +4182,36 @@ impl CommitmentCoreContract {
             .get::<_, Vec<Address>>(&DataKey::AuthorizedUpdaters)
             .unwrap_or(Vec::new(&e))
     }
+
+    /// Allow `asset` to be used for new commitments (admin-only).
+    pub fn add_allowed_asset(e: Env, caller: Address, asset: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::AllowedAsset(asset.clone()), &true);
+        e.events()
+            .publish((Symbol::new(&e, "AssetAllowed"), caller), asset);
+    }
+
+    /// Stop accepting `asset` for new commitments (admin-only).
+    ///
+    /// Existing commitments in the asset are unaffected.
+    pub fn remove_allowed_asset(e: Env, caller: Address, asset: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .remove(&DataKey::AllowedAsset(asset.clone()));
+        e.events()
+            .publish((Symbol::new(&e, "AssetRemoved"), caller), asset);
+    }
+
+    /// Whether `asset` is allow-listed for new commitments.
+    pub fn is_asset_allowed(e: Env, asset: Address) -> bool {
+        e.storage()
+            .instance()
+            .get::<_, bool>(&DataKey::AllowedAsset(asset))
+            .unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
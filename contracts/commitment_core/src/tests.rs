@@ -2,9 +2,10 @@
 
 use super::*;
 use soroban_sdk::{
-    symbol_short,
-    testutils::{Address as _, Events, Ledger},
-    vec, Address, Env, IntoVal, String,
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{storage::Instance as _, Address as _, Events, IssuerFlags, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, BytesN, Env, IntoVal, String, Val,
 };
 
 // Helper function to create a test commitment
@@ -38,6 +39,8 @@ fn create_test_commitment(
         expires_at,
         current_value,
         status: String::from_str(e, "active"),
+        label: String::from_str(e, ""),
+        tags: Vec::new(e),
     }
 }
 
@@ -48,13 +51,120 @@ fn store_commitment(e: &Env, contract_id: &Address, commitment: &Commitment) {
     });
 }
 
+// Helper to deploy core wired to a real NFT contract and a funded test token.
+// Returns (admin, owner, core contract id, nft contract id, asset address).
+fn setup_with_nft(e: &Env) -> (Address, Address, Address, Address, Address) {
+    e.mock_all_auths();
+
+    let admin = Address::generate(e);
+    let owner = Address::generate(e);
+
+    let nft_id = e.register_contract(None, commitment_nft::CommitmentNFTContract);
+    let nft_client = commitment_nft::CommitmentNFTContractClient::new(e, &nft_id);
+    nft_client.initialize(&admin);
+
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(e, &contract_id);
+    client.initialize(&admin, &nft_id);
+    nft_client.set_core_contract(&contract_id);
+
+    let token_admin = Address::generate(e);
+    let asset = e.register_stellar_asset_contract_v2(token_admin).address();
+    StellarAssetClient::new(e, &asset).mint(&owner, &1_000_000);
+    client.add_allowed_asset(&admin, &asset);
+
+    (admin, owner, contract_id, nft_id, asset)
+}
+
+// Minimal NFT stand-in for tests that do not need real NFT state; answers the
+// `total_supply` probe run by `initialize`.
+#[contract]
+pub struct MockNftContract;
+
+#[contractimpl]
+impl MockNftContract {
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint(
+        e: Env,
+        _owner: Address,
+        _commitment_id: String,
+        _duration_days: u32,
+        _max_loss_percent: u32,
+        _commitment_type: String,
+        _initial_amount: i128,
+        _asset_address: Address,
+        _early_exit_penalty: u32,
+    ) -> u32 {
+        let key = symbol_short!("next_id");
+        let id: u32 = e.storage().instance().get(&key).unwrap_or(0);
+        e.storage().instance().set(&key, &(id + 1));
+        id
+    }
+
+    pub fn settle_with_reason(_e: Env, _caller: Address, _token_id: u32, _reason: u32) {}
+
+    pub fn total_supply(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&symbol_short!("next_id"))
+            .unwrap_or(0)
+    }
+}
+
+// Helper: core wired to MockNftContract with a funded commitment "c_0" stored.
+// Returns (admin, owner, core contract id, asset address).
+fn setup_funded_commitment(
+    e: &Env,
+    amount: i128,
+    early_exit_penalty: u32,
+) -> (Address, Address, Address, Address) {
+    e.mock_all_auths();
+
+    let admin = Address::generate(e);
+    let owner = Address::generate(e);
+    let nft_id = e.register_contract(None, MockNftContract);
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    CommitmentCoreContractClient::new(e, &contract_id).initialize(&admin, &nft_id);
+
+    let token_admin = Address::generate(e);
+    let asset = e.register_stellar_asset_contract_v2(token_admin).address();
+    StellarAssetClient::new(e, &asset).mint(&contract_id, &amount);
+
+    let mut commitment = create_test_commitment(e, "c_0", &owner, amount, amount, 10, 30, 0);
+    commitment.asset_address = asset.clone();
+    commitment.rules.early_exit_penalty = early_exit_penalty;
+    store_commitment(e, &contract_id, &commitment);
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &amount);
+        e.storage()
+            .instance()
+            .set(&DataKey::AssetTvl(asset.clone()), &amount);
+    });
+
+    (admin, owner, contract_id, asset)
+}
+
+// Helper to build default commitment rules for create flows
+fn test_rules(e: &Env) -> CommitmentRules {
+    CommitmentRules {
+        duration_days: 30,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(e, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 0,
+        grace_period_days: 0,
+    }
+}
+
 #[test]
 fn test_initialize() {
     let e = Env::default();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     // Test successful initialization
     e.as_contract(&contract_id, || {
@@ -68,7 +178,7 @@ fn test_create_commitment_valid() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let _owner = Address::generate(&e);
     let _asset_address = Address::generate(&e);
 
@@ -165,7 +275,7 @@ fn test_get_owner_commitments() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
 
     e.as_contract(&contract_id, || {
@@ -185,7 +295,7 @@ fn test_get_total_commitments() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
@@ -204,7 +314,7 @@ fn test_get_admin() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
@@ -222,7 +332,7 @@ fn test_get_nft_contract() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
 
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
@@ -601,37 +711,34 @@ fn test_check_violations_zero_amount() {
 #[test]
 fn test_create_commitment_event() {
     let e = Env::default();
-    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    let owner = Address::generate(&e);
-    let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
-
-    client.initialize(&admin, &nft_contract);
-
-    let rules = CommitmentRules {
-        duration_days: 30,
-        max_loss_percent: 10,
-        commitment_type: String::from_str(&e, "safe"),
-        early_exit_penalty: 5,
-        min_fee_threshold: 100,
-        grace_period_days: 0,
-    };
 
-    // Note: This might panic if mock token transfers are not set up, but we are testing events.
-    // However, create_commitment calls transfer_assets.
-    // We need to mock the token contract or use a test token.
-    // For simplicity, we might skip this test if it's too complex to mock everything here,
-    // OR we assume the user has set up mocks (which they haven't in this file).
-    // But wait, create_commitment calls `transfer_assets` which calls `token::Client::transfer`.
-    // If we don't have a real token contract, this will fail.
-    // `origin/master` tests use `create_test_commitment` helper which bypasses `create_commitment` logic.
-    // So `origin/master` tests don't test `create_commitment` fully?
-    // `test_create_commitment_valid` calls `validate_rules` directly.
-    // It seems `origin/master` avoids calling `create_commitment` because of dependencies.
-
-    // I will comment out this test for now to avoid breaking build, or try to mock it.
-    // But I should include the other event tests which are simpler (update_value, settle, etc).
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let commitment = client.get_commitment(&commitment_id);
+
+    let expected_topics: Vec<Val> = (
+        symbol_short!("Created"),
+        commitment_id.clone(),
+        owner.clone(),
+    )
+        .into_val(&e);
+    let (_, _, data) = e
+        .events()
+        .all()
+        .iter()
+        .find(|(id, topics, _)| *id == contract_id && *topics == expected_topics)
+        .unwrap();
+    let data: (i128, CommitmentRules, u32, u64) = data.into_val(&e);
+    assert_eq!(
+        data,
+        (
+            1000,
+            commitment.rules,
+            commitment.nft_token_id,
+            e.ledger().timestamp()
+        )
+    );
 }
 
 #[test]
@@ -640,7 +747,7 @@ fn test_update_value_event() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
     let updater = Address::generate(&e);
     let commitment_id = String::from_str(&e, "test_id");
@@ -679,7 +786,7 @@ fn test_update_value_rate_limit_enforced() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
     let updater = Address::generate(&e);
     let commitment_id = String::from_str(&e, "rl_test");
@@ -717,6 +824,74 @@ fn test_update_value_rate_limit_enforced() {
     client.update_value(&updater, &commitment_id, &200);
 }
 
+#[test]
+fn test_create_commitment_sliding_rate_limit() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let create = symbol_short!("create");
+    client.set_rate_limit(&admin, &create, &60, &1);
+    client.set_limit_mode(&admin, &create, &RateMode::Sliding);
+
+    e.ledger().with_mut(|l| l.timestamp = 1059);
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    // Still inside the trailing window of the first call
+    e.ledger().with_mut(|l| l.timestamp = 1118);
+    assert!(client
+        .try_create_commitment(&owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+
+    e.ledger().with_mut(|l| l.timestamp = 1119);
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+}
+
+#[test]
+fn test_rate_limit_exempt_owner_bypasses_tight_limit() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_rate_limit(&admin, &symbol_short!("create"), &3600, &1);
+
+    assert!(!client.is_rate_limit_exempt(&owner));
+    client.set_rate_limit_exempt(&admin, &owner, &true);
+    assert!(client.is_rate_limit_exempt(&owner));
+
+    for _ in 0..3 {
+        client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    }
+
+    client.set_rate_limit_exempt(&admin, &owner, &false);
+    assert!(!client.is_rate_limit_exempt(&owner));
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert!(client
+        .try_create_commitment(&owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+}
+
+#[test]
+fn test_create_commitment_global_rate_limit_shared_by_owners() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let other_owner = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&other_owner, &1_000_000);
+
+    client.set_global_limit(&admin, &symbol_short!("create"), &3600, &2);
+
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.create_commitment(&other_owner, &1000, &asset, &test_rules(&e));
+    assert!(client
+        .try_create_commitment(&owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+    assert!(client
+        .try_create_commitment(&other_owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+
+    e.ledger().with_mut(|l| l.timestamp += 3600);
+    client.create_commitment(&other_owner, &1000, &asset, &test_rules(&e));
+}
+
 #[test]
 #[should_panic(expected = "Commitment not found")]
 fn test_settle_event() {
@@ -727,7 +902,7 @@ fn test_settle_event() {
     let commitment_id = String::from_str(&e, "test_id");
     // This will panic because commitment doesn't exist
     // The test verifies that the function properly validates preconditions
-    client.settle(&commitment_id);
+    client.settle(&commitment_id, &Address::generate(&e));
 }
 
 #[test]
@@ -790,6 +965,8 @@ fn create_test_commitment_with_penalty(
         expires_at,
         current_value,
         status: String::from_str(e, "active"),
+        label: String::from_str(e, ""),
+        tags: Vec::new(e),
     }
 }
 
@@ -805,7 +982,7 @@ fn test_early_exit_commitment_not_found() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
@@ -831,7 +1008,7 @@ fn test_early_exit_unauthorized_caller() {
     let owner = Address::generate(&e);
     let unauthorized_caller = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_unauthorized";
 
     e.as_contract(&contract_id, || {
@@ -861,7 +1038,7 @@ fn test_early_exit_already_settled() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_settled";
 
     e.as_contract(&contract_id, || {
@@ -894,7 +1071,7 @@ fn test_early_exit_already_violated() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_violated";
 
     e.as_contract(&contract_id, || {
@@ -927,7 +1104,7 @@ fn test_early_exit_already_exited() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_already_exited";
 
     e.as_contract(&contract_id, || {
@@ -963,7 +1140,7 @@ fn test_early_exit_state_update() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = e.register_contract(None, CommitmentCoreContract); // Mock NFT contract
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_state";
 
     e.as_contract(&contract_id, || {
@@ -1055,7 +1232,7 @@ fn test_early_exit_event_emission() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = e.register_contract(None, CommitmentCoreContract); // Mock
+    let nft_contract = e.register_contract(None, MockNftContract);
     let commitment_id = "test_commitment_event";
 
     e.as_contract(&contract_id, || {
@@ -1198,7 +1375,7 @@ fn test_early_exit_status_transition() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
     let admin = Address::generate(&e);
-    let nft_contract = e.register_contract(None, CommitmentCoreContract);
+    let nft_contract = e.register_contract(None, MockNftContract);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
@@ -1223,7 +1400,7 @@ fn test_update_value_unauthorized_caller() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
     let unauthorized = Address::generate(&e);
 
@@ -1247,7 +1424,7 @@ fn test_update_value_no_violation() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
     let updater = Address::generate(&e);
 
@@ -1270,13 +1447,149 @@ fn test_update_value_no_violation() {
     assert_eq!(client.get_total_value_locked(), 950);
 }
 
+fn setup_updatable_commitment(e: &Env) -> (Address, Address, Address) {
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(e);
+    let owner = Address::generate(e);
+    let updater = Address::generate(e);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(
+            e.clone(),
+            admin.clone(),
+            e.register_contract(None, MockNftContract),
+        );
+        add_authorized_updater(e, &updater);
+        let commitment = create_test_commitment(e, "test_id", &owner, 1000, 1000, 10, 30, 1000);
+        set_commitment(e, &commitment);
+    });
+    (admin, updater, contract_id)
+}
+
+#[test]
+#[should_panic(expected = "Value update too soon after the previous one")]
+fn test_update_value_too_soon_rejected() {
+    let e = Env::default();
+    let (admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "test_id");
+    client.set_min_update_interval(&admin, &3600);
+
+    e.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.update_value(&updater, &id, &990);
+    e.ledger().with_mut(|l| l.timestamp = 10_000 + 3599);
+    client.update_value(&updater, &id, &980);
+}
+
+#[test]
+#[should_panic(expected = "Commitment is frozen")]
+fn test_update_value_blocked_while_frozen() {
+    let e = Env::default();
+    let (admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "test_id");
+
+    client.freeze_commitment(&admin, &id);
+    assert!(client.is_commitment_frozen(&id));
+    client.update_value(&updater, &id, &990);
+}
+
+#[test]
+fn test_update_value_resumes_after_unfreeze() {
+    let e = Env::default();
+    let (admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "test_id");
+
+    client.freeze_commitment(&admin, &id);
+    assert!(client.try_update_value(&updater, &id, &990).is_err());
+    client.unfreeze_commitment(&admin, &id);
+    assert!(!client.is_commitment_frozen(&id));
+
+    client.update_value(&updater, &id, &990);
+    assert_eq!(client.get_commitment(&id).current_value, 990);
+}
+
+#[test]
+fn test_freeze_requires_admin_and_existing_commitment() {
+    let e = Env::default();
+    let (_admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert!(client
+        .try_freeze_commitment(&updater, &String::from_str(&e, "test_id"))
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Commitment not found")]
+fn test_freeze_unknown_commitment() {
+    let e = Env::default();
+    let (admin, _updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.freeze_commitment(&admin, &String::from_str(&e, "missing"));
+}
+
+#[test]
+fn test_frozen_commitment_blocks_allocate_but_allows_early_exit() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    client.freeze_commitment(&admin, &id);
+    assert!(client
+        .try_allocate(&id, &Address::generate(&e), &100)
+        .is_err());
+
+    client.early_exit(&id, &owner);
+    assert_eq!(
+        client.get_commitment(&id).status,
+        String::from_str(&e, "early_exit")
+    );
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 900);
+}
+
+#[test]
+fn test_update_value_after_interval_accepted() {
+    let e = Env::default();
+    let (admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "test_id");
+    client.set_min_update_interval(&admin, &3600);
+
+    e.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.update_value(&updater, &id, &990);
+    e.ledger().with_mut(|l| l.timestamp = 10_000 + 3600);
+    client.update_value(&updater, &id, &980);
+
+    assert_eq!(client.get_commitment(&id).current_value, 980);
+    assert_eq!(client.get_last_updated_at(&id), Some(13_600));
+}
+
+#[test]
+fn test_update_value_without_interval_skips_timestamp() {
+    let e = Env::default();
+    let (_admin, updater, contract_id) = setup_updatable_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "test_id");
+
+    e.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.update_value(&updater, &id, &990);
+    client.update_value(&updater, &id, &980);
+
+    assert_eq!(client.get_commitment(&id).current_value, 980);
+    assert_eq!(client.get_last_updated_at(&id), None);
+}
+
 #[test]
 fn test_update_value_triggers_violation() {
     let e = Env::default();
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let owner = Address::generate(&e);
     let updater = Address::generate(&e);
 
@@ -1304,7 +1617,7 @@ fn test_add_and_get_authorized_updaters() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let updater1 = Address::generate(&e);
     let updater2 = Address::generate(&e);
 
@@ -1328,7 +1641,7 @@ fn test_remove_authorized_updater() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let updater = Address::generate(&e);
 
     e.as_contract(&contract_id, || {
@@ -1350,7 +1663,7 @@ fn test_add_updater_non_admin_fails() {
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
-    let nft_contract = Address::generate(&e);
+    let nft_contract = e.register_contract(None, MockNftContract);
     let non_admin = Address::generate(&e);
     let updater = Address::generate(&e);
 
@@ -1361,3 +1674,2815 @@ fn test_add_updater_non_admin_fails() {
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
     client.add_updater(&non_admin, &updater);
 }
+
+// ============================================================================
+// NFT Reverse Lookup Tests
+// ============================================================================
+
+#[test]
+fn test_get_commitment_by_nft_after_create() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let commitment = client.get_commitment(&commitment_id);
+
+    let nft_client = commitment_nft::CommitmentNFTContractClient::new(&e, &nft_id);
+    assert_eq!(nft_client.owner_of(&commitment.nft_token_id), owner);
+
+    let resolved = client.get_commitment_by_nft(&commitment.nft_token_id);
+    assert_eq!(resolved, commitment);
+    assert_eq!(resolved.commitment_id, commitment_id);
+}
+
+#[test]
+fn test_nft_public_metadata_shows_early_exit_payout() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let nft_client = commitment_nft::CommitmentNFTContractClient::new(&e, &nft_id);
+
+    // 5% penalty on 1000 returns 950
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let token_id = client.get_commitment(&commitment_id).nft_token_id;
+    assert_eq!(nft_client.get_public_metadata(&token_id).final_value, None);
+
+    client.early_exit(&commitment_id, &owner);
+    let public = nft_client.get_public_metadata(&token_id);
+    assert_eq!(public.final_value, Some(950));
+    assert_eq!(
+        public.settlement_reason,
+        commitment_nft::SettlementReason::EarlyExit
+    );
+}
+
+#[test]
+fn test_reconcile_matches_balance() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.reconcile(&asset), (0, 0));
+
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.create_commitment(&owner, &2500, &asset, &test_rules(&e));
+    assert_eq!(client.reconcile(&asset), (3500, 3500));
+
+    // Commitments in another asset are not counted
+    let other_asset = e
+        .register_stellar_asset_contract_v2(Address::generate(&e))
+        .address();
+    StellarAssetClient::new(&e, &other_asset).mint(&owner, &1_000);
+    client.add_allowed_asset(&admin, &other_asset);
+    client.create_commitment(&owner, &700, &other_asset, &test_rules(&e));
+    assert_eq!(client.reconcile(&asset), (3500, 3500));
+    assert_eq!(client.reconcile(&other_asset), (700, 700));
+}
+
+#[test]
+fn test_get_held_balance_tracks_committed_amount() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get_held_balance(&asset), 0);
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_held_balance(&asset), 1000);
+    assert_eq!(
+        client.get_held_balance(&asset),
+        TokenClient::new(&e, &asset).balance(&contract_id)
+    );
+}
+
+#[test]
+fn test_recompute_tvl_repairs_corrupted_total() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let closed_id = client.create_commitment(&owner, &2500, &asset, &test_rules(&e));
+    client.create_commitment(&owner, &700, &asset, &test_rules(&e));
+
+    // Closed commitments do not count towards TVL
+    let mut closed = client.get_commitment(&closed_id);
+    closed.status = String::from_str(&e, "violated");
+    store_commitment(&e, &contract_id, &closed);
+
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &123_456i128);
+        e.storage()
+            .instance()
+            .set(&DataKey::AssetTvl(asset.clone()), &99i128);
+    });
+
+    assert_eq!(client.recompute_tvl(&admin), 1700);
+    assert_eq!(client.get_total_value_locked(), 1700);
+    assert_eq!(client.get_asset_tvl(&asset), 1700);
+
+    let events = e.events().all();
+    let last = events.last().unwrap();
+    let data: (i128, i128) = last.2.into_val(&e);
+    assert_eq!(data, (123_456, 1700));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_recompute_tvl_unauthorized() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.recompute_tvl(&owner);
+}
+
+#[test]
+fn test_bump_commitment_ttl() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let ttl_after_create = e.as_contract(&contract_id, || e.storage().instance().get_ttl());
+    assert!(ttl_after_create >= TTL_THRESHOLD);
+
+    // Well into the threshold window, an explicit bump restores the full TTL
+    e.ledger().with_mut(|li| {
+        li.sequence_number += TTL_EXTEND_TO - TTL_THRESHOLD + 1;
+    });
+    client.bump_commitment_ttl(&commitment_id);
+    let ttl = e.as_contract(&contract_id, || e.storage().instance().get_ttl());
+    assert_eq!(ttl, TTL_EXTEND_TO);
+}
+
+#[test]
+#[should_panic(expected = "Commitment not found")]
+fn test_bump_commitment_ttl_unknown_commitment() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.bump_commitment_ttl(&String::from_str(&e, "missing"));
+}
+
+#[test]
+#[should_panic(expected = "Commitment not found")]
+fn test_get_commitment_by_nft_unknown_token() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.get_commitment_by_nft(&42);
+}
+
+// ============================================================================
+// Allowance-based Creation Tests
+// ============================================================================
+
+#[test]
+fn test_create_commitment_from_allowance_pulls_funds() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+
+    token.approve(&owner, &contract_id, &5000, &(e.ledger().sequence() + 100));
+
+    let commitment_id =
+        client.create_commitment_from_allowance(&owner, &2000, &asset, &test_rules(&e));
+
+    assert_eq!(token.balance(&owner), 1_000_000 - 2000);
+    assert_eq!(token.balance(&contract_id), 2000);
+    assert_eq!(token.allowance(&owner, &contract_id), 3000);
+
+    let commitment = client.get_commitment(&commitment_id);
+    assert_eq!(commitment.amount, 2000);
+    assert_eq!(client.get_total_value_locked(), 2000);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient allowance for contract")]
+fn test_create_commitment_from_allowance_insufficient_allowance() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+
+    token.approve(&owner, &contract_id, &500, &(e.ledger().sequence() + 100));
+
+    client.create_commitment_from_allowance(&owner, &2000, &asset, &test_rules(&e));
+}
+
+// ============================================================================
+// Max Duration Tests
+// ============================================================================
+
+#[test]
+fn test_get_max_duration_days_default() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get_max_duration_days(), DEFAULT_MAX_DURATION_DAYS);
+}
+
+#[test]
+fn test_create_commitment_at_max_duration() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = DEFAULT_MAX_DURATION_DAYS;
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &rules);
+
+    let commitment = client.get_commitment(&commitment_id);
+    assert_eq!(commitment.rules.duration_days, DEFAULT_MAX_DURATION_DAYS);
+}
+
+#[test]
+#[should_panic(expected = "Invalid duration")]
+fn test_create_commitment_above_max_duration() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = DEFAULT_MAX_DURATION_DAYS + 1;
+    client.create_commitment(&owner, &1000, &asset, &rules);
+}
+
+#[test]
+fn test_set_max_duration_days_boundary() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_max_duration_days(&admin, &90);
+    assert_eq!(client.get_max_duration_days(), 90);
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = 90;
+    client.create_commitment(&owner, &1000, &asset, &rules);
+
+    rules.duration_days = 91;
+    let result = client.try_create_commitment(&owner, &1000, &asset, &rules);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_max_duration_days_non_admin_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_max_duration_days(&owner, &90);
+}
+
+#[test]
+#[should_panic(expected = "Invalid duration")]
+fn test_set_max_duration_days_zero_fails() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_max_duration_days(&admin, &0);
+}
+
+// ============================================================================
+// Admin Transfer Tests
+// ============================================================================
+
+// Helper to deploy a timelock owned by `admin` and attach it to core
+fn setup_timelock(e: &Env, admin: &Address, contract_id: &Address) -> Address {
+    let timelock_id = e.register_contract(None, time_lock::TimelockContract);
+    time_lock::TimelockContractClient::new(e, &timelock_id).initialize(admin);
+    CommitmentCoreContractClient::new(e, contract_id).set_timelock(admin, &timelock_id);
+    timelock_id
+}
+
+#[test]
+fn test_set_admin_success() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let new_admin = Address::generate(&e);
+
+    client.set_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+
+    // New admin can use admin functions, old admin cannot
+    client.set_max_duration_days(&new_admin, &100);
+    assert!(client.try_set_max_duration_days(&admin, &200).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_admin_unauthorized() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_admin(&owner, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Admin change not approved by an executed timelock action")]
+fn test_set_admin_with_timelock_requires_executed_action() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let new_admin = Address::generate(&e);
+
+    // Queued but not yet executed
+    timelock.queue_action(
+        &time_lock::ActionType::AdminChange,
+        &new_admin,
+        &Symbol::new(&e, "set_admin"),
+        &vec![&e, new_admin.into_val(&e)],
+        &String::from_str(&e, "core admin"),
+        &172800,
+    );
+
+    client.set_admin(&admin, &new_admin);
+}
+
+#[test]
+fn test_validate_commitment_accepts_valid_params() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.validate_commitment(&1000, &asset, &test_rules(&e));
+    // Nothing was created
+    assert_eq!(client.get_total_commitments(), 0);
+}
+
+#[test]
+fn test_validate_commitment_rejection_reasons() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(
+        client.try_validate_commitment(&0, &asset, &test_rules(&e)),
+        Err(Ok(CommitmentError::InvalidAmount))
+    );
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = 0;
+    assert_eq!(
+        client.try_validate_commitment(&1000, &asset, &rules),
+        Err(Ok(CommitmentError::InvalidDuration))
+    );
+
+    rules.duration_days = DEFAULT_MAX_DURATION_DAYS + 1;
+    assert_eq!(
+        client.try_validate_commitment(&1000, &asset, &rules),
+        Err(Ok(CommitmentError::InvalidDuration))
+    );
+
+    let mut rules = test_rules(&e);
+    rules.max_loss_percent = 101;
+    assert_eq!(
+        client.try_validate_commitment(&1000, &asset, &rules),
+        Err(Ok(CommitmentError::InvalidMaxLossPercent))
+    );
+
+    let mut rules = test_rules(&e);
+    rules.early_exit_penalty = 150;
+    assert_eq!(
+        client.try_validate_commitment(&1000, &asset, &rules),
+        Err(Ok(CommitmentError::InvalidEarlyExitPenalty))
+    );
+
+    let mut rules = test_rules(&e);
+    rules.commitment_type = String::from_str(&e, "reckless");
+    assert_eq!(
+        client.try_validate_commitment(&1000, &asset, &rules),
+        Err(Ok(CommitmentError::InvalidCommitmentType))
+    );
+
+    assert_eq!(
+        client.try_validate_commitment(&1000, &Address::generate(&e), &test_rules(&e)),
+        Err(Ok(CommitmentError::AssetNotAllowed))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid percent")]
+fn test_create_commitment_rejects_penalty_above_100() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut rules = test_rules(&e);
+    rules.early_exit_penalty = 150;
+    client.create_commitment(&owner, &1000, &asset, &rules);
+}
+
+#[test]
+fn test_create_commitment_with_allowed_asset() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert!(client.is_asset_allowed(&asset));
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_commitment(&commitment_id).asset_address, asset);
+}
+
+#[test]
+#[should_panic(expected = "Asset is not on the allow-list")]
+fn test_create_commitment_with_disallowed_asset() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let junk = e
+        .register_stellar_asset_contract_v2(Address::generate(&e))
+        .address();
+    StellarAssetClient::new(&e, &junk).mint(&owner, &1_000);
+    assert!(!client.is_asset_allowed(&junk));
+
+    client.create_commitment(&owner, &1000, &junk, &test_rules(&e));
+}
+
+#[test]
+fn test_remove_allowed_asset() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    client.remove_allowed_asset(&admin, &asset);
+    assert!(!client.is_asset_allowed(&asset));
+    assert!(client
+        .try_create_commitment(&owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+    // Existing commitments are untouched
+    assert_eq!(
+        client.get_commitment(&commitment_id).status,
+        String::from_str(&e, "active")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_add_allowed_asset_unauthorized() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.add_allowed_asset(&owner, &asset);
+}
+
+#[test]
+fn test_validate_commitment_not_initialized() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(
+        client.try_validate_commitment(&1000, &Address::generate(&e), &test_rules(&e)),
+        Err(Ok(CommitmentError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_owner_commitment_count_tracks_active() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert_eq!(client.get_owner_commitment_count(&owner), 0);
+
+    let first = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_owner_commitment_count(&owner), 2);
+
+    e.ledger().with_mut(|l| l.timestamp += 31 * 86400);
+    client.settle(&first, &owner);
+
+    // Active count drops; the history list keeps both
+    assert_eq!(client.get_owner_commitment_count(&owner), 1);
+    assert_eq!(client.get_owner_commitments(&owner).len(), 2);
+}
+
+#[test]
+fn test_owner_total_value_tracks_active_commitments() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let updater = Address::generate(&e);
+    client.add_updater(&admin, &updater);
+    assert_eq!(client.get_owner_total_value(&owner), 0);
+
+    let first = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let second = client.create_commitment(&owner, &2500, &asset, &test_rules(&e));
+    assert_eq!(client.get_owner_total_value(&owner), 3500);
+
+    // Revaluations move the aggregate
+    client.update_value(&updater, &second, &2400);
+    assert_eq!(client.get_owner_total_value(&owner), 3400);
+
+    e.ledger().with_mut(|l| l.timestamp += 31 * 86400);
+    client.settle(&first, &owner);
+    assert_eq!(client.get_owner_total_value(&owner), 2400);
+
+    // Other owners are unaffected
+    assert_eq!(client.get_owner_total_value(&Address::generate(&e)), 0);
+}
+
+#[test]
+fn test_asset_tvl_tracked_per_asset() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let updater = Address::generate(&e);
+    client.add_updater(&admin, &updater);
+
+    let other_asset = e
+        .register_stellar_asset_contract_v2(Address::generate(&e))
+        .address();
+    StellarAssetClient::new(&e, &other_asset).mint(&owner, &1_000_000);
+    client.add_allowed_asset(&admin, &other_asset);
+
+    let first = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let second = client.create_commitment(&owner, &4000, &other_asset, &test_rules(&e));
+    let third = client.create_commitment(&owner, &500, &asset, &test_rules(&e));
+    assert_eq!(client.get_asset_tvl(&asset), 1500);
+    assert_eq!(client.get_asset_tvl(&other_asset), 4000);
+
+    client.update_value(&updater, &second, &3800);
+    assert_eq!(client.get_asset_tvl(&asset), 1500);
+    assert_eq!(client.get_asset_tvl(&other_asset), 3800);
+
+    e.ledger().with_mut(|l| l.timestamp += 31 * 86400);
+    client.settle(&first, &owner);
+    assert_eq!(client.get_asset_tvl(&asset), 500);
+    assert_eq!(client.get_asset_tvl(&other_asset), 3800);
+    assert_eq!(
+        client.get_asset_tvl(&asset) + client.get_asset_tvl(&other_asset),
+        client.get_total_value_locked()
+    );
+
+    // The index keeps settled commitments and pages in creation order
+    assert_eq!(
+        client.get_commitments_by_asset(&asset, &0, &10),
+        vec![&e, first.clone(), third.clone()]
+    );
+    assert_eq!(
+        client.get_commitments_by_asset(&asset, &1, &1),
+        vec![&e, third]
+    );
+    assert_eq!(
+        client.get_commitments_by_asset(&other_asset, &0, &10),
+        vec![&e, second]
+    );
+    assert_eq!(client.get_commitments_by_asset(&asset, &2, &10).len(), 0);
+}
+
+#[test]
+fn test_violating_update_value_clears_asset_tvl() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let updater = Address::generate(&e);
+    client.add_updater(&admin, &updater);
+
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_asset_tvl(&asset), 1000);
+
+    // 20% loss breaks the 10% limit
+    client.update_value(&updater, &commitment_id, &800);
+    assert_eq!(
+        client.get_commitment(&commitment_id).status,
+        String::from_str(&e, "violated")
+    );
+    assert_eq!(client.get_asset_tvl(&asset), 0);
+    assert_eq!(client.get_total_value_locked(), 0);
+    assert_eq!(client.get_owner_total_value(&owner), 0);
+}
+
+#[test]
+fn test_asset_tvl_drops_on_early_exit() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert_eq!(client.get_asset_tvl(&asset), 1000);
+
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+    assert_eq!(client.get_asset_tvl(&asset), 0);
+}
+
+#[test]
+fn test_create_commitment_idempotent_retry_returns_existing() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let key = BytesN::from_array(&e, &[7; 32]);
+
+    let first = client.create_commitment_idempotent(&owner, &1000, &asset, &test_rules(&e), &key);
+    let retry = client.create_commitment_idempotent(&owner, &1000, &asset, &test_rules(&e), &key);
+    assert_eq!(first, retry);
+    assert_eq!(client.get_total_commitments(), 1);
+    assert_eq!(client.get_owner_commitments(&owner).len(), 1);
+    assert_eq!(client.get_total_value_locked(), 1000);
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 999_000);
+    assert_eq!(
+        client.get_idempotent_commitment(&owner, &key),
+        Some(first.clone())
+    );
+
+    // A fresh key opens a new commitment
+    let other = client.create_commitment_idempotent(
+        &owner,
+        &1000,
+        &asset,
+        &test_rules(&e),
+        &BytesN::from_array(&e, &[8; 32]),
+    );
+    assert_ne!(other, first);
+    assert_eq!(client.get_total_commitments(), 2);
+}
+
+#[test]
+fn test_get_maturing_before_returns_only_due() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = 10;
+    let ten_days = client.create_commitment(&owner, &1000, &asset, &rules);
+    rules.duration_days = 30;
+    let thirty_days = client.create_commitment(&owner, &1000, &asset, &rules);
+    rules.duration_days = 20;
+    let twenty_days = client.create_commitment(&owner, &1000, &asset, &rules);
+
+    assert_eq!(client.get_maturing_before(&(5 * 86400), &0, &10).len(), 0);
+    assert_eq!(
+        client.get_maturing_before(&(20 * 86400), &0, &10),
+        vec![&e, ten_days.clone(), twenty_days.clone()]
+    );
+
+    // Settled commitments drop out
+    e.ledger().with_mut(|l| l.timestamp = 10 * 86400);
+    client.settle(&ten_days, &owner);
+    assert_eq!(
+        client.get_maturing_before(&(30 * 86400), &0, &10),
+        vec![&e, thirty_days.clone(), twenty_days.clone()]
+    );
+
+    // The scan window is bounded by start/limit
+    assert_eq!(
+        client.get_maturing_before(&(30 * 86400), &0, &2),
+        vec![&e, thirty_days]
+    );
+    assert_eq!(
+        client.get_maturing_before(&(30 * 86400), &2, &2),
+        vec![&e, twenty_days]
+    );
+}
+
+#[test]
+fn test_set_and_read_label() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_commitment_label(&id), None);
+
+    client.set_label(&id, &owner, &String::from_str(&e, "house fund"));
+    client.set_tags(&id, &owner, &vec![&e, String::from_str(&e, "long-term")]);
+
+    let label = client.get_commitment_label(&id).unwrap();
+    assert_eq!(label.label, String::from_str(&e, "house fund"));
+    assert_eq!(label.tags, vec![&e, String::from_str(&e, "long-term")]);
+}
+
+#[test]
+fn test_create_commitment_with_label() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment_with_label(
+        &owner,
+        &1000,
+        &asset,
+        &test_rules(&e),
+        &String::from_str(&e, "retirement"),
+        &Vec::new(&e),
+    );
+
+    let label = client.get_commitment_label(&id).unwrap();
+    assert_eq!(label.label, String::from_str(&e, "retirement"));
+    assert!(label.tags.is_empty());
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.amount, 1000);
+    assert_eq!(commitment.label, String::from_str(&e, "retirement"));
+}
+
+// Commitment layout stored before label and tags
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyCommitment {
+    pub commitment_id: String,
+    pub owner: Address,
+    pub nft_token_id: u32,
+    pub rules: CommitmentRules,
+    pub amount: i128,
+    pub asset_address: Address,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub current_value: i128,
+    pub status: String,
+}
+
+#[test]
+fn test_legacy_commitment_layout_still_decodes() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    let c = client.get_commitment(&id);
+    e.as_contract(&contract_id, || {
+        e.storage().instance().set(
+            &DataKey::Commitment(id.clone()),
+            &LegacyCommitment {
+                commitment_id: c.commitment_id.clone(),
+                owner: c.owner.clone(),
+                nft_token_id: c.nft_token_id,
+                rules: c.rules.clone(),
+                amount: c.amount,
+                asset_address: c.asset_address.clone(),
+                created_at: c.created_at,
+                expires_at: c.expires_at,
+                current_value: c.current_value,
+                status: c.status.clone(),
+            },
+        );
+    });
+
+    assert_eq!(client.get_commitment(&id), c);
+    assert_eq!(client.get_commitment_label(&id), None);
+
+    // Setting a label rewrites the entry in the current layout
+    client.set_label(&id, &owner, &String::from_str(&e, "house fund"));
+    assert_eq!(
+        client.get_commitment(&id).label,
+        String::from_str(&e, "house fund")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Label or tags exceed size limits")]
+fn test_set_label_too_long_rejected() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let long = String::from_str(
+        &e,
+        "this label is far too long to be stored against a single commitment entry",
+    );
+    client.set_label(&id, &owner, &long);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_label_non_owner_rejected() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.set_label(&id, &Address::generate(&e), &String::from_str(&e, "mine"));
+}
+
+#[test]
+fn test_set_and_clear_payout_hook() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let hook = Address::generate(&e);
+
+    client.set_payout_hook(&id, &owner, &Some(hook.clone()));
+    assert_eq!(client.get_payout_hook(&id), Some(hook));
+
+    client.set_payout_hook(&id, &owner, &None);
+    assert_eq!(client.get_payout_hook(&id), None);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_payout_hook_non_owner_rejected() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.set_payout_hook(&id, &Address::generate(&e), &Some(Address::generate(&e)));
+}
+
+#[test]
+fn test_set_and_clear_payout_asset() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let stable = Address::generate(&e);
+    let router = Address::generate(&e);
+
+    client.set_swap_router(&admin, &router);
+    assert_eq!(client.get_swap_router(), Some(router));
+    client.set_payout_asset(&id, &owner, &Some(stable.clone()), &950);
+    assert_eq!(
+        client.get_payout_asset(&id),
+        Some(PayoutAssetConfig {
+            asset: stable,
+            min_out: 950
+        })
+    );
+
+    client.set_payout_asset(&id, &owner, &None, &0);
+    assert_eq!(client.get_payout_asset(&id), None);
+}
+
+#[test]
+#[should_panic(expected = "Swap router not configured")]
+fn test_set_payout_asset_requires_router() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.set_payout_asset(&id, &owner, &Some(Address::generate(&e)), &950);
+}
+
+#[test]
+fn test_settle_with_own_asset_as_payout_asset_pays_natively() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.set_swap_router(&admin, &Address::generate(&e));
+    client.set_payout_asset(&id, &owner, &Some(asset.clone()), &1000);
+
+    let before = token.balance(&owner);
+    e.ledger()
+        .with_mut(|l| l.timestamp = client.get_commitment(&id).expires_at);
+    client.settle(&id, &owner);
+
+    assert_eq!(token.balance(&owner), before + 1000);
+}
+
+#[test]
+fn test_lifecycle_phase_through_grace_period() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let mut rules = test_rules(&e);
+    rules.grace_period_days = 3;
+    let id = client.create_commitment(&owner, &1000, &asset, &rules);
+    let expires_at = client.get_commitment(&id).expires_at;
+
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::Active);
+
+    e.ledger().with_mut(|l| l.timestamp = expires_at);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::InGrace);
+    e.ledger()
+        .with_mut(|l| l.timestamp = expires_at + 3 * 86400 - 1);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::InGrace);
+
+    e.ledger()
+        .with_mut(|l| l.timestamp = expires_at + 3 * 86400);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::PastGrace);
+
+    client.settle(&id, &owner);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::Settled);
+}
+
+#[test]
+fn test_lifecycle_phase_without_grace_and_closed_statuses() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let expires_at = client.get_commitment(&id).expires_at;
+
+    // No grace period: expired commitments are past grace right away
+    e.ledger().with_mut(|l| l.timestamp = expires_at);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::PastGrace);
+
+    let mut commitment = client.get_commitment(&id);
+    commitment.status = String::from_str(&e, "violated");
+    store_commitment(&e, &contract_id, &commitment);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::Violated);
+
+    commitment.status = String::from_str(&e, "early_exit");
+    store_commitment(&e, &contract_id, &commitment);
+    assert_eq!(client.get_lifecycle_phase(&id), LifecyclePhase::EarlyExited);
+}
+
+#[test]
+fn test_calendar_mode_shifts_saturday_expiry_to_monday() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let weekend = (1 << shared_utils::SATURDAY) | (1 << shared_utils::SUNDAY);
+
+    // Without calendar mode a 30-day commitment from the epoch ends on a Saturday
+    e.ledger().with_mut(|l| l.timestamp = 0);
+    let plain_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let plain = client.get_commitment(&plain_id);
+    assert_eq!(
+        shared_utils::TimeUtils::weekday(plain.expires_at),
+        shared_utils::SATURDAY
+    );
+
+    client.set_settlement_calendar(&admin, &weekend);
+    assert_eq!(client.get_settlement_calendar(), weekend);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.expires_at, plain.expires_at + 2 * 86400);
+    assert_eq!(
+        shared_utils::TimeUtils::weekday(commitment.expires_at),
+        shared_utils::MONDAY
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid calendar: at least one weekday must allow settlement")]
+fn test_calendar_mode_rejects_all_days() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_settlement_calendar(&admin, &shared_utils::ALL_WEEKDAYS_MASK);
+}
+
+#[test]
+fn test_propose_accept_admin() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let candidate = Address::generate(&e);
+
+    client.propose_admin(&admin, &candidate);
+    assert_eq!(client.get_pending_admin(), Some(candidate.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin(&candidate);
+    assert_eq!(client.get_admin(), candidate);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_accept_admin_wrong_address_rejected() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.propose_admin(&admin, &Address::generate(&e));
+    client.accept_admin(&Address::generate(&e));
+}
+
+#[test]
+#[should_panic(expected = "No pending admin proposal")]
+fn test_cancel_admin_proposal() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let candidate = Address::generate(&e);
+
+    client.propose_admin(&admin, &candidate);
+    client.cancel_admin_proposal(&admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    client.accept_admin(&candidate);
+}
+
+#[test]
+fn test_set_admin_with_timelock_executed_action() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let new_admin = Address::generate(&e);
+
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::AdminChange,
+        &new_admin,
+        &Symbol::new(&e, "set_admin"),
+        &vec![&e, new_admin.into_val(&e)],
+        &String::from_str(&e, "core admin"),
+        &172800,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 172800);
+    timelock.execute_action(&action_id);
+
+    client.set_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_timelock(), Some(timelock_id));
+
+    // The consumed action cannot authorize the same change again
+    assert!(client.try_set_admin(&new_admin, &new_admin).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Admin change not approved by an executed timelock action")]
+fn test_set_admin_ignores_approval_outside_scan_window() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let new_admin = Address::generate(&e);
+
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::AdminChange,
+        &new_admin,
+        &Symbol::new(&e, "set_admin"),
+        &vec![&e, new_admin.into_val(&e)],
+        &String::from_str(&e, "core admin"),
+        &172800,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 172800);
+    timelock.execute_action(&action_id);
+
+    // Bury the approval under a full window of newer actions
+    for _ in 0..MAX_TIMELOCK_SCAN {
+        timelock.queue_action(
+            &time_lock::ActionType::ParameterChange,
+            &contract_id,
+            &Symbol::new(&e, "set_max_duration"),
+            &vec![&e],
+            &String::from_str(&e, "filler"),
+            &172800,
+        );
+    }
+
+    client.set_admin(&admin, &new_admin);
+}
+
+fn set_guard(e: &Env, contract_id: &Address) {
+    e.as_contract(contract_id, || {
+        e.storage().instance().set(&DataKey::ReentrancyGuard, &true);
+    });
+}
+
+#[test]
+fn test_reset_reentrancy_guard() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    // A guard left set blocks guarded entry points
+    set_guard(&e, &contract_id);
+    assert!(client
+        .try_create_commitment(&owner, &1000, &asset, &test_rules(&e))
+        .is_err());
+
+    client.reset_reentrancy_guard(&admin);
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    assert_eq!(client.get_total_commitments(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_reset_reentrancy_guard_unauthorized() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.reset_reentrancy_guard(&owner);
+}
+
+#[test]
+#[should_panic(expected = "Guard reset not approved by an executed timelock action")]
+fn test_reset_reentrancy_guard_with_timelock_requires_executed_action() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    setup_timelock(&e, &admin, &contract_id);
+
+    client.reset_reentrancy_guard(&admin);
+}
+
+#[test]
+fn test_reset_reentrancy_guard_with_timelock() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    set_guard(&e, &contract_id);
+
+    // No executed action yet
+    assert!(client.try_reset_reentrancy_guard(&admin).is_err());
+
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::ParameterChange,
+        &contract_id,
+        &Symbol::new(&e, "reset_reentrancy_guard"),
+        &vec![&e, admin.into_val(&e)],
+        &String::from_str(&e, "clear stuck guard"),
+        &86400,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    client.reset_reentrancy_guard(&admin);
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    // The consumed action cannot authorize another reset
+    assert!(client.try_reset_reentrancy_guard(&admin).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Guard reset not approved by an executed timelock action")]
+fn test_reset_reentrancy_guard_rejects_action_for_other_function() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+
+    // Approved for a different parameter change on the same contract
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::ParameterChange,
+        &contract_id,
+        &Symbol::new(&e, "set_fee_recipient"),
+        &vec![&e, admin.into_val(&e)],
+        &String::from_str(&e, "fee recipient"),
+        &86400,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    client.reset_reentrancy_guard(&admin);
+}
+
+#[test]
+fn test_early_exit_charges_penalty_without_waiver() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    assert!(!client.is_penalty_waived(&id));
+    client.early_exit(&id, &owner);
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 900);
+}
+
+#[test]
+#[should_panic(expected = "Minimum hold period has not elapsed")]
+fn test_early_exit_before_min_hold_rejected() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_min_hold_seconds(&admin, &3600);
+
+    e.ledger().with_mut(|l| l.timestamp = 3599);
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+}
+
+#[test]
+fn test_early_exit_after_min_hold_allowed() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_min_hold_seconds(&admin, &3600);
+    assert_eq!(client.get_min_hold_seconds(), 3600);
+
+    e.ledger().with_mut(|l| l.timestamp = 3600);
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 900);
+}
+
+#[test]
+#[should_panic(expected = "Arithmetic overflow")]
+fn test_early_exit_penalty_overflow_is_rejected() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    // A corrupted value whose penalty cannot be computed must not exit penalty-free
+    let mut commitment = client.get_commitment(&id);
+    commitment.current_value = i128::MAX / 100;
+    store_commitment(&e, &contract_id, &commitment);
+
+    client.early_exit(&id, &owner);
+}
+
+#[test]
+fn test_waived_penalty_returns_full_value_on_early_exit() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    client.waive_penalty(&admin, &id);
+    assert!(client.is_penalty_waived(&id));
+    client.early_exit(&id, &owner);
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_waive_penalty_admin_only() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.waive_penalty(&owner, &String::from_str(&e, "c_0"));
+}
+
+#[test]
+fn test_waive_penalty_with_timelock() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let id = String::from_str(&e, "c_0");
+
+    // Not approved yet
+    assert!(client.try_waive_penalty(&admin, &id).is_err());
+    assert!(!client.is_penalty_waived(&id));
+
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::ParameterChange,
+        &contract_id,
+        &Symbol::new(&e, "waive_penalty"),
+        &vec![&e, admin.into_val(&e), id.into_val(&e)],
+        &String::from_str(&e, "oracle outage"),
+        &86400,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    client.waive_penalty(&admin, &id);
+    assert!(client.is_penalty_waived(&id));
+}
+
+#[test]
+#[should_panic(expected = "Penalty waiver not approved by an executed timelock action")]
+fn test_waive_penalty_rejects_action_for_other_commitment() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let timelock_id = setup_timelock(&e, &admin, &contract_id);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let other = String::from_str(&e, "c_9");
+
+    let action_id = timelock.queue_action(
+        &time_lock::ActionType::ParameterChange,
+        &contract_id,
+        &Symbol::new(&e, "waive_penalty"),
+        &vec![&e, admin.into_val(&e), other.into_val(&e)],
+        &String::from_str(&e, "oracle outage"),
+        &86400,
+    );
+    e.ledger().with_mut(|l| l.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    client.waive_penalty(&admin, &String::from_str(&e, "c_0"));
+}
+
+#[test]
+#[should_panic(expected = "Commitment is not active")]
+fn test_waive_penalty_rejects_violated_commitment() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    let mut commitment = client.get_commitment(&id);
+    commitment.current_value = 800;
+    store_commitment(&e, &contract_id, &commitment);
+    client.mark_violated(&admin, &id);
+
+    client.waive_penalty(&admin, &id);
+}
+
+#[test]
+fn test_get_contract_info() {
+    let e = Env::default();
+    let (admin, owner, contract_id, nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let info = client.get_contract_info();
+    assert_eq!(
+        info,
+        ContractInfo {
+            admin: admin.clone(),
+            nft_contract: nft_id.clone(),
+            version: CURRENT_VERSION,
+            total_commitments: 0,
+            tvl: 0,
+            paused: false,
+        }
+    );
+
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    client.create_commitment(&owner, &2500, &asset, &test_rules(&e));
+    e.as_contract(&contract_id, || Pausable::pause(&e));
+
+    let info = client.get_contract_info();
+    assert_eq!(info.admin, admin);
+    assert_eq!(info.nft_contract, nft_id);
+    assert_eq!(info.version, CURRENT_VERSION);
+    assert_eq!(info.total_commitments, 2);
+    assert_eq!(info.tvl, 3500);
+    assert!(info.paused);
+}
+
+#[test]
+fn test_renounce_admin() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.renounce_admin(&admin);
+
+    assert_ne!(client.get_admin(), admin);
+    assert!(client.try_set_max_duration_days(&admin, &100).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_renounce_admin_unauthorized() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.renounce_admin(&owner);
+}
+
+// ============================================================================
+// Version & Upgrade Tests
+// ============================================================================
+
+#[test]
+fn test_get_version_after_initialize() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Invalid WASM hash")]
+fn test_upgrade_rejects_zero_wasm_hash() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let zero = BytesN::from_array(&e, &[0; 32]);
+    client.upgrade(&admin, &zero);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_upgrade_non_admin_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let hash = BytesN::from_array(&e, &[1; 32]);
+    client.upgrade(&owner, &hash);
+}
+
+#[test]
+fn test_migrate_from_legacy_version() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    // Simulate legacy storage layout (version 0)
+    e.as_contract(&contract_id, || {
+        e.storage().instance().remove(&DataKey::Version);
+        e.storage().instance().remove(&DataKey::TotalValueLocked);
+    });
+    assert_eq!(client.get_version(), 0);
+
+    // Wrong source version is rejected
+    assert!(client.try_migrate(&admin, &1).is_err());
+
+    client.migrate(&admin, &0);
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+    assert_eq!(client.get_total_value_locked(), 0);
+
+    // Replay is rejected
+    assert!(client.try_migrate(&admin, &0).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Contract already migrated to current version")]
+fn test_migrate_already_current() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.migrate(&admin, &CURRENT_VERSION);
+}
+
+// ============================================================================
+// Mark Violated Tests
+// ============================================================================
+
+#[test]
+fn test_mark_violated_persists_status() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let updater = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+    client.add_updater(&admin, &updater);
+
+    // 20% loss against a 10% limit
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 800, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &800i128);
+    });
+
+    let id = String::from_str(&e, "c_0");
+    assert!(client.check_violations(&id));
+    // check_violations stays read-only
+    assert_eq!(
+        client.get_commitment(&id).status,
+        String::from_str(&e, "active")
+    );
+
+    client.mark_violated(&updater, &id);
+
+    let stored = client.get_commitment(&id);
+    assert_eq!(stored.status, String::from_str(&e, "violated"));
+    assert_eq!(client.get_total_value_locked(), 0);
+
+    // No further allocation once violated
+    let result = client.try_allocate(&id, &Address::generate(&e), &100);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Commitment has no rule violation")]
+fn test_mark_violated_without_violation_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 950, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+
+    client.mark_violated(&admin, &String::from_str(&e, "c_0"));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_mark_violated_unauthorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 800, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+
+    client.mark_violated(&owner, &String::from_str(&e, "c_0"));
+}
+
+#[test]
+fn test_matured_commitment_within_limit_not_violatable() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let expires_at = client.get_commitment(&id).expires_at;
+    e.ledger().with_mut(|l| l.timestamp = expires_at);
+
+    // Expiry alone is reported but cannot lock the principal as violated
+    assert!(client.check_violations(&id));
+    let result = client.try_mark_violated(&admin, &id);
+    assert!(result.is_err());
+
+    client.settle(&id, &owner);
+    assert_eq!(
+        client.get_commitment(&id).status,
+        String::from_str(&e, "settled")
+    );
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 1_000_000);
+}
+
+// Price feed stand-in answering `get_price_valid` with whatever was last set
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    pub fn set_price(e: Env, price: i128) {
+        e.storage().instance().set(&symbol_short!("price"), &price);
+    }
+
+    pub fn get_price_valid(e: Env, _asset: Address, _max_staleness: Option<u64>) -> OraclePrice {
+        OraclePrice {
+            price: e.storage().instance().get(&symbol_short!("price")).unwrap(),
+            updated_at: e.ledger().timestamp(),
+            decimals: 6,
+            confidence: 0,
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Price oracle not configured")]
+fn test_check_and_mark_violation_requires_oracle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 1000, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+
+    client.check_and_mark_violation(&admin, &String::from_str(&e, "c_0"));
+}
+
+#[test]
+fn test_check_and_mark_violation_anchors_unpriced_commitment() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let oracle_id = e.register_contract(None, MockPriceOracle);
+    let oracle = MockPriceOracleClient::new(&e, &oracle_id);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+    client.set_price_oracle(&admin, &oracle_id);
+    assert_eq!(client.get_price_oracle(), Some(oracle_id));
+    // Stored directly, so no entry price was recorded at creation
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 1000, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+    let id = String::from_str(&e, "c_0");
+
+    // The first check only anchors the value to the current price
+    oracle.set_price(&200);
+    assert!(!client.check_and_mark_violation(&admin, &id));
+    assert_eq!(client.get_commitment(&id).current_value, 1000);
+
+    // Later moves are measured from that anchor
+    oracle.set_price(&150);
+    assert!(client.check_and_mark_violation(&admin, &id));
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.current_value, 750);
+    assert_eq!(commitment.status, String::from_str(&e, "violated"));
+}
+
+#[test]
+#[should_panic(expected = "Value update too soon after the previous one")]
+fn test_check_and_mark_violation_respects_min_update_interval() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let oracle_id = e.register_contract(None, MockPriceOracle);
+    let oracle = MockPriceOracleClient::new(&e, &oracle_id);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+    client.set_price_oracle(&admin, &oracle_id);
+    client.add_updater(&admin, &admin);
+    client.set_min_update_interval(&admin, &3600);
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 1000, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+    let id = String::from_str(&e, "c_0");
+    oracle.set_price(&200);
+    client.check_and_mark_violation(&admin, &id);
+
+    // A pushed value and an oracle revaluation share one throttle
+    e.ledger().with_mut(|l| l.timestamp = 10_000);
+    client.update_value(&admin, &id, &990);
+    e.ledger().with_mut(|l| l.timestamp = 10_000 + 3599);
+    client.check_and_mark_violation(&admin, &id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_check_and_mark_violation_unauthorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    client.initialize(&admin, &e.register_contract(None, MockNftContract));
+    let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 1000, 10, 30, 0);
+    store_commitment(&e, &contract_id, &commitment);
+
+    client.check_and_mark_violation(&owner, &String::from_str(&e, "c_0"));
+}
+
+// ============================================================================
+// Penalty Split Tests
+// ============================================================================
+
+#[test]
+fn test_early_exit_penalty_split_70_30() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let treasury = Address::generate(&e);
+    let rewards_pool = Address::generate(&e);
+
+    client.set_penalty_split(&admin, &7000, &3000);
+    client.set_penalty_recipients(&admin, &treasury, &Some(rewards_pool.clone()));
+    assert_eq!(
+        client.get_penalty_split(),
+        Some(PenaltySplit {
+            treasury_bps: 7000,
+            rewards_bps: 3000
+        })
+    );
+
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+
+    // 10% penalty on 1000 = 100 -> 70 treasury / 30 rewards
+    assert_eq!(token.balance(&owner), 900);
+    assert_eq!(token.balance(&treasury), 70);
+    assert_eq!(token.balance(&rewards_pool), 30);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    let events = e.events().all();
+    let last = events.last().unwrap();
+    let data: (i128, i128, i128, i128, u64) = last.2.into_val(&e);
+    assert_eq!((data.0, data.1, data.2, data.3), (100, 900, 70, 30));
+}
+
+#[test]
+fn test_early_exit_releases_tvl() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert_eq!(client.get_total_value_locked(), 1000);
+
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+// Helper: decode the most recent `StatusChanged` event as
+// (commitment_id, old_status, new_status, timestamp)
+fn last_status_change(e: &Env) -> (String, String, String, u64) {
+    let events = e.events().all();
+    for i in (0..events.len()).rev() {
+        let (_, topics, data) = events.get(i).unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(e);
+        if name == symbol_short!("StatusChg") {
+            let id: String = topics.get(1).unwrap().into_val(e);
+            let (old_status, new_status, at): (String, String, u64) = data.into_val(e);
+            return (id, old_status, new_status, at);
+        }
+    }
+    panic!("no StatusChanged event");
+}
+
+#[test]
+fn test_early_exit_emits_status_changed() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    e.ledger().with_mut(|l| l.timestamp = 500);
+
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+
+    assert_eq!(
+        last_status_change(&e),
+        (
+            String::from_str(&e, "c_0"),
+            String::from_str(&e, "active"),
+            String::from_str(&e, "early_exit"),
+            500
+        )
+    );
+}
+
+#[test]
+fn test_settle_emits_status_changed() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let expires_at = client.get_commitment(&id).expires_at;
+    e.ledger().with_mut(|l| l.timestamp = expires_at);
+
+    client.settle(&id, &owner);
+
+    assert_eq!(
+        last_status_change(&e),
+        (
+            id,
+            String::from_str(&e, "active"),
+            String::from_str(&e, "settled"),
+            expires_at
+        )
+    );
+}
+
+#[test]
+fn test_early_exit_without_split_keeps_penalty() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+
+    client.early_exit(&String::from_str(&e, "c_0"), &owner);
+
+    assert_eq!(token.balance(&owner), 900);
+    assert_eq!(token.balance(&contract_id), 100);
+}
+
+#[test]
+fn test_settlement_receipt_on_settle() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "c_0");
+
+    assert_eq!(client.get_settlement_receipt(&commitment_id), None);
+
+    let settled_at = 31 * 86400;
+    e.ledger().with_mut(|l| l.timestamp = settled_at);
+    client.settle(&commitment_id, &owner);
+
+    let receipt = client.get_settlement_receipt(&commitment_id).unwrap();
+    assert_eq!(receipt.commitment_id, commitment_id);
+    assert_eq!(receipt.amount_paid, 1000);
+    assert_eq!(receipt.settled_at, settled_at);
+    assert_eq!(receipt.final_status, String::from_str(&e, "settled"));
+    assert_eq!(
+        TokenClient::new(&e, &asset).balance(&owner),
+        receipt.amount_paid
+    );
+}
+
+#[test]
+fn test_total_settled_value_accumulates() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let first = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let second = client.create_commitment(&owner, &2500, &asset, &test_rules(&e));
+    let exited = client.create_commitment(&owner, &400, &asset, &test_rules(&e));
+    assert_eq!(client.get_total_settled_value(), 0);
+
+    // Early exits are not settlements
+    client.early_exit(&exited, &owner);
+    assert_eq!(client.get_total_settled_value(), 0);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&first, &owner);
+    client.settle(&second, &owner);
+
+    let paid = client.get_settlement_receipt(&first).unwrap().amount_paid
+        + client.get_settlement_receipt(&second).unwrap().amount_paid;
+    assert_eq!(paid, 3500);
+    assert_eq!(client.get_total_settled_value(), paid);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_settlement_receipt_on_early_exit() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "c_0");
+
+    e.ledger().with_mut(|l| l.timestamp = 5 * 86400);
+    client.early_exit(&commitment_id, &owner);
+
+    let receipt = client.get_settlement_receipt(&commitment_id).unwrap();
+    assert_eq!(receipt.amount_paid, 900);
+    assert_eq!(receipt.settled_at, 5 * 86400);
+    assert_eq!(receipt.final_status, String::from_str(&e, "early_exit"));
+    assert_eq!(
+        TokenClient::new(&e, &asset).balance(&owner),
+        receipt.amount_paid
+    );
+}
+
+// Turns the funded `c_0` into a "safe" commitment whose value dipped to 800;
+// with max_loss_percent = 10 its payout floor is 900.
+fn setup_dipped_safe_commitment(e: &Env) -> (Address, Address, Address, Address) {
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(e, 1000, 10);
+    let mut commitment = CommitmentCoreContractClient::new(e, &contract_id)
+        .get_commitment(&String::from_str(e, "c_0"));
+    commitment.rules.commitment_type = String::from_str(e, "safe");
+    commitment.current_value = 800;
+    store_commitment(e, &contract_id, &commitment);
+    (admin, owner, contract_id, asset)
+}
+
+#[test]
+fn test_settle_safe_commitment_topped_up_from_insurance() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_dipped_safe_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    StellarAssetClient::new(&e, &asset).mint(&admin, &500);
+    client.deposit_insurance(&admin, &asset, &500);
+    assert_eq!(client.get_insurance_fund(&asset), 500);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&String::from_str(&e, "c_0"), &owner);
+
+    let token = TokenClient::new(&e, &asset);
+    assert_eq!(token.balance(&owner), 900);
+    assert_eq!(client.get_insurance_fund(&asset), 400);
+    assert_eq!(
+        client
+            .get_settlement_receipt(&String::from_str(&e, "c_0"))
+            .unwrap()
+            .amount_paid,
+        900
+    );
+}
+
+#[test]
+fn test_project_settlement_matches_settle_payout() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_dipped_safe_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "c_0");
+
+    // No insurance yet: only the booked value is projected
+    assert_eq!(client.project_settlement(&commitment_id), (800, 800));
+
+    StellarAssetClient::new(&e, &asset).mint(&admin, &500);
+    client.deposit_insurance(&admin, &asset, &500);
+    let (gross, net) = client.project_settlement(&commitment_id);
+    assert_eq!((gross, net), (900, 900));
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&commitment_id, &owner);
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), net);
+    assert_eq!(
+        client
+            .get_settlement_receipt(&commitment_id)
+            .unwrap()
+            .amount_paid,
+        net
+    );
+}
+
+#[test]
+#[should_panic(expected = "Commitment is not active")]
+fn test_project_settlement_rejects_settled_commitment() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "c_0");
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&commitment_id, &owner);
+    client.project_settlement(&commitment_id);
+}
+
+#[test]
+#[should_panic(expected = "Insurance fund cannot cover the safe commitment floor")]
+fn test_settle_safe_commitment_insufficient_insurance() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_dipped_safe_commitment(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    StellarAssetClient::new(&e, &asset).mint(&admin, &50);
+    client.deposit_insurance(&admin, &asset, &50);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&String::from_str(&e, "c_0"), &owner);
+}
+
+#[test]
+fn test_settle_non_safe_commitment_not_topped_up() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let mut commitment = client.get_commitment(&String::from_str(&e, "c_0"));
+    commitment.current_value = 800;
+    store_commitment(&e, &contract_id, &commitment);
+
+    StellarAssetClient::new(&e, &asset).mint(&admin, &500);
+    client.deposit_insurance(&admin, &asset, &500);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&String::from_str(&e, "c_0"), &owner);
+
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 800);
+    assert_eq!(client.get_insurance_fund(&asset), 500);
+}
+
+#[test]
+fn test_settle_by_owner_during_self_window() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_settlement_self_window(&admin, &86400);
+    assert_eq!(client.get_settlement_self_window(), 86400);
+
+    // Expiry is day 30; still inside the owner's window
+    e.ledger().with_mut(|l| l.timestamp = 30 * 86400 + 3600);
+    client.settle(&String::from_str(&e, "c_0"), &owner);
+
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_settle_by_keeper_during_self_window_rejected() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_settlement_self_window(&admin, &86400);
+
+    e.ledger().with_mut(|l| l.timestamp = 30 * 86400 + 3600);
+    client.settle(&String::from_str(&e, "c_0"), &Address::generate(&e));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_settle_by_seller_during_self_window_rejected_after_nft_sale() {
+    let e = Env::default();
+    let (admin, owner, contract_id, nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let nft_client = commitment_nft::CommitmentNFTContractClient::new(&e, &nft_id);
+    client.set_settlement_self_window(&admin, &86400);
+    let commitment_id = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let token_id = client.get_commitment(&commitment_id).nft_token_id;
+
+    e.ledger().with_mut(|l| l.timestamp = 30 * 86400 + 3600);
+    nft_client.settle(&admin, &token_id);
+    nft_client.transfer(&owner, &Address::generate(&e), &token_id);
+
+    // The buyer now holds the first-settlement window
+    client.settle(&commitment_id, &owner);
+}
+
+#[test]
+fn test_settle_by_keeper_after_self_window() {
+    let e = Env::default();
+    let (admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_settlement_self_window(&admin, &86400);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&String::from_str(&e, "c_0"), &Address::generate(&e));
+
+    // Funds still go to the owner
+    assert_eq!(TokenClient::new(&e, &asset).balance(&owner), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Invalid penalty split: bps must sum to 10000")]
+fn test_set_penalty_split_invalid_sum() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_penalty_split(&admin, &7000, &2000);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_penalty_split_non_admin() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_penalty_split(&owner, &7000, &3000);
+}
+
+// ============================================================================
+// Reward Claim Tests
+// ============================================================================
+
+// Helper: core wired to MockNftContract, funded owners, 50/50 penalty split with
+// the rewards share retained for claims.
+// Returns (core contract id, asset address, owners).
+fn setup_rewards(e: &Env) -> (Address, Address, Address, Address) {
+    e.mock_all_auths();
+
+    let admin = Address::generate(e);
+    let nft_id = e.register_contract(None, MockNftContract);
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(e, &contract_id);
+    client.initialize(&admin, &nft_id);
+    client.set_penalty_split(&admin, &5000, &5000);
+    client.set_penalty_recipients(&admin, &Address::generate(e), &None);
+
+    let token_admin = Address::generate(e);
+    let asset = e.register_stellar_asset_contract_v2(token_admin).address();
+    client.add_allowed_asset(&admin, &asset);
+    let exiter = Address::generate(e);
+    let holder = Address::generate(e);
+    StellarAssetClient::new(e, &asset).mint(&exiter, &10_000);
+    StellarAssetClient::new(e, &asset).mint(&holder, &10_000);
+
+    (contract_id, asset, exiter, holder)
+}
+
+#[test]
+fn test_claim_reward_after_settle() {
+    let e = Env::default();
+    let (contract_id, asset, exiter, holder) = setup_rewards(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+
+    let mut rules = test_rules(&e);
+    rules.early_exit_penalty = 12;
+    let exit_id = client.create_commitment(&exiter, &1000, &asset, &rules);
+    let hold_id = client.create_commitment(&holder, &1000, &asset, &rules);
+
+    // 12% penalty = 120 -> 60 treasury, 60 retained for rewards
+    client.early_exit(&exit_id, &exiter);
+    assert_eq!(client.get_claimable_reward(&hold_id), 0);
+
+    e.ledger().with_mut(|l| l.timestamp += 30 * 86400);
+    client.settle(&hold_id, &holder);
+    assert_eq!(client.get_claimable_reward(&hold_id), 60);
+
+    let before = token.balance(&holder);
+    assert_eq!(client.claim_reward(&hold_id, &holder), 60);
+    assert_eq!(token.balance(&holder), before + 60);
+    assert_eq!(client.get_claimable_reward(&hold_id), 0);
+
+    // Early-exited commitment earns nothing
+    assert!(client.try_claim_reward(&exit_id, &exiter).is_err());
+}
+
+#[test]
+fn test_claim_reward_pro_rata_by_amount_and_duration() {
+    let e = Env::default();
+    let (contract_id, asset, exiter, holder) = setup_rewards(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let holder2 = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&holder2, &10_000);
+
+    let mut rules = test_rules(&e);
+    rules.early_exit_penalty = 20;
+    rules.duration_days = 10;
+    let exit_id = client.create_commitment(&exiter, &3000, &asset, &rules);
+    // weights: 1000 * 10 and 1000 * 30 -> 1:3
+    let short_id = client.create_commitment(&holder, &1000, &asset, &rules);
+    rules.duration_days = 30;
+    let long_id = client.create_commitment(&holder2, &1000, &asset, &rules);
+
+    // 20% of 3000 = 600 -> 300 retained for rewards
+    client.early_exit(&exit_id, &exiter);
+
+    e.ledger().with_mut(|l| l.timestamp += 30 * 86400);
+    client.settle(&short_id, &holder);
+    client.settle(&long_id, &holder2);
+
+    assert_eq!(client.claim_reward(&short_id, &holder), 75);
+    assert_eq!(client.claim_reward(&long_id, &holder2), 225);
+}
+
+#[test]
+fn test_forfeited_rewards_return_to_pool() {
+    let e = Env::default();
+    let (contract_id, asset, exiter, holder) = setup_rewards(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let exiter2 = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&exiter2, &10_000);
+
+    let mut rules = test_rules(&e);
+    rules.early_exit_penalty = 12;
+    let exit_id = client.create_commitment(&exiter, &1000, &asset, &rules);
+    let exit2_id = client.create_commitment(&exiter2, &1000, &asset, &rules);
+    let hold_id = client.create_commitment(&holder, &1000, &asset, &rules);
+
+    // 60 retained, split 30/30 between the two commitments still accruing
+    client.early_exit(&exit_id, &exiter);
+    // The second exit forfeits its 30 and retains another 60, all for the holder
+    client.early_exit(&exit2_id, &exiter2);
+
+    e.ledger().with_mut(|l| l.timestamp += 30 * 86400);
+    client.settle(&hold_id, &holder);
+    assert_eq!(client.claim_reward(&hold_id, &holder), 120);
+}
+
+#[test]
+#[should_panic(expected = "Reward already claimed")]
+fn test_claim_reward_twice_fails() {
+    let e = Env::default();
+    let (contract_id, asset, exiter, holder) = setup_rewards(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let exit_id = client.create_commitment(&exiter, &1000, &asset, &test_rules(&e));
+    let hold_id = client.create_commitment(&holder, &1000, &asset, &test_rules(&e));
+    client.early_exit(&exit_id, &exiter);
+
+    e.ledger().with_mut(|l| l.timestamp += 30 * 86400);
+    client.settle(&hold_id, &holder);
+    client.claim_reward(&hold_id, &holder);
+    client.claim_reward(&hold_id, &holder);
+}
+
+#[test]
+#[should_panic(expected = "Invalid commitment status for this operation")]
+fn test_claim_reward_before_settle_fails() {
+    let e = Env::default();
+    let (contract_id, asset, _exiter, holder) = setup_rewards(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let hold_id = client.create_commitment(&holder, &1000, &asset, &test_rules(&e));
+    client.claim_reward(&hold_id, &holder);
+}
+
+// ============================================================================
+// NFT Contract Probe Tests
+// ============================================================================
+
+#[test]
+fn test_initialize_probe_accepts_nft_contract() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let nft_id = e.register_contract(None, commitment_nft::CommitmentNFTContract);
+    commitment_nft::CommitmentNFTContractClient::new(&e, &nft_id).initialize(&admin);
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.initialize(&admin, &nft_id);
+
+    assert_eq!(client.get_nft_contract(), nft_id);
+}
+
+#[test]
+#[should_panic(expected = "Invalid NFT contract: probe call failed")]
+fn test_initialize_probe_rejects_non_nft_address() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let not_nft = Address::generate(&e);
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+
+    CommitmentCoreContractClient::new(&e, &contract_id).initialize(&admin, &not_nft);
+}
+
+#[test]
+#[should_panic(expected = "Invalid NFT contract: probe call failed")]
+fn test_initialize_probe_rejects_non_nft_contract() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    // A deployed contract that does not implement the NFT interface
+    let other_core = e.register_contract(None, CommitmentCoreContract);
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+
+    CommitmentCoreContractClient::new(&e, &contract_id).initialize(&admin, &other_core);
+}
+
+// ============================================================================
+// Allocation Registry Tests
+// ============================================================================
+
+#[test]
+fn test_allocate_records_each_pool() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let id = String::from_str(&e, "c_0");
+    let pool_a = Address::generate(&e);
+    let pool_b = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool_a);
+    client.approve_pool(&owner, &id, &pool_b);
+
+    e.ledger().with_mut(|l| l.timestamp = 500);
+    client.allocate(&id, &pool_a, &300);
+    client.allocate(&id, &pool_b, &200);
+    client.allocate(&id, &pool_a, &100);
+
+    let records = client.get_allocations(&id);
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records.get(0).unwrap(),
+        AllocationRecord {
+            pool: pool_a.clone(),
+            amount: 400,
+            at: 500
+        }
+    );
+    assert_eq!(records.get(1).unwrap().pool, pool_b);
+    assert_eq!(records.get(1).unwrap().amount, 200);
+
+    assert_eq!(token.balance(&pool_a), 400);
+    assert_eq!(token.balance(&pool_b), 200);
+    assert_eq!(client.get_commitment(&id).current_value, 400);
+}
+
+#[test]
+fn test_deallocate_updates_registry() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let id = String::from_str(&e, "c_0");
+    let pool_a = Address::generate(&e);
+    let pool_b = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool_a);
+    client.approve_pool(&owner, &id, &pool_b);
+
+    client.allocate(&id, &pool_a, &300);
+    client.allocate(&id, &pool_b, &200);
+
+    client.deallocate(&id, &pool_a, &100);
+    client.deallocate(&id, &pool_b, &200);
+
+    let records = client.get_allocations(&id);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records.get(0).unwrap().pool, pool_a);
+    assert_eq!(records.get(0).unwrap().amount, 200);
+
+    assert_eq!(token.balance(&contract_id), 800);
+    assert_eq!(client.get_commitment(&id).current_value, 800);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient balance")]
+fn test_deallocate_more_than_allocated_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool);
+
+    client.allocate(&id, &pool, &300);
+    client.deallocate(&id, &pool, &301);
+}
+
+#[test]
+#[should_panic(expected = "Commitment is not active")]
+fn test_deallocate_to_settled_commitment_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool);
+    client.allocate(&id, &pool, &300);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&id, &owner);
+
+    client.deallocate(&id, &pool, &300);
+}
+
+// ============================================================================
+// Compliance-Gated Allocation Tests
+// ============================================================================
+
+#[test]
+fn test_set_min_alloc_score_and_engine() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let engine = Address::generate(&e);
+
+    assert_eq!(client.get_min_alloc_score(), 0);
+    assert_eq!(client.get_attestation_engine(), None);
+
+    client.set_attestation_engine(&admin, &engine);
+    client.set_min_alloc_score(&admin, &70);
+
+    assert_eq!(client.get_attestation_engine(), Some(engine));
+    assert_eq!(client.get_min_alloc_score(), 70);
+}
+
+#[test]
+#[should_panic(expected = "Invalid percent")]
+fn test_set_min_alloc_score_above_100_fails() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_min_alloc_score(&admin, &101);
+}
+
+#[test]
+#[should_panic(expected = "Compliance score below minimum for allocation")]
+fn test_allocate_blocked_when_score_unavailable() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool);
+
+    // Engine address that cannot answer counts as score 0
+    client.set_attestation_engine(&admin, &Address::generate(&e));
+    client.set_min_alloc_score(&admin, &50);
+
+    client.allocate(&id, &pool, &100);
+}
+
+#[test]
+#[should_panic(expected = "Compliance score below minimum for allocation")]
+fn test_allocate_blocked_when_engine_missing() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+    client.approve_pool(&owner, &id, &pool);
+
+    // A minimum with no engine to check it against fails closed
+    client.set_min_alloc_score(&admin, &50);
+
+    client.allocate(&id, &pool, &100);
+}
+
+#[test]
+fn test_shared_commitment_early_exit_splits_by_share() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let partner = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&partner, &1_000_000);
+    let token = TokenClient::new(&e, &asset);
+
+    let contributors = vec![&e, (owner.clone(), 3_000), (partner.clone(), 1_000)];
+    let commitment_id = client.create_shared_commitment(&contributors, &asset, &test_rules(&e));
+
+    let commitment = client.get_commitment(&commitment_id);
+    assert_eq!(commitment.owner, owner);
+    assert_eq!(commitment.amount, 4_000);
+    assert_eq!(client.get_shares(&commitment_id), contributors);
+    assert_eq!(token.balance(&contract_id), 4_000);
+
+    // 5% penalty leaves 3_800, split 3:1
+    client.early_exit(&commitment_id, &owner);
+    assert_eq!(token.balance(&owner), 1_000_000 - 3_000 + 2_850);
+    assert_eq!(token.balance(&partner), 1_000_000 - 1_000 + 950);
+}
+
+#[test]
+fn test_shared_commitment_failed_transfer_held_for_claim() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let partner = Address::generate(&e);
+
+    // An asset whose issuer can revoke a holder's authorization
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let asset = sac.address();
+    let asset_admin = StellarAssetClient::new(&e, &asset);
+    asset_admin.mint(&owner, &1_000_000);
+    asset_admin.mint(&partner, &1_000_000);
+    client.add_allowed_asset(&admin, &asset);
+    let token = TokenClient::new(&e, &asset);
+
+    let contributors = vec![&e, (owner.clone(), 3_000), (partner.clone(), 1_000)];
+    let commitment_id = client.create_shared_commitment(&contributors, &asset, &test_rules(&e));
+
+    // The partner can no longer receive; the owner is still paid
+    asset_admin.set_authorized(&partner, &false);
+    client.early_exit(&commitment_id, &owner);
+    assert_eq!(token.balance(&owner), 1_000_000 - 3_000 + 2_850);
+    assert_eq!(token.balance(&partner), 1_000_000 - 1_000);
+    assert_eq!(client.get_shared_claim(&commitment_id, &partner), 950);
+
+    asset_admin.set_authorized(&partner, &true);
+    assert_eq!(client.claim_shared_payout(&partner, &commitment_id), 950);
+    assert_eq!(token.balance(&partner), 1_000_000 - 1_000 + 950);
+    assert_eq!(client.get_shared_claim(&commitment_id, &partner), 0);
+    // Only the early-exit penalty stays behind
+    assert_eq!(token.balance(&contract_id), 200);
+}
+
+#[test]
+#[should_panic(expected = "Nothing to claim")]
+fn test_claim_shared_payout_without_claim_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let partner = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&partner, &1_000_000);
+
+    let contributors = vec![&e, (owner.clone(), 3_000), (partner.clone(), 1_000)];
+    let commitment_id = client.create_shared_commitment(&contributors, &asset, &test_rules(&e));
+    client.early_exit(&commitment_id, &owner);
+
+    client.claim_shared_payout(&partner, &commitment_id);
+}
+
+#[test]
+#[should_panic(expected = "Invalid contributors")]
+fn test_shared_commitment_rejects_duplicate_contributor() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let contributors = vec![&e, (owner.clone(), 1_000), (owner.clone(), 1_000)];
+    client.create_shared_commitment(&contributors, &asset, &test_rules(&e));
+}
+
+#[test]
+fn test_get_shares_empty_for_single_owner_commitment() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let commitment_id = client.create_commitment(&owner, &1_000, &asset, &test_rules(&e));
+    assert!(client.get_shares(&commitment_id).is_empty());
+}
+
+#[test]
+fn test_get_commitments_marks_missing_ids() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut ids = Vec::new(&e);
+    for amount in [1_000, 2_000, 3_000] {
+        ids.push_back(client.create_commitment(&owner, &amount, &asset, &test_rules(&e)));
+    }
+    let missing = String::from_str(&e, "c_404");
+    ids.insert(1, missing);
+
+    let commitments = client.get_commitments(&ids);
+    assert_eq!(commitments.len(), 4);
+    assert_eq!(commitments.get_unchecked(0).unwrap().amount, 1_000);
+    assert_eq!(commitments.get_unchecked(1), None);
+    assert_eq!(commitments.get_unchecked(2).unwrap().amount, 2_000);
+    assert_eq!(commitments.get_unchecked(3).unwrap().amount, 3_000);
+}
+
+#[test]
+#[should_panic(expected = "Too many ids in one call")]
+fn test_get_commitments_rejects_oversized_batch() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut ids = Vec::new(&e);
+    for _ in 0..=MAX_BULK_LOOKUP {
+        ids.push_back(String::from_str(&e, "c_0"));
+    }
+    client.get_commitments(&ids);
+}
+
+#[test]
+#[should_panic(expected = "Too few attestations recorded to settle")]
+fn test_settle_blocked_without_engine_when_attestations_required() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_min_attestations(&admin, &1);
+    assert_eq!(client.get_min_attestations(), 1);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    client.settle(&String::from_str(&e, "c_0"), &owner);
+}
+
+#[test]
+fn test_commitment_ids_carry_owner_prefix() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let other_owner = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset).mint(&other_owner, &1_000_000);
+
+    let first = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+    let second = client.create_commitment(&other_owner, &1000, &asset, &test_rules(&e));
+    let third = client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    let to_bytes = |id: &String| {
+        let mut buf = [0u8; 32];
+        let len = id.len() as usize;
+        id.copy_into_slice(&mut buf[..len]);
+        (buf, len)
+    };
+    let (first, first_len) = to_bytes(&first);
+    let (second, second_len) = to_bytes(&second);
+    let (third, third_len) = to_bytes(&third);
+
+    // `<8 hex chars>_<counter>`, unique through the global counter
+    assert_eq!(first_len, 10);
+    assert_eq!(&first[8..first_len], b"_0");
+    assert_eq!(&second[8..second_len], b"_1");
+    assert_eq!(&third[8..third_len], b"_2");
+    assert_ne!(first[..8], second[..8]);
+    assert_eq!(first[..8], third[..8]);
+    assert!(first[..8].iter().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[test]
+fn test_recompute_tvl_reads_legacy_and_prefixed_ids() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    // A commitment stored under the legacy `c_<counter>` scheme...
+    let mut legacy = create_test_commitment(&e, "c_0", &owner, 400, 400, 10, 30, 0);
+    legacy.asset_address = asset.clone();
+    store_commitment(&e, &contract_id, &legacy);
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &1u64);
+    });
+
+    // ...and one created with an owner-prefixed id
+    client.create_commitment(&owner, &1000, &asset, &test_rules(&e));
+
+    assert_eq!(client.recompute_tvl(&admin), 1400);
+    assert_eq!(client.reconcile(&asset).0, 1400);
+}
+
+#[test]
+fn test_allocate_to_approved_pool() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+
+    client.approve_pool(&owner, &id, &pool);
+    client.approve_pool(&owner, &id, &pool);
+    assert_eq!(client.get_allowed_pools(&id).len(), 1);
+
+    client.allocate(&id, &pool, &400);
+    assert_eq!(token.balance(&pool), 400);
+    assert_eq!(client.get_commitment(&id).current_value, 600);
+}
+
+#[test]
+#[should_panic(expected = "Target pool not approved by the owner")]
+fn test_allocate_to_unapproved_pool_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+
+    client.approve_pool(&owner, &id, &Address::generate(&e));
+    client.allocate(&id, &Address::generate(&e), &100);
+}
+
+#[test]
+#[should_panic(expected = "Target pool not approved by the owner")]
+fn test_allocate_after_revoke_pool_fails() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = String::from_str(&e, "c_0");
+    let pool = Address::generate(&e);
+
+    client.approve_pool(&owner, &id, &pool);
+    client.revoke_pool(&owner, &id, &pool);
+    assert!(client.get_allowed_pools(&id).is_empty());
+    client.allocate(&id, &pool, &100);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_approve_pool_requires_owner() {
+    let e = Env::default();
+    let (_admin, _owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.approve_pool(
+        &Address::generate(&e),
+        &String::from_str(&e, "c_0"),
+        &Address::generate(&e),
+    );
+}
+
+#[test]
+fn test_require_transition_allows_active_to_terminal() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    e.as_contract(&contract_id, || {
+        for to in [
+            CommitmentStatus::Settled,
+            CommitmentStatus::EarlyExit,
+            CommitmentStatus::Violated,
+        ] {
+            require_transition(&e, CommitmentStatus::Active, to);
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "Illegal commitment status transition")]
+fn test_require_transition_rejects_settled_to_active() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    e.as_contract(&contract_id, || {
+        require_transition(&e, CommitmentStatus::Settled, CommitmentStatus::Active);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Illegal commitment status transition")]
+fn test_require_transition_rejects_violated_to_settled() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    e.as_contract(&contract_id, || {
+        require_transition(&e, CommitmentStatus::Violated, CommitmentStatus::Settled);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Illegal commitment status transition")]
+fn test_require_transition_rejects_early_exit_to_violated() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    e.as_contract(&contract_id, || {
+        require_transition(&e, CommitmentStatus::EarlyExit, CommitmentStatus::Violated);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Illegal commitment status transition")]
+fn test_require_transition_rejects_active_to_active() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    e.as_contract(&contract_id, || {
+        require_transition(&e, CommitmentStatus::Active, CommitmentStatus::Active);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Illegal commitment status transition")]
+fn test_settled_commitment_cannot_be_marked_violated() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _asset) = setup_funded_commitment(&e, 1000, 10);
+    let id = String::from_str(&e, "c_0");
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    CommitmentCoreContractClient::new(&e, &contract_id).settle(&id, &owner);
+
+    e.as_contract(&contract_id, || {
+        let mut commitment = read_commitment(&e, &id).unwrap();
+        transition_status(&e, &mut commitment, CommitmentStatus::Violated);
+    });
+}
+
+#[test]
+fn test_create_commitment_auto_alloc_deploys_fraction() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let pool = Address::generate(&e);
+
+    // Unconfigured: nothing is allocated
+    assert_eq!(client.get_auto_allocation(), (0, 10_000));
+    let untouched =
+        client.create_commitment_auto_alloc(&owner, &1000, &asset, &test_rules(&e), &pool);
+    assert_eq!(client.get_commitment(&untouched).current_value, 1000);
+    assert!(client.get_allocations(&untouched).is_empty());
+
+    client.set_auto_allocation(&admin, &3000, &5000);
+    let id = client.create_commitment_auto_alloc(&owner, &1000, &asset, &test_rules(&e), &pool);
+
+    assert_eq!(client.get_commitment(&id).current_value, 700);
+    assert_eq!(token.balance(&pool), 300);
+    assert_eq!(client.get_allocations(&id).len(), 1);
+    assert!(client.get_allowed_pools(&id).contains(&pool));
+    // TVL still counts the allocated funds as committed
+    assert_eq!(client.get_total_value_locked(), 2000);
+    // 30% allocated against a 10% loss limit is not a loss
+    assert!(!client.check_violations(&id));
+    assert!(client.try_mark_violated(&admin, &id).is_err());
+}
+
+#[test]
+fn test_create_commitment_auto_alloc_keeps_reserve() {
+    let e = Env::default();
+    let (admin, owner, contract_id, _nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let token = TokenClient::new(&e, &asset);
+    let pool = Address::generate(&e);
+
+    client.set_auto_allocation(&admin, &5000, &5000);
+    client.create_commitment_auto_alloc(&owner, &1000, &asset, &test_rules(&e), &pool);
+    assert_eq!(token.balance(&pool), 500);
+
+    // 80% of the 2000 now locked must stay held, which leaves nothing spare
+    client.set_auto_allocation(&admin, &2000, &8000);
+    let id = client.create_commitment_auto_alloc(&owner, &1000, &asset, &test_rules(&e), &pool);
+    assert_eq!(client.get_commitment(&id).current_value, 1000);
+    assert!(client.get_allocations(&id).is_empty());
+    assert_eq!(token.balance(&pool), 500);
+    assert_eq!(client.get_held_balance(&asset), 1500);
+}
+
+#[test]
+#[should_panic(expected = "Auto-allocation would breach the reserve ratio")]
+fn test_set_auto_allocation_rejects_reserve_breach() {
+    let e = Env::default();
+    let (admin, _owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_auto_allocation(&admin, &6000, &5000);
+}
+
+#[test]
+fn test_settle_batch_settles_only_matured() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, nft_id, asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let nft_client = commitment_nft::CommitmentNFTContractClient::new(&e, &nft_id);
+
+    let mut rules = test_rules(&e);
+    rules.duration_days = 10;
+    let short = client.create_commitment(&owner, &1000, &asset, &rules);
+    rules.duration_days = 30;
+    let medium = client.create_commitment(&owner, &1000, &asset, &rules);
+    rules.duration_days = 60;
+    let long = client.create_commitment(&owner, &1000, &asset, &rules);
+
+    e.ledger().with_mut(|l| l.timestamp = 31 * 86400);
+    let ids = vec![
+        &e,
+        short.clone(),
+        String::from_str(&e, "missing"),
+        long.clone(),
+        medium.clone(),
+    ];
+    let settled = client.settle_batch(&owner, &ids);
+    assert_eq!(settled, vec![&e, short.clone(), medium.clone()]);
+
+    let settled_status = String::from_str(&e, "settled");
+    assert_eq!(client.get_commitment(&short).status, settled_status);
+    assert_eq!(client.get_commitment(&medium).status, settled_status);
+    assert_eq!(
+        client.get_commitment(&long).status,
+        String::from_str(&e, "active")
+    );
+    assert!(!nft_client.is_active(&client.get_commitment(&short).nft_token_id));
+    assert!(!nft_client.is_active(&client.get_commitment(&medium).nft_token_id));
+    assert!(nft_client.is_active(&client.get_commitment(&long).nft_token_id));
+    assert_eq!(client.get_total_value_locked(), 1000);
+
+    // Already-settled ids are skipped on a second pass
+    assert_eq!(client.settle_batch(&owner, &ids), Vec::new(&e));
+}
+
+#[test]
+#[should_panic(expected = "Too many ids in one call")]
+fn test_settle_batch_rejects_oversized_batch() {
+    let e = Env::default();
+    let (_admin, owner, contract_id, _nft_id, _asset) = setup_with_nft(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let mut ids = Vec::new(&e);
+    for _ in 0..=MAX_SETTLE_BATCH {
+        ids.push_back(String::from_str(&e, "c_0"));
+    }
+    client.settle_batch(&owner, &ids);
+}
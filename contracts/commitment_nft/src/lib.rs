@@ -1,12 +1,21 @@
 #![no_std]
-use shared_utils::{EmergencyControl, Pausable};
+use shared_utils::{
+    BatchProcessor, EmergencyControl, Pausable, PauseRegistry, Storage, TimeUtils, Validation,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
+    String, Symbol, TryFromVal, Val, Vec,
 };
 
 /// Current storage/contract version for migrations
-const CURRENT_VERSION: u32 = 1;
+const CURRENT_VERSION: u32 = 2;
+
+/// Tokens `migrate` rewrites per call
+const MIGRATION_PAGE_SIZE: u32 = 50;
+
+/// Upper bound on the secondary-sale royalty (10%).
+pub const MAX_ROYALTY_BPS: u32 = 1_000;
 
 // ============================================================================
 // Error Types
@@ -55,6 +64,20 @@ pub enum ContractError {
     TransferToZeroAddress = 18,
     /// NFT is locked (active commitment) and cannot be transferred
     NFTLocked = 19,
+    /// NFT is frozen by the admin and cannot be transferred
+    TokenFrozen = 20,
+    /// Batch is empty, too large, or batch operations are disabled
+    InvalidBatchSize = 21,
+    /// Minting would exceed the configured max supply
+    SupplyCapReached = 22,
+    /// Royalty above MAX_ROYALTY_BPS
+    InvalidRoyalty = 23,
+    /// `Unsettled` passed as a settlement reason
+    InvalidSettlementReason = 24,
+    /// Token metadata was frozen with `freeze_metadata`
+    MetadataFrozen = 25,
+    /// Recipient already holds `max_per_owner` tokens
+    OwnerCapExceeded = 26,
 }
 
 // ============================================================================
@@ -75,6 +98,20 @@ pub struct CommitmentMetadata {
     pub asset_address: Address,
 }
 
+/// Why a token was deactivated
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SettlementReason {
+    /// Token is still active
+    Unsettled = 0,
+    /// Settled at or after expiry
+    Matured = 1,
+    /// Commitment breached its rules
+    Violated = 2,
+    /// Owner exited before expiry
+    EarlyExit = 3,
+}
+
 /// The Commitment NFT structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -84,6 +121,40 @@ pub struct CommitmentNFT {
     pub metadata: CommitmentMetadata,
     pub is_active: bool,
     pub early_exit_penalty: u32,
+    /// Latest value reported by the core contract (starts at `initial_amount`)
+    pub current_value: i128,
+    /// Why the token was deactivated (`Unsettled` while active)
+    pub settlement_reason: SettlementReason,
+    /// Set by `freeze_metadata`; metadata, including `current_value`, is then immutable
+    pub metadata_frozen: bool,
+}
+
+/// Marketplace-facing view of a token returned by `get_public_metadata`.
+///
+/// The in-flight `current_value` is left out. `final_value` stays `None` and
+/// `settlement_reason` `Unsettled` until the token is settled (or violated),
+/// so an active token never shows an outcome it does not have yet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicMetadata {
+    pub token_id: u32,
+    pub owner: Address,
+    pub metadata: CommitmentMetadata,
+    pub is_active: bool,
+    pub early_exit_penalty: u32,
+    /// Value the token settled at: core records the amount paid out on
+    /// settlement and early exit, and the remaining value on violation
+    pub final_value: Option<i128>,
+    /// Why the token was deactivated (`Unsettled` while active)
+    pub settlement_reason: SettlementReason,
+}
+
+/// Secondary-sale royalty terms (EIP-2981 style), shared by all tokens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Royalty {
+    pub receiver: Address,
+    pub bps: u32,
 }
 
 /// Parameters for batch NFT transfer operations
@@ -120,6 +191,20 @@ pub enum DataKey {
     ReentrancyGuard,
     /// Contract version
     Version,
+    /// Admin freeze flag (token_id -> bool)
+    Frozen(u32),
+    /// Maximum number of mintable tokens (0 = unlimited)
+    MaxSupply,
+    /// Underlying commitment breached its rules (token_id -> bool)
+    Violated(u32),
+    /// Secondary-sale royalty terms (Royalty)
+    Royalty,
+    /// Number of minted tokens still active (unsettled, not violated)
+    ActiveSupply,
+    /// Maximum number of tokens one address may hold (0 = unlimited)
+    MaxPerOwner,
+    /// In-progress `migrate`: (next index into TokenIds, active tokens so far)
+    MigrationProgress,
 }
 
 // Events
@@ -157,6 +242,18 @@ impl CommitmentNFTContract {
         // Initialize paused state (default: not paused)
         e.storage().instance().set(&Pausable::PAUSED_KEY, &false);
 
+        // No supply cap until the admin sets one
+        e.storage().instance().set(&DataKey::MaxSupply, &0u32);
+
+        // No royalty until the admin sets one; the admin receives it by default
+        e.storage().instance().set(
+            &DataKey::Royalty,
+            &Royalty {
+                receiver: admin,
+                bps: 0,
+            },
+        );
+
         Ok(())
     }
 
@@ -187,6 +284,39 @@ impl CommitmentNFTContract {
         Pausable::is_paused(&e)
     }
 
+    /// Pause one operation scope (e.g. `mint`) without pausing the contract
+    pub fn pause_scope(e: Env, caller: Address, scope: Symbol) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        PauseRegistry::pause_scope(&e, &scope);
+        Ok(())
+    }
+
+    /// Unpause an operation scope
+    pub fn unpause_scope(e: Env, caller: Address, scope: Symbol) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        PauseRegistry::unpause_scope(&e, &scope);
+        Ok(())
+    }
+
+    /// Check if an operation scope is paused here or in the shared registry
+    pub fn is_scope_paused(e: Env, scope: Symbol) -> bool {
+        PauseRegistry::is_scope_paused(&e, &scope)
+    }
+
+    /// Follow (or stop following, with `None`) a shared pause registry.
+    ///
+    /// While a registry is set, a scope it fails to report on is treated as
+    /// paused; clearing the registry restores the local flags alone.
+    pub fn set_pause_registry(
+        e: Env,
+        caller: Address,
+        registry: Option<Address>,
+    ) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        PauseRegistry::set_registry(&e, registry);
+        Ok(())
+    }
+
     /// Validate commitment type
     fn is_valid_commitment_type(e: &Env, commitment_type: &String) -> bool {
         let safe = String::from_str(e, "safe");
@@ -237,6 +367,105 @@ impl CommitmentNFTContract {
         read_version(&e)
     }
 
+    /// Set the maximum number of mintable tokens (admin-only, 0 = unlimited).
+    ///
+    /// A cap at or below the current supply blocks further mints.
+    pub fn set_max_supply(e: Env, caller: Address, max_supply: u32) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        e.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+        e.events()
+            .publish((Symbol::new(&e, "MaxSupplySet"),), (max_supply,));
+        Ok(())
+    }
+
+    /// Get the maximum number of mintable tokens (0 = unlimited)
+    pub fn get_max_supply(e: Env) -> u32 {
+        read_max_supply(&e)
+    }
+
+    /// Set the maximum number of tokens one address may hold (admin-only,
+    /// 0 = unlimited).
+    ///
+    /// Enforced on `mint` and `transfer` recipients; holders already above a
+    /// lowered cap keep their tokens.
+    pub fn set_max_per_owner(
+        e: Env,
+        caller: Address,
+        max_per_owner: u32,
+    ) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        e.storage()
+            .instance()
+            .set(&DataKey::MaxPerOwner, &max_per_owner);
+        e.events()
+            .publish((Symbol::new(&e, "MaxPerOwnerSet"),), (max_per_owner,));
+        Ok(())
+    }
+
+    /// Get the maximum number of tokens one address may hold (0 = unlimited)
+    pub fn get_max_per_owner(e: Env) -> u32 {
+        read_max_per_owner(&e)
+    }
+
+    /// Set the royalty receiver and rate in basis points (admin-only).
+    ///
+    /// `bps` may not exceed `MAX_ROYALTY_BPS`.
+    pub fn set_royalty(
+        e: Env,
+        caller: Address,
+        receiver: Address,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        if bps > MAX_ROYALTY_BPS {
+            return Err(ContractError::InvalidRoyalty);
+        }
+        e.storage().instance().set(
+            &DataKey::Royalty,
+            &Royalty {
+                receiver: receiver.clone(),
+                bps,
+            },
+        );
+        e.events()
+            .publish((Symbol::new(&e, "RoyaltySet"),), (receiver, bps));
+        Ok(())
+    }
+
+    /// Royalty owed on a secondary sale of `token_id` for `sale_price`.
+    ///
+    /// Returns `(receiver, sale_price * bps / 10_000)`. Read-only metadata:
+    /// marketplaces choose whether to honor it.
+    pub fn royalty_info(
+        e: Env,
+        token_id: u32,
+        sale_price: i128,
+    ) -> Result<(Address, i128), ContractError> {
+        if !e.storage().persistent().has(&DataKey::NFT(token_id)) {
+            return Err(ContractError::TokenNotFound);
+        }
+        if sale_price < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        // Deployments initialized before royalties existed owe nothing
+        let royalty: Royalty = match e.storage().instance().get(&DataKey::Royalty) {
+            Some(royalty) => royalty,
+            None => Royalty {
+                receiver: e
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Admin)
+                    .ok_or(ContractError::NotInitialized)?,
+                bps: 0,
+            },
+        };
+        let amount = sale_price
+            .checked_mul(royalty.bps as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / 10_000;
+        Ok((royalty.receiver, amount))
+    }
+
     /// Update admin (admin-only).
     pub fn set_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), ContractError> {
         require_admin(&e, &caller)?;
@@ -257,7 +486,12 @@ impl CommitmentNFTContract {
     }
 
     /// Migrate storage from a previous version to CURRENT_VERSION (admin-only).
-    pub fn migrate(e: Env, caller: Address, from_version: u32) -> Result<(), ContractError> {
+    ///
+    /// Tokens are rewritten in the current layout and recounted into
+    /// `active_supply` `MIGRATION_PAGE_SIZE` at a time; call again with the
+    /// same `from_version` until it returns `true`, at which point the
+    /// version is bumped.
+    pub fn migrate(e: Env, caller: Address, from_version: u32) -> Result<bool, ContractError> {
         require_admin(&e, &caller)?;
 
         let stored_version = read_version(&e);
@@ -282,10 +516,42 @@ impl CommitmentNFTContract {
                 .set(&DataKey::ReentrancyGuard, &false);
         }
 
+        let token_ids: Vec<u32> = e
+            .storage()
+            .instance()
+            .get(&DataKey::TokenIds)
+            .unwrap_or(Vec::new(&e));
+        let (start, mut active) = e
+            .storage()
+            .instance()
+            .get::<_, (u32, u32)>(&DataKey::MigrationProgress)
+            .unwrap_or((0, 0));
+        let end = start
+            .saturating_add(MIGRATION_PAGE_SIZE)
+            .min(token_ids.len());
+        for i in start..end {
+            let token_id = token_ids.get_unchecked(i);
+            if let Some(nft) = read_nft(&e, token_id) {
+                if nft.is_active {
+                    active += 1;
+                }
+                e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+            }
+        }
+
+        if end < token_ids.len() {
+            e.storage()
+                .instance()
+                .set(&DataKey::MigrationProgress, &(end, active));
+            return Ok(false);
+        }
+
+        e.storage().instance().remove(&DataKey::MigrationProgress);
+        e.storage().instance().set(&DataKey::ActiveSupply, &active);
         e.storage()
             .instance()
             .set(&DataKey::Version, &CURRENT_VERSION);
-        Ok(())
+        Ok(true)
     }
 
     // ========================================================================
@@ -334,8 +600,9 @@ impl CommitmentNFTContract {
         e.storage().instance().set(&DataKey::ReentrancyGuard, &true);
         EmergencyControl::require_not_emergency(&e);
 
-        // Check if contract is paused
+        // Check if contract or minting is paused
         Pausable::require_not_paused(&e);
+        PauseRegistry::require_not_paused_scope(&e, &symbol_short!("mint"));
 
         // CHECKS: Verify contract is initialized
         if !e.storage().instance().has(&DataKey::Admin) {
@@ -371,13 +638,29 @@ impl CommitmentNFTContract {
             return Err(ContractError::InvalidAmount);
         }
 
-        // EFFECTS: Update state
         // Generate unique token_id
         let token_id: u32 = e
             .storage()
             .instance()
             .get(&DataKey::TokenCounter)
             .unwrap_or(0);
+
+        // Enforce the supply cap (tokens are never burned, so the counter is the supply)
+        let max_supply = read_max_supply(&e);
+        if max_supply > 0 && token_id >= max_supply {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(ContractError::SupplyCapReached);
+        }
+        if owner_at_cap(&e, &owner) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(ContractError::OwnerCapExceeded);
+        }
+
+        // EFFECTS: Update state
         let next_token_id = token_id + 1;
         e.storage()
             .instance()
@@ -385,8 +668,7 @@ impl CommitmentNFTContract {
 
         // Calculate timestamps
         let created_at = e.ledger().timestamp();
-        let seconds_per_day: u64 = 86400;
-        let expires_at = created_at + (duration_days as u64 * seconds_per_day);
+        let expires_at = created_at + TimeUtils::days_to_seconds(duration_days);
 
         // Create CommitmentMetadata
         let metadata = CommitmentMetadata {
@@ -407,6 +689,9 @@ impl CommitmentNFTContract {
             metadata,
             is_active: true,
             early_exit_penalty,
+            current_value: initial_amount,
+            settlement_reason: SettlementReason::Unsettled,
+            metadata_frozen: false,
         };
 
         // Store NFT data
@@ -424,15 +709,7 @@ impl CommitmentNFTContract {
         );
 
         // Update owner tokens list
-        let mut owner_tokens: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(owner.clone()))
-            .unwrap_or(Vec::new(&e));
-        owner_tokens.push_back(token_id);
-        e.storage()
-            .persistent()
-            .set(&DataKey::OwnerTokens(owner.clone()), &owner_tokens);
+        Storage::push_to_index(&e, &DataKey::OwnerTokens(owner.clone()), token_id);
 
         // Add token_id to the list of all tokens
         let mut token_ids: Vec<u32> = e
@@ -442,12 +719,30 @@ impl CommitmentNFTContract {
             .unwrap_or(Vec::new(&e));
         token_ids.push_back(token_id);
         e.storage().instance().set(&DataKey::TokenIds, &token_ids);
+        track_active_supply(&e, true);
 
         // Clear reentrancy guard
         e.storage()
             .instance()
             .set(&DataKey::ReentrancyGuard, &false);
 
+        // Keep the new token's entries (and the instance) from being archived
+        let persistent = e.storage().persistent();
+        persistent.extend_ttl(&DataKey::NFT(token_id), TTL_THRESHOLD, TTL_EXTEND_TO);
+        persistent.extend_ttl(
+            &DataKey::OwnerBalance(owner.clone()),
+            TTL_THRESHOLD,
+            TTL_EXTEND_TO,
+        );
+        persistent.extend_ttl(
+            &DataKey::OwnerTokens(owner.clone()),
+            TTL_THRESHOLD,
+            TTL_EXTEND_TO,
+        );
+        e.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
         // Emit mint event
         e.events().publish(
             (symbol_short!("Mint"), token_id, owner.clone()),
@@ -463,19 +758,31 @@ impl CommitmentNFTContract {
 
     /// Get NFT metadata by token_id
     pub fn get_metadata(e: Env, token_id: u32) -> Result<CommitmentNFT, ContractError> {
-        e.storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or(ContractError::TokenNotFound)
+        read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)
+    }
+
+    /// Get the public view of a token: the reduced view while it is
+    /// unsettled, with `final_value` and `settlement_reason` once settled.
+    ///
+    /// `final_value` is the value core last recorded on the token, which core
+    /// sets to the actual payout when it settles or exits the commitment.
+    pub fn get_public_metadata(e: Env, token_id: u32) -> Result<PublicMetadata, ContractError> {
+        let nft = Self::get_metadata(e, token_id)?;
+        let settled = nft.settlement_reason != SettlementReason::Unsettled;
+        Ok(PublicMetadata {
+            token_id: nft.token_id,
+            owner: nft.owner,
+            metadata: nft.metadata,
+            is_active: nft.is_active,
+            early_exit_penalty: nft.early_exit_penalty,
+            final_value: settled.then_some(nft.current_value),
+            settlement_reason: nft.settlement_reason,
+        })
     }
 
     /// Get owner of NFT
     pub fn owner_of(e: Env, token_id: u32) -> Result<Address, ContractError> {
-        let nft: CommitmentNFT = e
-            .storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or(ContractError::TokenNotFound)?;
+        let nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
 
         Ok(nft.owner)
     }
@@ -511,24 +818,15 @@ impl CommitmentNFTContract {
         from.require_auth();
 
         // Validate 'to' address is not the same as 'from' (prevent self-transfer)
-        if to == from {
+        Validation::require_distinct(&e, &from, &to, ContractError::TransferToZeroAddress);
+
+        // Get the NFT
+        let mut nft: CommitmentNFT = read_nft(&e, token_id).ok_or_else(|| {
             e.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
-            return Err(ContractError::TransferToZeroAddress);
-        }
-
-        // Get the NFT
-        let mut nft: CommitmentNFT = e
-            .storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or_else(|| {
-                e.storage()
-                    .instance()
-                    .set(&DataKey::ReentrancyGuard, &false);
-                ContractError::TokenNotFound
-            })?;
+            ContractError::TokenNotFound
+        })?;
 
         // Verify ownership
         if nft.owner != from {
@@ -538,6 +836,14 @@ impl CommitmentNFTContract {
             return Err(ContractError::NotOwner);
         }
 
+        // Frozen tokens (e.g. under dispute) cannot move, even after settlement
+        if is_frozen(&e, token_id) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(ContractError::TokenFrozen);
+        }
+
         // Check if NFT is active (locked) - active commitments cannot be transferred
         if nft.is_active {
             e.storage()
@@ -546,6 +852,13 @@ impl CommitmentNFTContract {
             return Err(ContractError::NFTLocked);
         }
 
+        if owner_at_cap(&e, &to) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(ContractError::OwnerCapExceeded);
+        }
+
         // EFFECTS: Update state
         // Update owner
         nft.owner = to.clone();
@@ -577,27 +890,8 @@ impl CommitmentNFTContract {
             .set(&DataKey::OwnerBalance(to.clone()), &(to_balance + 1));
 
         // Update owner tokens lists
-        let mut from_tokens: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(from.clone()))
-            .unwrap_or(Vec::new(&e));
-        if let Some(index) = from_tokens.iter().position(|id| id == token_id) {
-            from_tokens.remove(index as u32);
-        }
-        e.storage()
-            .persistent()
-            .set(&DataKey::OwnerTokens(from.clone()), &from_tokens);
-
-        let mut to_tokens: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(to.clone()))
-            .unwrap_or(Vec::new(&e));
-        to_tokens.push_back(token_id);
-        e.storage()
-            .persistent()
-            .set(&DataKey::OwnerTokens(to.clone()), &to_tokens);
+        Storage::remove_from_index(&e, &DataKey::OwnerTokens(from.clone()), token_id);
+        Storage::push_to_index(&e, &DataKey::OwnerTokens(to.clone()), token_id);
 
         // Clear reentrancy guard
         e.storage()
@@ -615,11 +909,7 @@ impl CommitmentNFTContract {
 
     /// Check if NFT is active
     pub fn is_active(e: Env, token_id: u32) -> Result<bool, ContractError> {
-        let nft: CommitmentNFT = e
-            .storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or(ContractError::TokenNotFound)?;
+        let nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
 
         Ok(nft.is_active)
     }
@@ -632,6 +922,14 @@ impl CommitmentNFTContract {
             .unwrap_or(0)
     }
 
+    /// Get the number of tokens still active.
+    ///
+    /// Unlike `total_supply` (ever minted), this drops when a token is
+    /// settled or its commitment is marked violated.
+    pub fn active_supply(e: Env) -> u32 {
+        read_active_supply(&e)
+    }
+
     /// Get NFT count for a specific owner
     pub fn balance_of(e: Env, owner: Address) -> u32 {
         e.storage()
@@ -651,11 +949,7 @@ impl CommitmentNFTContract {
         let mut nfts: Vec<CommitmentNFT> = Vec::new(&e);
 
         for token_id in token_ids.iter() {
-            if let Some(nft) = e
-                .storage()
-                .persistent()
-                .get::<DataKey, CommitmentNFT>(&DataKey::NFT(token_id))
-            {
+            if let Some(nft) = read_nft(&e, token_id) {
                 nfts.push_back(nft);
             }
         }
@@ -665,20 +959,12 @@ impl CommitmentNFTContract {
 
     /// Get all NFTs owned by a specific address
     pub fn get_nfts_by_owner(e: Env, owner: Address) -> Vec<CommitmentNFT> {
-        let token_ids: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(owner))
-            .unwrap_or(Vec::new(&e));
+        let token_ids: Vec<u32> = Storage::read_index(&e, &DataKey::OwnerTokens(owner));
 
         let mut owned_nfts: Vec<CommitmentNFT> = Vec::new(&e);
 
         for token_id in token_ids.iter() {
-            if let Some(nft) = e
-                .storage()
-                .persistent()
-                .get::<DataKey, CommitmentNFT>(&DataKey::NFT(token_id))
-            {
+            if let Some(nft) = read_nft(&e, token_id) {
                 owned_nfts.push_back(nft);
             }
         }
@@ -697,6 +983,26 @@ impl CommitmentNFTContract {
     /// Uses checks-effects-interactions pattern. This function only writes to storage
     /// and doesn't make external calls, but still protected for consistency.
     pub fn settle(e: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        Self::settle_with_reason(e, caller, token_id, SettlementReason::Matured)
+    }
+
+    /// Mark NFT as settled and record why, as decided by core.
+    ///
+    /// `Matured` still requires the token to have expired; `Violated` and
+    /// `EarlyExit` end a commitment before expiry, so core's own checks are
+    /// trusted and only core may pass them. `Unsettled` is rejected with
+    /// `InvalidSettlementReason`. The admin may settle `Matured` tokens, as
+    /// with `settle`.
+    pub fn settle_with_reason(
+        e: Env,
+        caller: Address,
+        token_id: u32,
+        reason: SettlementReason,
+    ) -> Result<(), ContractError> {
+        if reason == SettlementReason::Unsettled {
+            return Err(ContractError::InvalidSettlementReason);
+        }
+
         // Reentrancy protection
         let guard: bool = e
             .storage()
@@ -730,7 +1036,9 @@ impl CommitmentNFTContract {
             .instance()
             .get(&DataKey::Admin)
             .ok_or(ContractError::NotInitialized)?;
-        if caller != core_contract && caller != admin {
+        // Only core knows whether a commitment really ended early, so the
+        // admin may settle matured tokens only
+        if caller != core_contract && (caller != admin || reason != SettlementReason::Matured) {
             e.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
@@ -739,16 +1047,12 @@ impl CommitmentNFTContract {
         caller.require_auth();
 
         // CHECKS: Get the NFT
-        let mut nft: CommitmentNFT = e
-            .storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or_else(|| {
-                e.storage()
-                    .instance()
-                    .set(&DataKey::ReentrancyGuard, &false);
-                ContractError::TokenNotFound
-            })?;
+        let mut nft: CommitmentNFT = read_nft(&e, token_id).ok_or_else(|| {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            ContractError::TokenNotFound
+        })?;
 
         // Check if already settled
         if !nft.is_active {
@@ -760,7 +1064,7 @@ impl CommitmentNFTContract {
 
         // Verify the commitment has expired
         let current_time = e.ledger().timestamp();
-        if current_time < nft.metadata.expires_at {
+        if reason == SettlementReason::Matured && current_time < nft.metadata.expires_at {
             e.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
@@ -770,7 +1074,9 @@ impl CommitmentNFTContract {
         // EFFECTS: Update state
         // Mark as inactive (settled)
         nft.is_active = false;
+        nft.settlement_reason = reason;
         e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+        track_active_supply(&e, false);
 
         // Clear reentrancy guard
         e.storage()
@@ -784,18 +1090,175 @@ impl CommitmentNFTContract {
         Ok(())
     }
 
-    /// Check if an NFT has expired (based on time)
-    pub fn is_expired(e: Env, token_id: u32) -> Result<bool, ContractError> {
-        let nft: CommitmentNFT = e
+    /// Record the latest commitment value on the NFT.
+    ///
+    /// Only the configured core contract may call this; it mirrors the value
+    /// passed to core's `update_value` so NFT holders see the live position.
+    pub fn update_current_value(
+        e: Env,
+        caller: Address,
+        token_id: u32,
+        value: i128,
+    ) -> Result<(), ContractError> {
+        let core_contract: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::CoreContract)
+            .ok_or(ContractError::NotInitialized)?;
+        if caller != core_contract {
+            return Err(ContractError::NotAuthorized);
+        }
+        caller.require_auth();
+
+        let mut nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
+        if nft.metadata_frozen {
+            return Err(ContractError::MetadataFrozen);
+        }
+
+        let old_value = nft.current_value;
+        nft.current_value = value;
+        e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+
+        e.events().publish(
+            (symbol_short!("ValUpd"), token_id),
+            (old_value, value, e.ledger().timestamp()),
+        );
+
+        Ok(())
+    }
+
+    /// Flag a token whose commitment was marked violated in core.
+    ///
+    /// Only the configured core contract may call this. The token is
+    /// deactivated so holders and secondary buyers see the real state.
+    pub fn mark_violated(e: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        let core_contract: Address = e
             .storage()
+            .instance()
+            .get(&DataKey::CoreContract)
+            .ok_or(ContractError::NotInitialized)?;
+        if caller != core_contract {
+            return Err(ContractError::NotAuthorized);
+        }
+        caller.require_auth();
+
+        let mut nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
+
+        if nft.is_active {
+            nft.is_active = false;
+            nft.settlement_reason = SettlementReason::Violated;
+            e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+            track_active_supply(&e, false);
+        }
+        e.storage()
+            .persistent()
+            .set(&DataKey::Violated(token_id), &true);
+
+        e.events().publish(
+            (symbol_short!("Violated"), token_id),
+            e.ledger().timestamp(),
+        );
+
+        Ok(())
+    }
+
+    /// Check whether a token's commitment was marked violated
+    pub fn is_violated(e: Env, token_id: u32) -> bool {
+        e.storage()
             .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or(ContractError::TokenNotFound)?;
+            .get(&DataKey::Violated(token_id))
+            .unwrap_or(false)
+    }
+
+    /// Settle every expired, active token in `token_ids` (commitment_core only).
+    ///
+    /// Expiry alone does not mean the backing commitment was settled in core,
+    /// so only core may unlock tokens in bulk (via its own `settle_batch`);
+    /// the admin cannot.
+    ///
+    /// Missing, already-settled and unexpired tokens are skipped rather than
+    /// failing the whole call. Returns the IDs that were actually settled.
+    pub fn settle_batch(
+        e: Env,
+        caller: Address,
+        token_ids: Vec<u32>,
+    ) -> Result<Vec<u32>, ContractError> {
+        let guard: bool = e
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(ContractError::ReentrancyDetected);
+        }
+        EmergencyControl::require_not_emergency(&e);
+        Pausable::require_not_paused(&e);
+
+        let core_contract: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::CoreContract)
+            .ok_or(ContractError::NotInitialized)?;
+        if caller != core_contract {
+            return Err(ContractError::NotAuthorized);
+        }
+        caller.require_auth();
+
+        BatchProcessor::enforce_batch_limits(
+            &e,
+            token_ids.len(),
+            Some(String::from_str(&e, "commitment_nft")),
+        )
+        .map_err(|_| ContractError::InvalidBatchSize)?;
+
+        e.storage().instance().set(&DataKey::ReentrancyGuard, &true);
+
+        let now = e.ledger().timestamp();
+        let mut settled = Vec::new(&e);
+        for token_id in token_ids.iter() {
+            let key = DataKey::NFT(token_id);
+            let Some(mut nft) = read_nft(&e, token_id) else {
+                continue;
+            };
+            if !nft.is_active || now < nft.metadata.expires_at {
+                continue;
+            }
+
+            nft.is_active = false;
+            nft.settlement_reason = SettlementReason::Matured;
+            e.storage().persistent().set(&key, &nft);
+            track_active_supply(&e, false);
+            e.events().publish((symbol_short!("Settle"), token_id), now);
+            settled.push_back(token_id);
+        }
+
+        e.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        Ok(settled)
+    }
+
+    /// Check if an NFT has expired (based on time)
+    pub fn is_expired(e: Env, token_id: u32) -> Result<bool, ContractError> {
+        let nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
 
         let current_time = e.ledger().timestamp();
         Ok(current_time >= nft.metadata.expires_at)
     }
 
+    /// Get `(is_active, is_expired, expires_at, now)` for a token in one call
+    pub fn get_token_status(
+        e: Env,
+        token_id: u32,
+    ) -> Result<(bool, bool, u64, u64), ContractError> {
+        let nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
+
+        let now = e.ledger().timestamp();
+        let expires_at = nft.metadata.expires_at;
+        Ok((nft.is_active, now >= expires_at, expires_at, now))
+    }
+
     /// Check if a token exists
     pub fn token_exists(e: Env, token_id: u32) -> bool {
         e.storage().persistent().has(&DataKey::NFT(token_id))
@@ -817,6 +1280,75 @@ impl CommitmentNFTContract {
         EmergencyControl::set_emergency_mode(&e, enabled);
         Ok(())
     }
+
+    /// Freeze a token so it cannot be transferred (admin only).
+    pub fn freeze(e: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        if !e.storage().persistent().has(&DataKey::NFT(token_id)) {
+            return Err(ContractError::TokenNotFound);
+        }
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::Frozen(token_id), &true);
+
+        e.events()
+            .publish((symbol_short!("Frozen"), token_id), e.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Lift a freeze placed with `freeze` (admin only).
+    pub fn unfreeze(e: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        if !e.storage().persistent().has(&DataKey::NFT(token_id)) {
+            return Err(ContractError::TokenNotFound);
+        }
+
+        e.storage().persistent().remove(&DataKey::Frozen(token_id));
+
+        e.events().publish(
+            (symbol_short!("Unfrozen"), token_id),
+            e.ledger().timestamp(),
+        );
+        Ok(())
+    }
+
+    /// Check whether a token is frozen
+    pub fn is_frozen(e: Env, token_id: u32) -> bool {
+        is_frozen(&e, token_id)
+    }
+
+    /// Permanently freeze a token's metadata (admin or token owner).
+    ///
+    /// Afterwards `update_current_value` is rejected with `MetadataFrozen`, so
+    /// `current_value` stays at its value as of the freeze (core's sync is
+    /// best effort and carries on); settlement still deactivates the token.
+    /// There is no unfreeze.
+    pub fn freeze_metadata(e: Env, caller: Address, token_id: u32) -> Result<(), ContractError> {
+        caller.require_auth();
+        let key = DataKey::NFT(token_id);
+        let mut nft: CommitmentNFT = read_nft(&e, token_id).ok_or(ContractError::TokenNotFound)?;
+        let admin: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        if caller != admin && caller != nft.owner {
+            return Err(ContractError::NotAuthorized);
+        }
+        if nft.metadata_frozen {
+            return Err(ContractError::MetadataFrozen);
+        }
+
+        nft.metadata_frozen = true;
+        e.storage().persistent().set(&key, &nft);
+
+        e.events().publish(
+            (symbol_short!("MetaFrzn"), token_id),
+            (caller, e.ledger().timestamp()),
+        );
+        Ok(())
+    }
 }
 
 fn read_version(e: &Env) -> u32 {
@@ -826,6 +1358,88 @@ fn read_version(e: &Env) -> u32 {
         .unwrap_or(0)
 }
 
+/// Read a stored token, tolerating layouts from before `current_value`,
+/// `settlement_reason` and `metadata_frozen` were added. Missing fields
+/// decode as the initial amount, `Unsettled` (or `Matured` for an inactive
+/// token, the only settlement those layouts had) and not frozen.
+fn read_nft(e: &Env, token_id: u32) -> Option<CommitmentNFT> {
+    let raw = e
+        .storage()
+        .persistent()
+        .get::<_, Map<Symbol, Val>>(&DataKey::NFT(token_id))?;
+    let field = |name: &str| -> Option<Val> { raw.get(Symbol::new(e, name)) };
+    let owner = field("owner").and_then(|v| Address::try_from_val(e, &v).ok())?;
+    let metadata = field("metadata").and_then(|v| CommitmentMetadata::try_from_val(e, &v).ok())?;
+    let is_active = field("is_active").and_then(|v| bool::try_from_val(e, &v).ok())?;
+    let early_exit_penalty =
+        field("early_exit_penalty").and_then(|v| u32::try_from_val(e, &v).ok())?;
+    let current_value = field("current_value")
+        .and_then(|v| i128::try_from_val(e, &v).ok())
+        .unwrap_or(metadata.initial_amount);
+    let settlement_reason = field("settlement_reason")
+        .and_then(|v| SettlementReason::try_from_val(e, &v).ok())
+        .unwrap_or(if is_active {
+            SettlementReason::Unsettled
+        } else {
+            SettlementReason::Matured
+        });
+    let metadata_frozen = field("metadata_frozen")
+        .and_then(|v| bool::try_from_val(e, &v).ok())
+        .unwrap_or(false);
+    Some(CommitmentNFT {
+        owner,
+        token_id,
+        metadata,
+        is_active,
+        early_exit_penalty,
+        current_value,
+        settlement_reason,
+        metadata_frozen,
+    })
+}
+
+fn read_max_supply(e: &Env) -> u32 {
+    e.storage().instance().get(&DataKey::MaxSupply).unwrap_or(0)
+}
+
+fn read_max_per_owner(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::MaxPerOwner)
+        .unwrap_or(0)
+}
+
+/// Whether `owner` already holds `max_per_owner` tokens
+fn owner_at_cap(e: &Env, owner: &Address) -> bool {
+    let cap = read_max_per_owner(e);
+    cap > 0 && CommitmentNFTContract::balance_of(e.clone(), owner.clone()) >= cap
+}
+
+fn read_active_supply(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ActiveSupply)
+        .unwrap_or(0)
+}
+
+/// Count a token becoming active (`true`, on mint) or inactive (`false`).
+fn track_active_supply(e: &Env, minted: bool) {
+    let supply = read_active_supply(e);
+    let supply = if minted {
+        supply.saturating_add(1)
+    } else {
+        supply.saturating_sub(1)
+    };
+    e.storage().instance().set(&DataKey::ActiveSupply, &supply);
+}
+
+fn is_frozen(e: &Env, token_id: u32) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Frozen(token_id))
+        .unwrap_or(false)
+}
+
 fn require_admin(e: &Env, caller: &Address) -> Result<(), ContractError> {
     caller.require_auth();
     let admin: Address = e
@@ -8,9 +8,13 @@
 use shared_utils::Validation;
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
-pub const CURRENT_VERSION: u32 = 1;
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Most consumer contracts notified per asset on `set_price`.
+pub const MAX_SUBSCRIBERS: u32 = 20;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -27,6 +31,16 @@ pub enum OracleError {
     InvalidWasmHash = 9,
     InvalidVersion = 10,
     AlreadyMigrated = 11,
+    NoPendingAdmin = 12,
+    ConfidenceTooWide = 13,
+    ZeroPrice = 14,
+    MathOverflow = 15,
+    TimelockRequired = 16,
+    InvalidTimelockAction = 17,
+    TimelockNotSet = 18,
+    PriceFrozen = 19,
+    TooManySubscribers = 20,
+    InsufficientSources = 21,
 }
 
 #[contracttype]
@@ -35,6 +49,33 @@ pub struct PriceData {
     pub price: i128,
     pub updated_at: u64,
     pub decimals: u32,
+    /// Absolute confidence band around `price` (same decimals). 0 = exact.
+    pub confidence: i128,
+}
+
+/// Inclusive sanity range for an asset's price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceBounds {
+    pub min: i128,
+    pub max: i128,
+}
+
+/// Mirror of the timelock contract's `QueuedAction` for cross-contract decoding.
+/// `action_type` is the raw `ActionType` discriminant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockAction {
+    pub id: u64,
+    pub action_type: u32,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub data: String,
+    pub queued_at: u64,
+    pub executable_at: u64,
+    pub executed: bool,
+    pub cancelled: bool,
 }
 
 #[contracttype]
@@ -43,6 +84,22 @@ pub struct OracleConfig {
     pub max_staleness_seconds: u64,
 }
 
+/// Global settings returned by `get_config`.
+///
+/// Assembled on read from the stored `OracleConfig` and the keys added after
+/// it, so the stored layout never changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfigView {
+    pub max_staleness_seconds: u64,
+    /// Widest confidence band (bps of price) accepted (0 = unchecked)
+    pub max_confidence_bps: u32,
+    /// Max distance (bps of the median) `get_robust_price` keeps (0 = keep all)
+    pub outlier_bps: u32,
+    /// Submissions that must survive trimming in `get_robust_price`
+    pub min_sources: u32,
+}
+
 #[contracttype]
 pub enum DataKey {
     Admin,
@@ -56,6 +113,30 @@ pub enum DataKey {
     OracleConfig,
     /// Contract version
     Version,
+    /// Candidate admin awaiting `accept_admin`
+    PendingAdmin,
+    /// Max confidence band as bps of price accepted by `get_price_valid` (0 = unchecked)
+    MaxConfidenceBps,
+    /// Sanity range `set_price` enforces: asset_address -> PriceBounds
+    PriceBounds(Address),
+    /// Timelock contract that approves whitelist changes
+    Timelock,
+    /// When set, whitelist changes need an executed timelock action
+    TimelockEnforced,
+    /// Timelock action ids already used for a whitelist change
+    ConsumedTimelockAction(u64),
+    /// Emergency freeze: asset_address -> bool (last price held, updates rejected)
+    FrozenPrice(Address),
+    /// Decimals every price for an asset must use: asset_address -> u32
+    AssetDecimals(Address),
+    /// Contracts called back on each price update: asset_address -> Vec<Address>
+    Subscribers(Address),
+    /// Latest price from each oracle: asset_address -> Map<oracle, PriceData>
+    Submissions(Address),
+    /// Max distance (bps of the median) `get_robust_price` keeps (0 = keep all)
+    OutlierBps,
+    /// Submissions that must survive trimming in `get_robust_price`
+    MinSources,
 }
 
 fn read_admin(e: &Env) -> Address {
@@ -121,9 +202,9 @@ fn write_config(e: &Env, config: &OracleConfig) {
 }
 
 fn set_max_staleness_internal(e: &Env, seconds: u64) {
-    let config = OracleConfig {
-        max_staleness_seconds: seconds,
-    };
+    // Read-modify-write so other config fields survive
+    let mut config = read_config(e);
+    config.max_staleness_seconds = seconds;
     write_config(e, &config);
     if e.storage().instance().has(&DataKey::MaxStalenessSeconds) {
         e.storage()
@@ -132,6 +213,116 @@ fn set_max_staleness_internal(e: &Env, seconds: u64) {
     }
 }
 
+/// Read a stored price, tolerating the pre-v2 layout without `confidence`
+/// (decoded as a band of 0).
+fn read_price(e: &Env, asset: &Address) -> Option<PriceData> {
+    let raw = e
+        .storage()
+        .instance()
+        .get::<_, Map<Symbol, Val>>(&DataKey::Price(asset.clone()))?;
+    let field = |name: &str| -> Option<Val> { raw.get(Symbol::new(e, name)) };
+    let price = field("price").and_then(|v| i128::try_from_val(e, &v).ok())?;
+    let updated_at = field("updated_at").and_then(|v| u64::try_from_val(e, &v).ok())?;
+    let decimals = field("decimals").and_then(|v| u32::try_from_val(e, &v).ok())?;
+    let confidence = field("confidence")
+        .and_then(|v| i128::try_from_val(e, &v).ok())
+        .unwrap_or(0);
+    Some(PriceData {
+        price,
+        updated_at,
+        decimals,
+        confidence,
+    })
+}
+
+fn read_submissions(e: &Env, asset: &Address) -> Map<Address, PriceData> {
+    e.storage()
+        .instance()
+        .get::<_, Map<Address, PriceData>>(&DataKey::Submissions(asset.clone()))
+        .unwrap_or(Map::new(e))
+}
+
+/// Sort prices ascending (insertion sort; one entry per oracle).
+fn sort_prices(prices: &mut Vec<i128>) {
+    for i in 1..prices.len() {
+        let value = prices.get_unchecked(i);
+        let mut j = i;
+        while j > 0 && prices.get_unchecked(j - 1) > value {
+            prices.set(j, prices.get_unchecked(j - 1));
+            j -= 1;
+        }
+        prices.set(j, value);
+    }
+}
+
+/// Median of non-empty ascending prices; mean of the middle two for an
+/// even count.
+fn median_of_sorted(prices: &Vec<i128>) -> i128 {
+    let mid = prices.len() / 2;
+    let upper = prices.get_unchecked(mid);
+    if prices.len() % 2 == 1 {
+        return upper;
+    }
+    let lower = prices.get_unchecked(mid - 1);
+    lower + (upper - lower) / 2
+}
+
+fn read_subscribers(e: &Env, asset: &Address) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKey::Subscribers(asset.clone()))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Call `on_price_update(asset, price)` on every subscriber of `asset`.
+/// Best effort: a subscriber that fails or lacks the callback is skipped.
+fn notify_subscribers(e: &Env, asset: &Address, price: i128) {
+    let callback = Symbol::new(e, "on_price_update");
+    for subscriber in read_subscribers(e, asset).iter() {
+        let _ = e.try_invoke_contract::<(), soroban_sdk::Error>(
+            &subscriber,
+            &callback,
+            (asset.clone(), price).into_val(e),
+        );
+    }
+}
+
+fn is_price_frozen(e: &Env, asset: &Address) -> bool {
+    e.storage()
+        .instance()
+        .has(&DataKey::FrozenPrice(asset.clone()))
+}
+
+fn read_max_confidence_bps(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::MaxConfidenceBps)
+        .unwrap_or(0)
+}
+
+/// Fetch a price and apply the staleness rules shared by the validated getters.
+/// A frozen price is never stale: it is held deliberately until unfrozen.
+fn read_fresh_price(
+    e: &Env,
+    asset: &Address,
+    max_staleness_override: Option<u64>,
+) -> Result<PriceData, OracleError> {
+    let data = read_price(e, asset).ok_or(OracleError::PriceNotFound)?;
+    if data.price < 0 {
+        return Err(OracleError::InvalidPrice);
+    }
+    if is_price_frozen(e, asset) {
+        return Ok(data);
+    }
+    let max_staleness =
+        max_staleness_override.unwrap_or_else(|| read_config(e).max_staleness_seconds);
+    let now = e.ledger().timestamp();
+    if now < data.updated_at || now - data.updated_at > max_staleness {
+        return Err(OracleError::StalePrice);
+    }
+    Ok(data)
+}
+
 fn require_admin_result(e: &Env, caller: &Address) -> Result<(), OracleError> {
     caller.require_auth();
     let admin = e
@@ -145,6 +336,60 @@ fn require_admin_result(e: &Env, caller: &Address) -> Result<(), OracleError> {
     Ok(())
 }
 
+fn is_timelock_enforced(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<_, bool>(&DataKey::TimelockEnforced)
+        .unwrap_or(false)
+}
+
+/// Require `action_id` to be an executed, uncancelled and unused timelock
+/// action calling `function` on this contract with `arg` among its args, and
+/// mark it consumed. Binding the target to this contract keeps an approval
+/// from replaying on another deployment that shares the timelock.
+fn consume_timelock_action(
+    e: &Env,
+    action_id: u64,
+    function: &str,
+    arg: &Address,
+) -> Result<(), OracleError> {
+    let timelock = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Timelock)
+        .ok_or(OracleError::TimelockNotSet)?;
+    let consumed_key = DataKey::ConsumedTimelockAction(action_id);
+    if e.storage().instance().has(&consumed_key) {
+        return Err(OracleError::InvalidTimelockAction);
+    }
+
+    let mut args = Vec::new(e);
+    args.push_back(action_id.into_val(e));
+    let action = match e.try_invoke_contract::<TimelockAction, soroban_sdk::Error>(
+        &timelock,
+        &Symbol::new(e, "get_action"),
+        args,
+    ) {
+        Ok(Ok(action)) => action,
+        _ => return Err(OracleError::InvalidTimelockAction),
+    };
+    let arg_bound = action
+        .args
+        .iter()
+        .any(|val| Address::try_from_val(e, &val).is_ok_and(|a| a == *arg));
+    if !action.executed
+        || action.cancelled
+        || action.target != e.current_contract_address()
+        || action.function != Symbol::new(e, function)
+        || !arg_bound
+    {
+        return Err(OracleError::InvalidTimelockAction);
+    }
+
+    e.storage().instance().set(&consumed_key, &true);
+    Ok(())
+}
+
 fn require_valid_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) -> Result<(), OracleError> {
     let zero = BytesN::from_array(e, &[0; 32]);
     if *wasm_hash == zero {
@@ -174,8 +419,14 @@ impl PriceOracleContract {
     }
 
     /// Add an address to the oracle whitelist (can push prices). Admin only.
+    ///
+    /// Errors with `TimelockRequired` once timelock enforcement is enabled;
+    /// use `add_oracle_timelocked` instead.
     pub fn add_oracle(e: Env, caller: Address, oracle_address: Address) -> Result<(), OracleError> {
         require_admin(&e, &caller);
+        if is_timelock_enforced(&e) {
+            return Err(OracleError::TimelockRequired);
+        }
         e.storage()
             .instance()
             .set(&DataKey::OracleWhitelist(oracle_address), &true);
@@ -183,83 +434,486 @@ impl PriceOracleContract {
     }
 
     /// Remove an address from the whitelist. Admin only.
+    ///
+    /// Errors with `TimelockRequired` once timelock enforcement is enabled;
+    /// use `remove_oracle_timelocked` instead.
     pub fn remove_oracle(
         e: Env,
         caller: Address,
         oracle_address: Address,
     ) -> Result<(), OracleError> {
         require_admin(&e, &caller);
+        if is_timelock_enforced(&e) {
+            return Err(OracleError::TimelockRequired);
+        }
+        e.storage()
+            .instance()
+            .remove(&DataKey::OracleWhitelist(oracle_address));
+        Ok(())
+    }
+
+    /// Add an oracle approved through the timelock. Admin only.
+    ///
+    /// `action_id` must be an executed timelock action calling `add_oracle`
+    /// on this contract with `oracle_address` as an arg; each action is
+    /// usable once.
+    pub fn add_oracle_timelocked(
+        e: Env,
+        caller: Address,
+        oracle_address: Address,
+        action_id: u64,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        consume_timelock_action(&e, action_id, "add_oracle", &oracle_address)?;
+        e.storage()
+            .instance()
+            .set(&DataKey::OracleWhitelist(oracle_address), &true);
+        Ok(())
+    }
+
+    /// Remove an oracle approved through the timelock. Admin only.
+    ///
+    /// `action_id` must be an executed timelock action calling
+    /// `remove_oracle` on this contract with `oracle_address` as an arg.
+    pub fn remove_oracle_timelocked(
+        e: Env,
+        caller: Address,
+        oracle_address: Address,
+        action_id: u64,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        consume_timelock_action(&e, action_id, "remove_oracle", &oracle_address)?;
         e.storage()
             .instance()
             .remove(&DataKey::OracleWhitelist(oracle_address));
         Ok(())
     }
 
+    /// Configure the timelock contract that approves whitelist changes. Admin only.
+    ///
+    /// Can only be set once so the admin cannot swap it out to bypass the delay.
+    pub fn set_timelock(e: Env, caller: Address, timelock: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if e.storage().instance().has(&DataKey::Timelock) {
+            return Err(OracleError::Unauthorized);
+        }
+        e.storage().instance().set(&DataKey::Timelock, &timelock);
+        Ok(())
+    }
+
+    /// Get the timelock contract approving whitelist changes, if any.
+    pub fn get_timelock(e: Env) -> Option<Address> {
+        e.storage().instance().get::<_, Address>(&DataKey::Timelock)
+    }
+
+    /// Require timelock approval for all further whitelist changes. Admin only.
+    ///
+    /// One-way: otherwise the admin could switch it off and add an oracle
+    /// instantly. Errors with `TimelockNotSet` if no timelock is configured.
+    pub fn enable_timelock_enforcement(e: Env, caller: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if !e.storage().instance().has(&DataKey::Timelock) {
+            return Err(OracleError::TimelockNotSet);
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::TimelockEnforced, &true);
+        Ok(())
+    }
+
+    /// Whether whitelist changes require an executed timelock action.
+    pub fn is_timelock_enforced(e: Env) -> bool {
+        is_timelock_enforced(&e)
+    }
+
     /// Check if an address is whitelisted.
     pub fn is_oracle_whitelisted(e: Env, address: Address) -> bool {
         is_whitelisted(&e, &address)
     }
 
-    /// Set price for an asset. Caller must be whitelisted. Validates price >= 0.
+    /// Set price for an asset. Caller must be whitelisted. Validates price >= 0
+    /// and confidence >= 0, and that `decimals` matches the asset's pinned
+    /// decimals (`InvalidPrice` otherwise).
     pub fn set_price(
         e: Env,
         caller: Address,
         asset: Address,
         price: i128,
         decimals: u32,
+        confidence: i128,
     ) -> Result<(), OracleError> {
         require_whitelisted(&e, &caller);
+        if is_price_frozen(&e, &asset) {
+            return Err(OracleError::PriceFrozen);
+        }
         Validation::require_non_negative(price);
+        Validation::require_non_negative(confidence);
+        if let Some(bounds) = Self::get_price_bounds(e.clone(), asset.clone()) {
+            if price < bounds.min || price > bounds.max {
+                return Err(OracleError::InvalidPrice);
+            }
+        }
+        // The first price pins the asset's decimals; later feeds must match
+        let decimals_key = DataKey::AssetDecimals(asset.clone());
+        match e.storage().instance().get::<_, u32>(&decimals_key) {
+            Some(pinned) if pinned != decimals => return Err(OracleError::InvalidPrice),
+            Some(_) => {}
+            None => e.storage().instance().set(&decimals_key, &decimals),
+        }
         let updated_at = e.ledger().timestamp();
         let data = PriceData {
             price,
             updated_at,
             decimals,
+            confidence,
         };
         e.storage()
             .instance()
             .set(&DataKey::Price(asset.clone()), &data);
+        let mut submissions = read_submissions(&e, &asset);
+        submissions.set(caller, data);
+        e.storage()
+            .instance()
+            .set(&DataKey::Submissions(asset.clone()), &submissions);
         e.events().publish(
-            (symbol_short!("PriceSet"), asset),
+            (symbol_short!("PriceSet"), asset.clone()),
             (price, updated_at, decimals),
         );
+        notify_subscribers(&e, &asset, price);
         Ok(())
     }
 
-    /// Get last price and timestamp for an asset. Returns (0, 0, 0) if not set.
-    pub fn get_price(e: Env, asset: Address) -> PriceData {
+    /// Register `subscriber` to receive `on_price_update(asset, price)` after
+    /// each `set_price` for `asset`. Admin only. Subscribing twice is a no-op;
+    /// at most `MAX_SUBSCRIBERS` per asset (`TooManySubscribers` otherwise).
+    ///
+    /// Callbacks run inside `set_price` and share its budget, so only vetted
+    /// contracts may subscribe. A subscriber cannot call back into the oracle
+    /// from them; read the price from the arguments instead.
+    pub fn subscribe(
+        e: Env,
+        caller: Address,
+        subscriber: Address,
+        asset: Address,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        let mut subscribers = read_subscribers(&e, &asset);
+        if subscribers.contains(&subscriber) {
+            return Ok(());
+        }
+        if subscribers.len() >= MAX_SUBSCRIBERS {
+            return Err(OracleError::TooManySubscribers);
+        }
+        subscribers.push_back(subscriber.clone());
         e.storage()
             .instance()
-            .get::<_, PriceData>(&DataKey::Price(asset))
-            .unwrap_or(PriceData {
-                price: 0,
-                updated_at: 0,
-                decimals: 0,
-            })
+            .set(&DataKey::Subscribers(asset.clone()), &subscribers);
+        e.events()
+            .publish((symbol_short!("Subscribe"), asset), subscriber);
+        Ok(())
+    }
+
+    /// Stop price update callbacks to `subscriber` for `asset`.
+    ///
+    /// `caller` is the subscriber itself or the admin, who can evict a
+    /// subscriber that misbehaves.
+    pub fn unsubscribe(
+        e: Env,
+        caller: Address,
+        subscriber: Address,
+        asset: Address,
+    ) -> Result<(), OracleError> {
+        if caller == subscriber {
+            caller.require_auth();
+        } else {
+            require_admin_result(&e, &caller)?;
+        }
+        let mut subscribers = read_subscribers(&e, &asset);
+        if let Some(index) = subscribers.first_index_of(&subscriber) {
+            subscribers.remove(index);
+            e.storage()
+                .instance()
+                .set(&DataKey::Subscribers(asset.clone()), &subscribers);
+            e.events()
+                .publish((symbol_short!("Unsubscr"), asset), subscriber);
+        }
+        Ok(())
+    }
+
+    /// Get the contracts notified on price updates for `asset`.
+    pub fn get_subscribers(e: Env, asset: Address) -> Vec<Address> {
+        read_subscribers(&e, &asset)
+    }
+
+    /// Hold an asset's price at its last value during extreme volatility. Admin only.
+    ///
+    /// While frozen, `set_price` rejects updates with `PriceFrozen` and the
+    /// validated getters return the held price regardless of its age. Errors
+    /// with `PriceNotFound` if the asset has no price to hold.
+    pub fn freeze_price(e: Env, caller: Address, asset: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if read_price(&e, &asset).is_none() {
+            return Err(OracleError::PriceNotFound);
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::FrozenPrice(asset.clone()), &true);
+        e.events()
+            .publish((symbol_short!("PriceFrz"), asset), caller);
+        Ok(())
+    }
+
+    /// Lift a price freeze so feeds can update the asset again. Admin only.
+    /// The held price then ages normally from its original `updated_at`.
+    pub fn unfreeze_price(e: Env, caller: Address, asset: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        e.storage()
+            .instance()
+            .remove(&DataKey::FrozenPrice(asset.clone()));
+        e.events()
+            .publish((symbol_short!("PriceUnfz"), asset), caller);
+        Ok(())
+    }
+
+    /// Whether an asset's price is frozen.
+    pub fn is_price_frozen(e: Env, asset: Address) -> bool {
+        is_price_frozen(&e, &asset)
+    }
+
+    /// Set the inclusive range `set_price` accepts for an asset. Admin only.
+    ///
+    /// Catches obviously wrong feeds (decode bugs, misplaced decimals),
+    /// including the first price for an asset. Errors with `InvalidPrice`
+    /// if `min` is negative or above `max`.
+    pub fn set_price_bounds(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        min: i128,
+        max: i128,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if min < 0 || min > max {
+            return Err(OracleError::InvalidPrice);
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::PriceBounds(asset), &PriceBounds { min, max });
+        Ok(())
+    }
+
+    /// Change the decimals `set_price` requires for an asset. Admin only.
+    ///
+    /// Without this, the decimals of an asset's first price are kept and a
+    /// feed switching precision is rejected with `InvalidPrice`.
+    pub fn set_asset_decimals(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        decimals: u32,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        e.storage()
+            .instance()
+            .set(&DataKey::AssetDecimals(asset.clone()), &decimals);
+        e.events()
+            .publish((symbol_short!("AssetDec"), asset), decimals);
+        Ok(())
+    }
+
+    /// Get the decimals required for an asset's prices, if pinned.
+    pub fn get_asset_decimals(e: Env, asset: Address) -> Option<u32> {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::AssetDecimals(asset))
+    }
+
+    /// Get the price bounds for an asset, if configured.
+    pub fn get_price_bounds(e: Env, asset: Address) -> Option<PriceBounds> {
+        e.storage()
+            .instance()
+            .get::<_, PriceBounds>(&DataKey::PriceBounds(asset))
+    }
+
+    /// Get last price and timestamp for an asset. Returns (0, 0, 0) if not set.
+    pub fn get_price(e: Env, asset: Address) -> PriceData {
+        read_price(&e, &asset).unwrap_or(PriceData {
+            price: 0,
+            updated_at: 0,
+            decimals: 0,
+            confidence: 0,
+        })
     }
 
     /// Get price if it exists and is not stale; otherwise error.
     /// `max_staleness_override`: if Some(secs), use instead of contract default.
+    /// When `max_confidence_bps` is set, prices whose confidence band exceeds
+    /// that share of the price are rejected with `ConfidenceTooWide`.
     pub fn get_price_valid(
         e: Env,
         asset: Address,
         max_staleness_override: Option<u64>,
     ) -> Result<PriceData, OracleError> {
-        let data = e
+        let data = read_fresh_price(&e, &asset, max_staleness_override)?;
+        let max_bps = read_max_confidence_bps(&e);
+        if max_bps > 0
+            && data.confidence.saturating_mul(10_000) > data.price.saturating_mul(max_bps as i128)
+        {
+            return Err(OracleError::ConfidenceTooWide);
+        }
+        Ok(data)
+    }
+
+    /// Get a fresh price together with its confidence band as `(price, confidence)`.
+    /// Applies the staleness rules of `get_price_valid` but not the band limit,
+    /// so callers can apply their own tolerance.
+    pub fn get_price_with_confidence(
+        e: Env,
+        asset: Address,
+        max_staleness_override: Option<u64>,
+    ) -> Result<(i128, i128), OracleError> {
+        let data = read_fresh_price(&e, &asset, max_staleness_override)?;
+        Ok((data.price, data.confidence))
+    }
+
+    /// Get the inverse of an asset's price (e.g. USD/ASSET from ASSET/USD)
+    /// scaled to `result_decimals`: `10^(decimals + result_decimals) / price`.
+    ///
+    /// Uses the same freshness rules as `get_price_valid`. Errors with
+    /// `ZeroPrice` when there is nothing to invert and `MathOverflow` when
+    /// the scale factor does not fit in an i128.
+    pub fn get_inverse_price(
+        e: Env,
+        asset: Address,
+        result_decimals: u32,
+    ) -> Result<i128, OracleError> {
+        let data = Self::get_price_valid(e, asset, None)?;
+        if data.price == 0 {
+            return Err(OracleError::ZeroPrice);
+        }
+        let exponent = data
+            .decimals
+            .checked_add(result_decimals)
+            .ok_or(OracleError::MathOverflow)?;
+        let scale = 10i128
+            .checked_pow(exponent)
+            .ok_or(OracleError::MathOverflow)?;
+        scale
+            .checked_div(data.price)
+            .ok_or(OracleError::MathOverflow)
+    }
+
+    /// Set the widest confidence band (bps of price) `get_price_valid` accepts.
+    /// 0 disables the check. Admin only.
+    pub fn set_max_confidence_bps(e: Env, caller: Address, bps: u32) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        e.storage().instance().set(&DataKey::MaxConfidenceBps, &bps);
+        Ok(())
+    }
+
+    /// Get the confidence band limit in bps (0 = unchecked).
+    pub fn get_max_confidence_bps(e: Env) -> u32 {
+        read_max_confidence_bps(&e)
+    }
+
+    /// Configure `get_robust_price` (admin only): submissions further than
+    /// `outlier_bps` from the median are discarded (0 keeps all), and at
+    /// least `min_sources` must remain.
+    pub fn set_outlier_config(
+        e: Env,
+        caller: Address,
+        outlier_bps: u32,
+        min_sources: u32,
+    ) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if outlier_bps > 10_000 || min_sources == 0 {
+            return Err(OracleError::InvalidPrice);
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::OutlierBps, &outlier_bps);
+        e.storage()
+            .instance()
+            .set(&DataKey::MinSources, &min_sources);
+        Ok(())
+    }
+
+    /// Get `(outlier_bps, min_sources)` used by `get_robust_price`.
+    pub fn get_outlier_config(e: Env) -> (u32, u32) {
+        let outlier_bps = e
             .storage()
             .instance()
-            .get::<_, PriceData>(&DataKey::Price(asset))
-            .ok_or(OracleError::PriceNotFound)?;
-        if data.price < 0 {
-            return Err(OracleError::InvalidPrice);
+            .get::<_, u32>(&DataKey::OutlierBps)
+            .unwrap_or(0);
+        let min_sources = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::MinSources)
+            .unwrap_or(1);
+        (outlier_bps, min_sources)
+    }
+
+    /// Aggregate the latest fresh submission of every whitelisted oracle.
+    ///
+    /// Submissions more than `outlier_bps` from their median are discarded
+    /// and the rest averaged; fewer than `min_sources` survivors is
+    /// `InsufficientSources`. `updated_at` is the oldest survivor's and
+    /// `confidence` half their spread. A frozen asset returns its held price.
+    pub fn get_robust_price(e: Env, asset: Address) -> Result<PriceData, OracleError> {
+        if is_price_frozen(&e, &asset) {
+            return read_fresh_price(&e, &asset, None);
         }
-        let max_staleness =
-            max_staleness_override.unwrap_or_else(|| read_config(&e).max_staleness_seconds);
+        let (outlier_bps, min_sources) = Self::get_outlier_config(e.clone());
+        let max_staleness = read_config(&e).max_staleness_seconds;
         let now = e.ledger().timestamp();
-        if now < data.updated_at || now - data.updated_at > max_staleness {
-            return Err(OracleError::StalePrice);
+
+        let mut fresh: Vec<PriceData> = Vec::new(&e);
+        let mut prices: Vec<i128> = Vec::new(&e);
+        for (oracle, data) in read_submissions(&e, &asset).iter() {
+            if !is_whitelisted(&e, &oracle)
+                || now < data.updated_at
+                || now - data.updated_at > max_staleness
+            {
+                continue;
+            }
+            prices.push_back(data.price);
+            fresh.push_back(data);
         }
-        Ok(data)
+        if prices.is_empty() {
+            return Err(OracleError::InsufficientSources);
+        }
+        sort_prices(&mut prices);
+        let median = median_of_sorted(&prices);
+
+        let mut count: u32 = 0;
+        let mut sum: i128 = 0;
+        let mut lowest = i128::MAX;
+        let mut highest = i128::MIN;
+        let mut oldest = u64::MAX;
+        let mut decimals = 0;
+        for data in fresh.iter() {
+            let distance = (data.price - median).abs().saturating_mul(10_000);
+            if outlier_bps > 0 && distance > median.saturating_mul(outlier_bps as i128) {
+                continue;
+            }
+            count += 1;
+            sum = sum
+                .checked_add(data.price)
+                .ok_or(OracleError::MathOverflow)?;
+            lowest = lowest.min(data.price);
+            highest = highest.max(data.price);
+            oldest = oldest.min(data.updated_at);
+            decimals = data.decimals;
+        }
+        if count < min_sources {
+            return Err(OracleError::InsufficientSources);
+        }
+        Ok(PriceData {
+            price: sum / count as i128,
+            updated_at: oldest,
+            decimals,
+            confidence: (highest - lowest) / 2,
+        })
     }
 
     /// Set default max staleness (seconds). Admin only.
@@ -274,6 +928,27 @@ impl PriceOracleContract {
         read_config(&e).max_staleness_seconds
     }
 
+    /// Get the full global oracle configuration. Per-asset settings are
+    /// reported by `get_asset_config`.
+    pub fn get_config(e: Env) -> OracleConfigView {
+        let (outlier_bps, min_sources) = Self::get_outlier_config(e.clone());
+        OracleConfigView {
+            max_staleness_seconds: read_config(&e).max_staleness_seconds,
+            max_confidence_bps: read_max_confidence_bps(&e),
+            outlier_bps,
+            min_sources,
+        }
+    }
+
+    /// Get `(decimals, price bounds)` configured for an asset, each `None`
+    /// when unset.
+    pub fn get_asset_config(e: Env, asset: Address) -> (Option<u32>, Option<PriceBounds>) {
+        (
+            Self::get_asset_decimals(e.clone(), asset.clone()),
+            Self::get_price_bounds(e, asset),
+        )
+    }
+
     /// Get admin address.
     pub fn get_admin(e: Env) -> Address {
         read_admin(&e)
@@ -291,6 +966,53 @@ impl PriceOracleContract {
         Ok(())
     }
 
+    /// Propose a new admin (admin-only). Takes effect on `accept_admin`.
+    pub fn propose_admin(e: Env, caller: Address, candidate: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        e.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &candidate);
+        e.events()
+            .publish((symbol_short!("AdminProp"), caller), candidate);
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal. Caller must be the proposed candidate.
+    pub fn accept_admin(e: Env, caller: Address) -> Result<(), OracleError> {
+        caller.require_auth();
+        let candidate = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PendingAdmin)
+            .ok_or(OracleError::NoPendingAdmin)?;
+        if caller != candidate {
+            return Err(OracleError::Unauthorized);
+        }
+        let previous = read_admin(&e);
+        e.storage().instance().set(&DataKey::Admin, &candidate);
+        e.storage().instance().remove(&DataKey::PendingAdmin);
+        e.events()
+            .publish((symbol_short!("AdminChg"), previous), candidate);
+        Ok(())
+    }
+
+    /// Cancel a pending admin proposal (admin-only).
+    pub fn cancel_admin_proposal(e: Env, caller: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        if !e.storage().instance().has(&DataKey::PendingAdmin) {
+            return Err(OracleError::NoPendingAdmin);
+        }
+        e.storage().instance().remove(&DataKey::PendingAdmin);
+        Ok(())
+    }
+
+    /// Get the pending admin candidate, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PendingAdmin)
+    }
+
     /// Upgrade contract WASM (admin-only).
     pub fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), OracleError> {
         require_admin_result(&e, &caller)?;
@@ -331,6 +1053,10 @@ impl PriceOracleContract {
             e.storage().instance().remove(&DataKey::MaxStalenessSeconds);
         }
 
+        // v1 -> v2: `PriceData` gained `confidence`. Entries written before
+        // the upgrade are decoded with a band of 0 by `read_price` and take
+        // the new layout on their next `set_price`, so nothing is rewritten here.
+
         write_version(&e, CURRENT_VERSION);
         Ok(())
     }
@@ -75,7 +75,7 @@ fn test_set_price_whitelisted() {
         PriceOracleContract::add_oracle(e.clone(), admin.clone(), oracle.clone()).unwrap();
     });
 
-    client.set_price(&oracle, &asset, &1000_00000000, &8);
+    client.set_price(&oracle, &asset, &1000_00000000, &8, &0);
     let data = client.get_price(&asset);
     assert_eq!(data.price, 1000_00000000);
     assert_eq!(data.decimals, 8);
@@ -97,7 +97,7 @@ fn test_set_price_unauthorized_fails() {
         PriceOracleContract::initialize(e.clone(), admin.clone()).unwrap();
     });
 
-    client.set_price(&unauthorized, &asset, &1000, &8);
+    client.set_price(&unauthorized, &asset, &1000, &8, &0);
 }
 
 #[test]
@@ -115,7 +115,7 @@ fn test_get_price_valid_fresh() {
         PriceOracleContract::add_oracle(e.clone(), admin.clone(), oracle.clone()).unwrap();
     });
 
-    client.set_price(&oracle, &asset, &500_0000000, &8);
+    client.set_price(&oracle, &asset, &500_0000000, &8, &0);
     let data = client.get_price_valid(&asset, &None);
     assert_eq!(data.price, 500_0000000);
 }
@@ -152,7 +152,7 @@ fn test_get_price_valid_stale() {
         PriceOracleContract::add_oracle(e.clone(), admin.clone(), oracle.clone()).unwrap();
     });
 
-    client.set_price(&oracle, &asset, &1000, &8);
+    client.set_price(&oracle, &asset, &1000, &8, &0);
 
     // Advance time past max staleness (default 3600)
     e.ledger().with_mut(|li| {
@@ -177,7 +177,7 @@ fn test_get_price_valid_override_staleness() {
         PriceOracleContract::add_oracle(e.clone(), admin.clone(), oracle.clone()).unwrap();
     });
 
-    client.set_price(&oracle, &asset, &1000, &8);
+    client.set_price(&oracle, &asset, &1000, &8, &0);
     e.ledger().with_mut(|li| {
         li.timestamp += 100;
     });
@@ -203,6 +203,77 @@ fn test_set_max_staleness() {
     assert_eq!(client.get_max_staleness(), 7200);
 }
 
+#[test]
+fn test_get_config_reflects_setters() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+
+    e.as_contract(&contract_id, || {
+        PriceOracleContract::initialize(e.clone(), admin.clone()).unwrap();
+    });
+    assert_eq!(
+        client.get_config(),
+        OracleConfigView {
+            max_staleness_seconds: 3600,
+            max_confidence_bps: 0,
+            outlier_bps: 0,
+            min_sources: 1,
+        }
+    );
+
+    client.set_max_staleness(&admin, &900);
+    client.set_max_confidence_bps(&admin, &250);
+    client.set_outlier_config(&admin, &500, &2);
+    assert_eq!(
+        client.get_config(),
+        OracleConfigView {
+            max_staleness_seconds: 900,
+            max_confidence_bps: 250,
+            outlier_bps: 500,
+            min_sources: 2,
+        }
+    );
+    assert_eq!(
+        client.get_config().max_staleness_seconds,
+        client.get_max_staleness()
+    );
+
+    // The stored config keeps its original layout
+    e.as_contract(&contract_id, || {
+        let stored: OracleConfig = e.storage().instance().get(&DataKey::OracleConfig).unwrap();
+        assert_eq!(stored.max_staleness_seconds, 900);
+    });
+}
+
+#[test]
+fn test_get_asset_config_reflects_setters() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(client.get_asset_config(&asset), (None, None));
+
+    client.set_asset_decimals(&admin, &asset, &8);
+    client.set_price_bounds(&admin, &asset, &10, &1_000);
+    assert_eq!(
+        client.get_asset_config(&asset),
+        (
+            Some(8),
+            Some(PriceBounds {
+                min: 10,
+                max: 1_000
+            })
+        )
+    );
+}
+
 #[test]
 fn test_fallback_get_price_returns_default_when_not_set() {
     let e = Env::default();
@@ -236,7 +307,7 @@ fn test_upgrade_and_migrate_preserves_state() {
     });
 
     client.add_oracle(&admin, &oracle);
-    client.set_price(&oracle, &asset, &2_000, &6);
+    client.set_price(&oracle, &asset, &2_000, &6, &0);
 
     // Simulate legacy storage layout (version 0)
     e.as_contract(&contract_id, || {
@@ -327,3 +398,691 @@ fn test_migrate_version_checks_and_replay_safety() {
     });
     assert!(!legacy_exists);
 }
+
+fn setup_oracle(e: &Env) -> (Address, PriceOracleContractClient<'_>) {
+    e.mock_all_auths();
+    let admin = Address::generate(e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(e, &contract_id);
+    client.initialize(&admin);
+    (admin, client)
+}
+
+#[test]
+fn test_propose_accept_admin() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let candidate = Address::generate(&e);
+
+    client.propose_admin(&admin, &candidate);
+    assert_eq!(client.get_pending_admin(), Some(candidate.clone()));
+    // Proposal alone does not move control
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin(&candidate);
+    assert_eq!(client.get_admin(), candidate);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_accept_admin_wrong_address_rejected() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let candidate = Address::generate(&e);
+    let impostor = Address::generate(&e);
+
+    client.propose_admin(&admin, &candidate);
+    assert_eq!(
+        client.try_accept_admin(&impostor),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(candidate));
+}
+
+#[test]
+fn test_cancel_admin_proposal() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let candidate = Address::generate(&e);
+
+    client.propose_admin(&admin, &candidate);
+    client.cancel_admin_proposal(&admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(&candidate),
+        Err(Ok(OracleError::NoPendingAdmin))
+    );
+    assert_eq!(
+        client.try_propose_admin(&candidate, &candidate),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_get_price_valid_accepts_tight_confidence_band() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_max_confidence_bps(&admin, &100);
+    assert_eq!(client.get_max_confidence_bps(), 100);
+
+    // 1% band on a price of 1000 sits exactly on the limit
+    client.set_price(&oracle, &asset, &1000, &8, &10);
+    let data = client.get_price_valid(&asset, &None);
+    assert_eq!(data.price, 1000);
+    assert_eq!(data.confidence, 10);
+    assert_eq!(client.get_price_with_confidence(&asset, &None), (1000, 10));
+}
+
+#[test]
+fn test_get_price_valid_rejects_wide_confidence_band() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price(&oracle, &asset, &1000, &8, &50);
+
+    // Unchecked until an admin sets a limit
+    assert_eq!(client.get_price_valid(&asset, &None).confidence, 50);
+
+    client.set_max_confidence_bps(&admin, &100);
+    assert_eq!(
+        client.try_get_price_valid(&asset, &None),
+        Err(Ok(OracleError::ConfidenceTooWide))
+    );
+    // The raw band is still readable for callers with their own tolerance
+    assert_eq!(client.get_price_with_confidence(&asset, &None), (1000, 50));
+}
+
+#[test]
+fn test_set_max_confidence_bps_admin_only() {
+    let e = Env::default();
+    let (_admin, client) = setup_oracle(&e);
+    let attacker = Address::generate(&e);
+    assert_eq!(
+        client.try_set_max_confidence_bps(&attacker, &100),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
+
+// Pre-v2 price layout, without `confidence`
+#[contracttype]
+#[derive(Clone)]
+struct LegacyPriceData {
+    price: i128,
+    updated_at: u64,
+    decimals: u32,
+}
+
+#[test]
+fn test_migrate_v1_price_defaults_confidence_to_zero() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let asset = Address::generate(&e);
+
+    e.as_contract(&client.address, || {
+        e.storage().instance().set(&DataKey::Version, &1u32);
+        e.storage().instance().set(
+            &DataKey::Price(asset.clone()),
+            &LegacyPriceData {
+                price: 2_000,
+                updated_at: e.ledger().timestamp(),
+                decimals: 6,
+            },
+        );
+    });
+
+    assert_eq!(client.try_migrate(&admin, &1), Ok(Ok(())));
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+
+    let data = client.get_price_valid(&asset, &None);
+    assert_eq!(data.price, 2_000);
+    assert_eq!(data.decimals, 6);
+    assert_eq!(data.confidence, 0);
+}
+
+#[test]
+fn test_get_inverse_price() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+
+    // 2.5 USD per ASSET at 6 decimals -> 0.4 ASSET per USD
+    client.set_price(&oracle, &asset, &2_500_000, &6, &0);
+    assert_eq!(client.get_inverse_price(&asset, &6), 400_000);
+    assert_eq!(client.get_inverse_price(&asset, &8), 40_000_000);
+}
+
+#[test]
+fn test_get_inverse_price_rejects_zero_price() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price(&oracle, &asset, &0, &6, &0);
+
+    assert_eq!(
+        client.try_get_inverse_price(&asset, &6),
+        Err(Ok(OracleError::ZeroPrice))
+    );
+}
+
+#[test]
+fn test_get_inverse_price_scale_overflow() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price(&oracle, &asset, &1_000, &18, &0);
+
+    assert_eq!(
+        client.try_get_inverse_price(&asset, &30),
+        Err(Ok(OracleError::MathOverflow))
+    );
+}
+
+#[test]
+fn test_set_price_within_bounds_accepted() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price_bounds(&admin, &asset, &100, &10_000);
+    assert_eq!(
+        client.get_price_bounds(&asset),
+        Some(PriceBounds {
+            min: 100,
+            max: 10_000
+        })
+    );
+
+    client.set_price(&oracle, &asset, &100, &6, &0);
+    client.set_price(&oracle, &asset, &10_000, &6, &0);
+    assert_eq!(client.get_price(&asset).price, 10_000);
+}
+
+#[test]
+fn test_set_price_below_min_rejected() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price_bounds(&admin, &asset, &100, &10_000);
+
+    assert_eq!(
+        client.try_set_price(&oracle, &asset, &99, &6, &0),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+    assert_eq!(client.get_price(&asset).price, 0);
+}
+
+#[test]
+fn test_set_price_above_max_rejected() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price_bounds(&admin, &asset, &100, &10_000);
+
+    assert_eq!(
+        client.try_set_price(&oracle, &asset, &10_001, &6, &0),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+}
+
+#[test]
+fn test_set_price_bounds_validation() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let asset = Address::generate(&e);
+
+    assert_eq!(
+        client.try_set_price_bounds(&admin, &asset, &500, &100),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_set_price_bounds(&Address::generate(&e), &asset, &1, &100),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(client.get_price_bounds(&asset), None);
+}
+
+#[test]
+fn test_set_price_with_matching_decimals_accepted() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+
+    assert_eq!(client.get_asset_decimals(&asset), None);
+    client.set_price(&oracle, &asset, &100_000000, &6, &0);
+    assert_eq!(client.get_asset_decimals(&asset), Some(6));
+
+    client.set_price(&oracle, &asset, &101_000000, &6, &0);
+    assert_eq!(client.get_price(&asset).price, 101_000000);
+}
+
+#[test]
+fn test_set_price_with_changed_decimals_rejected() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price(&oracle, &asset, &100_00000000, &8, &0);
+
+    assert_eq!(
+        client.try_set_price(&oracle, &asset, &100_000000, &6, &0),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+    assert_eq!(client.get_price(&asset).decimals, 8);
+
+    // The admin can move the asset to a new precision
+    assert_eq!(
+        client.try_set_asset_decimals(&oracle, &asset, &6),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    client.set_asset_decimals(&admin, &asset, &6);
+    client.set_price(&oracle, &asset, &100_000000, &6, &0);
+    assert_eq!(client.get_price(&asset).decimals, 6);
+}
+
+#[test]
+fn test_frozen_price_rejects_updates_and_stays_readable() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    client.add_oracle(&admin, &oracle);
+    client.set_price(&oracle, &asset, &1000, &8, &5);
+
+    client.freeze_price(&admin, &asset);
+    assert!(client.is_price_frozen(&asset));
+    assert_eq!(
+        client.try_set_price(&oracle, &asset, &10, &8, &0),
+        Err(Ok(OracleError::PriceFrozen))
+    );
+
+    // Well past max staleness, the held price is still served
+    e.ledger().with_mut(|li| li.timestamp += 10_000);
+    let held = client.get_price_valid(&asset, &None);
+    assert_eq!(held.price, 1000);
+    assert_eq!(held.updated_at, 0);
+    assert_eq!(client.get_price_with_confidence(&asset, &None), (1000, 5));
+
+    // Once unfrozen the held price ages normally and feeds resume
+    client.unfreeze_price(&admin, &asset);
+    assert!(!client.is_price_frozen(&asset));
+    assert_eq!(
+        client.try_get_price_valid(&asset, &None),
+        Err(Ok(OracleError::StalePrice))
+    );
+    client.set_price(&oracle, &asset, &1100, &8, &0);
+    assert_eq!(client.get_price_valid(&asset, &None).price, 1100);
+}
+
+#[test]
+fn test_freeze_price_requires_admin_and_existing_price() {
+    let e = Env::default();
+    let (admin, client) = setup_oracle(&e);
+    let asset = Address::generate(&e);
+    let stranger = Address::generate(&e);
+
+    assert_eq!(
+        client.try_freeze_price(&admin, &asset),
+        Err(Ok(OracleError::PriceNotFound))
+    );
+    assert_eq!(
+        client.try_freeze_price(&stranger, &asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_unfreeze_price(&stranger, &asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert!(!client.is_price_frozen(&asset));
+}
+
+fn setup_timelocked_oracle(e: &Env) -> (Address, Address, PriceOracleContractClient<'_>) {
+    e.mock_all_auths();
+    let admin = Address::generate(e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(e, &contract_id);
+    client.initialize(&admin);
+
+    let timelock_id = e.register_contract(None, time_lock::TimelockContract);
+    time_lock::TimelockContractClient::new(e, &timelock_id).initialize(&admin);
+    client.set_timelock(&admin, &timelock_id);
+    client.enable_timelock_enforcement(&admin);
+    (admin, timelock_id, client)
+}
+
+fn queue_whitelist_change(
+    e: &Env,
+    timelock_id: &Address,
+    target: &Address,
+    function: &str,
+    oracle: &Address,
+) -> u64 {
+    time_lock::TimelockContractClient::new(e, timelock_id).queue_action(
+        &time_lock::ActionType::ParameterChange,
+        target,
+        &Symbol::new(e, function),
+        &soroban_sdk::vec![e, oracle.into_val(e)],
+        &String::from_str(e, "oracle whitelist"),
+        &86400,
+    )
+}
+
+#[test]
+fn test_add_oracle_requires_executed_timelock_action() {
+    let e = Env::default();
+    let (admin, timelock_id, client) = setup_timelocked_oracle(&e);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let oracle = Address::generate(&e);
+    assert!(client.is_timelock_enforced());
+
+    // Direct additions are closed once enforcement is on
+    assert_eq!(
+        client.try_add_oracle(&admin, &oracle),
+        Err(Ok(OracleError::TimelockRequired))
+    );
+    assert_eq!(
+        client.try_add_oracle_timelocked(&admin, &oracle, &99),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+
+    // Queued but not executed yet
+    let action_id =
+        queue_whitelist_change(&e, &timelock_id, &client.address, "add_oracle", &oracle);
+    assert_eq!(
+        client.try_add_oracle_timelocked(&admin, &oracle, &action_id),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+
+    e.ledger().with_mut(|li| li.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    // An executed action for a different oracle does not match
+    let other = Address::generate(&e);
+    assert_eq!(
+        client.try_add_oracle_timelocked(&admin, &other, &action_id),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+
+    client.add_oracle_timelocked(&admin, &oracle, &action_id);
+    assert!(client.is_oracle_whitelisted(&oracle));
+
+    // Each action approves a single change
+    let remove_id =
+        queue_whitelist_change(&e, &timelock_id, &client.address, "remove_oracle", &oracle);
+    e.ledger().with_mut(|li| li.timestamp += 86400);
+    timelock.execute_action(&remove_id);
+    client.remove_oracle_timelocked(&admin, &oracle, &remove_id);
+    assert!(!client.is_oracle_whitelisted(&oracle));
+    assert_eq!(
+        client.try_add_oracle_timelocked(&admin, &oracle, &action_id),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+}
+
+#[test]
+fn test_remove_oracle_timelock_action_must_match_function() {
+    let e = Env::default();
+    let (admin, timelock_id, client) = setup_timelocked_oracle(&e);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let oracle = Address::generate(&e);
+
+    let add_id = queue_whitelist_change(&e, &timelock_id, &client.address, "add_oracle", &oracle);
+    e.ledger().with_mut(|li| li.timestamp += 86400);
+    timelock.execute_action(&add_id);
+    client.add_oracle_timelocked(&admin, &oracle, &add_id);
+
+    assert_eq!(
+        client.try_remove_oracle(&admin, &oracle),
+        Err(Ok(OracleError::TimelockRequired))
+    );
+    // An approval to add cannot be reused to remove
+    assert_eq!(
+        client.try_remove_oracle_timelocked(&admin, &oracle, &add_id),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+    assert!(client.is_oracle_whitelisted(&oracle));
+}
+
+#[test]
+fn test_timelock_action_does_not_replay_across_deployments() {
+    let e = Env::default();
+    let (admin, timelock_id, client) = setup_timelocked_oracle(&e);
+    let timelock = time_lock::TimelockContractClient::new(&e, &timelock_id);
+    let oracle = Address::generate(&e);
+
+    // A second oracle deployment sharing the same timelock
+    let other_id = e.register_contract(None, PriceOracleContract);
+    let other = PriceOracleContractClient::new(&e, &other_id);
+    other.initialize(&admin);
+    other.set_timelock(&admin, &timelock_id);
+    other.enable_timelock_enforcement(&admin);
+
+    let action_id = queue_whitelist_change(&e, &timelock_id, &other_id, "add_oracle", &oracle);
+    e.ledger().with_mut(|li| li.timestamp += 86400);
+    timelock.execute_action(&action_id);
+
+    assert_eq!(
+        client.try_add_oracle_timelocked(&admin, &oracle, &action_id),
+        Err(Ok(OracleError::InvalidTimelockAction))
+    );
+    other.add_oracle_timelocked(&admin, &oracle, &action_id);
+    assert!(other.is_oracle_whitelisted(&oracle));
+    assert!(!client.is_oracle_whitelisted(&oracle));
+}
+
+#[test]
+fn test_timelock_enforcement_needs_timelock() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let oracle = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_enable_timelock_enforcement(&admin),
+        Err(Ok(OracleError::TimelockNotSet))
+    );
+    assert!(!client.is_timelock_enforced());
+    client.add_oracle(&admin, &oracle);
+    assert!(client.is_oracle_whitelisted(&oracle));
+
+    let timelock_id = Address::generate(&e);
+    client.set_timelock(&admin, &timelock_id);
+    assert_eq!(client.get_timelock(), Some(timelock_id.clone()));
+    assert_eq!(
+        client.try_set_timelock(&admin, &Address::generate(&e)),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_subscribe_and_unsubscribe() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let oracle = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let subscriber = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+    client.add_oracle(&admin, &oracle);
+
+    client.subscribe(&admin, &subscriber, &asset);
+    client.subscribe(&admin, &subscriber, &asset);
+    assert_eq!(client.get_subscribers(&asset).len(), 1);
+
+    // A subscriber without the callback does not block the update
+    client.set_price(&oracle, &asset, &100, &6, &0);
+    assert_eq!(client.get_price(&asset).price, 100);
+
+    client.unsubscribe(&subscriber, &subscriber, &asset);
+    assert!(client.get_subscribers(&asset).is_empty());
+}
+
+#[test]
+fn test_subscribe_admin_only() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let subscriber = Address::generate(&e);
+    let stranger = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+
+    // Nobody can register themselves into the set_price path
+    assert_eq!(
+        client.try_subscribe(&subscriber, &subscriber, &asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert!(client.get_subscribers(&asset).is_empty());
+
+    // The admin can evict a subscriber; strangers cannot
+    client.subscribe(&admin, &subscriber, &asset);
+    assert_eq!(
+        client.try_unsubscribe(&stranger, &subscriber, &asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    client.unsubscribe(&admin, &subscriber, &asset);
+    assert!(client.get_subscribers(&asset).is_empty());
+}
+
+#[test]
+fn test_subscribe_capped_per_asset() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+
+    for _ in 0..MAX_SUBSCRIBERS {
+        client.subscribe(&admin, &Address::generate(&e), &asset);
+    }
+    assert_eq!(
+        client.try_subscribe(&admin, &Address::generate(&e), &asset),
+        Err(Ok(OracleError::TooManySubscribers))
+    );
+    // The cap is per asset
+    client.subscribe(&admin, &Address::generate(&e), &Address::generate(&e));
+}
+
+fn setup_oracles(e: &Env, count: u32) -> (Address, PriceOracleContractClient<'_>, Vec<Address>) {
+    e.mock_all_auths();
+    let admin = Address::generate(e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(e, &contract_id);
+    client.initialize(&admin);
+    let mut oracles = Vec::new(e);
+    for _ in 0..count {
+        let oracle = Address::generate(e);
+        client.add_oracle(&admin, &oracle);
+        oracles.push_back(oracle);
+    }
+    (admin, client, oracles)
+}
+
+#[test]
+fn test_robust_price_excludes_outlier() {
+    let e = Env::default();
+    let (admin, client, oracles) = setup_oracles(&e, 4);
+    let asset = Address::generate(&e);
+
+    client.set_outlier_config(&admin, &500, &3);
+    for (oracle, price) in oracles.iter().zip([100_0000, 101_0000, 99_0000, 150_0000]) {
+        client.set_price(&oracle, &asset, &price, &4, &0);
+    }
+
+    // Median 100.5: 150 is ~49% away and dropped, the rest averaged
+    let robust = client.get_robust_price(&asset);
+    assert_eq!(robust.price, 100_0000);
+    assert_eq!(robust.decimals, 4);
+    assert_eq!(robust.confidence, 1_0000);
+
+    // Without trimming, the outlier pulls the average up
+    client.set_outlier_config(&admin, &0, &1);
+    assert_eq!(client.get_robust_price(&asset).price, 112_5000);
+}
+
+#[test]
+fn test_robust_price_requires_min_sources() {
+    let e = Env::default();
+    let (admin, client, oracles) = setup_oracles(&e, 4);
+    let asset = Address::generate(&e);
+
+    assert_eq!(
+        client.try_get_robust_price(&asset),
+        Err(Ok(OracleError::InsufficientSources))
+    );
+
+    client.set_outlier_config(&admin, &500, &4);
+    for (oracle, price) in oracles.iter().zip([100, 101, 99, 150]) {
+        client.set_price(&oracle, &asset, &price, &0, &0);
+    }
+    assert_eq!(
+        client.try_get_robust_price(&asset),
+        Err(Ok(OracleError::InsufficientSources))
+    );
+}
+
+#[test]
+fn test_robust_price_skips_stale_and_removed_oracles() {
+    let e = Env::default();
+    let (admin, client, oracles) = setup_oracles(&e, 3);
+    let asset = Address::generate(&e);
+    client.set_max_staleness(&admin, &300);
+
+    client.set_price(&oracles.get_unchecked(0), &asset, &1_000, &0, &0);
+    e.ledger().with_mut(|li| li.timestamp += 400);
+    client.set_price(&oracles.get_unchecked(1), &asset, &2_000, &0, &0);
+    client.set_price(&oracles.get_unchecked(2), &asset, &3_000, &0, &0);
+    client.remove_oracle(&admin, &oracles.get_unchecked(2));
+
+    let robust = client.get_robust_price(&asset);
+    assert_eq!(robust.price, 2_000);
+    assert_eq!(robust.confidence, 0);
+}
+
+#[test]
+fn test_set_outlier_config_validates() {
+    let e = Env::default();
+    let (admin, client, _oracles) = setup_oracles(&e, 0);
+
+    assert_eq!(client.get_outlier_config(), (0, 1));
+    assert_eq!(
+        client.try_set_outlier_config(&admin, &10_001, &1),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_set_outlier_config(&admin, &500, &0),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+    assert_eq!(
+        client.try_set_outlier_config(&Address::generate(&e), &500, &2),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
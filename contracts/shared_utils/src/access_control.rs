@@ -1,7 +1,7 @@
 //! Access control patterns and utilities
 
 use super::storage::Storage;
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, Symbol};
 
 /// Access control helper functions
 pub struct AccessControl;
@@ -102,6 +102,122 @@ impl AccessControl {
     }
 }
 
+/// Pause flags keyed by an operation scope (e.g. `mint`)
+///
+/// Lets an admin stop one kind of operation without pausing the whole
+/// contract. A contract can also point at a shared registry contract (any
+/// contract exposing `is_scope_paused(scope) -> bool`), so a single pause
+/// there stops that scope in every contract that opted in. A registry that
+/// cannot answer counts as paused; the admin recovers by clearing it.
+pub struct PauseRegistry;
+
+impl PauseRegistry {
+    /// Storage key prefix for per-scope pause flags
+    pub const SCOPE_PREFIX: Symbol = symbol_short!("pscope");
+    /// Storage key for the optional shared registry address
+    pub const REGISTRY_KEY: Symbol = symbol_short!("pregistry");
+
+    /// Check if `scope` is paused locally or in the shared registry
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `scope` - The operation scope
+    ///
+    /// # Returns
+    /// `true` if paused (or the registry call fails), `false` otherwise
+    pub fn is_scope_paused(e: &Env, scope: &Symbol) -> bool {
+        if Self::is_scope_paused_locally(e, scope) {
+            return true;
+        }
+        match Self::get_registry(e) {
+            Some(registry) => !matches!(
+                e.try_invoke_contract::<bool, soroban_sdk::Error>(
+                    &registry,
+                    &Symbol::new(e, "is_scope_paused"),
+                    vec![e, scope.into_val(e)],
+                ),
+                Ok(Ok(false))
+            ),
+            None => false,
+        }
+    }
+
+    /// Check only this contract's own flag for `scope`
+    pub fn is_scope_paused_locally(e: &Env, scope: &Symbol) -> bool {
+        e.storage()
+            .instance()
+            .get::<_, bool>(&(Self::SCOPE_PREFIX, scope.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Pause `scope` in this contract
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `scope` - The operation scope
+    ///
+    /// # Panics
+    /// Panics if the scope is already paused
+    pub fn pause_scope(e: &Env, scope: &Symbol) {
+        if Self::is_scope_paused_locally(e, scope) {
+            panic!("Scope is already paused");
+        }
+        e.storage()
+            .instance()
+            .set(&(Self::SCOPE_PREFIX, scope.clone()), &true);
+        e.events()
+            .publish((Symbol::new(e, "ScopePause"), scope.clone()), ());
+    }
+
+    /// Unpause `scope` in this contract
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `scope` - The operation scope
+    ///
+    /// # Panics
+    /// Panics if the scope is not paused
+    pub fn unpause_scope(e: &Env, scope: &Symbol) {
+        if !Self::is_scope_paused_locally(e, scope) {
+            panic!("Scope is not paused");
+        }
+        e.storage()
+            .instance()
+            .remove(&(Self::SCOPE_PREFIX, scope.clone()));
+        e.events()
+            .publish((Symbol::new(e, "ScopeUnpause"), scope.clone()), ());
+    }
+
+    /// Set (or clear with `None`) the shared registry consulted by `is_scope_paused`
+    pub fn set_registry(e: &Env, registry: Option<Address>) {
+        match registry {
+            Some(registry) => e.storage().instance().set(&Self::REGISTRY_KEY, &registry),
+            None => e.storage().instance().remove(&Self::REGISTRY_KEY),
+        }
+    }
+
+    /// Get the shared registry address, if any
+    pub fn get_registry(e: &Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&Self::REGISTRY_KEY)
+    }
+
+    /// Modifier to require that `scope` is not paused
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `scope` - The operation scope
+    ///
+    /// # Panics
+    /// Panics if the scope is paused locally or in the shared registry
+    pub fn require_not_paused_scope(e: &Env, scope: &Symbol) {
+        if Self::is_scope_paused(e, scope) {
+            panic!("Scope is paused - operation not allowed");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::storage::Storage;
@@ -116,6 +232,10 @@ mod tests {
     #[contractimpl]
     impl TestContract {
         pub fn stub() {}
+
+        pub fn is_scope_paused(e: Env, scope: Symbol) -> bool {
+            PauseRegistry::is_scope_paused(&e, &scope)
+        }
     }
 
     #[test]
@@ -152,4 +272,56 @@ mod tests {
             AccessControl::require_owner(&env, &owner, &owner);
         });
     }
+
+    #[test]
+    fn test_scoped_pause_isolation() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestContract);
+        let mint = symbol_short!("mint");
+        let transfer = symbol_short!("transfer");
+
+        env.as_contract(&contract_id, || {
+            PauseRegistry::pause_scope(&env, &mint);
+            assert!(PauseRegistry::is_scope_paused(&env, &mint));
+            assert!(!PauseRegistry::is_scope_paused(&env, &transfer));
+            PauseRegistry::require_not_paused_scope(&env, &transfer);
+
+            PauseRegistry::unpause_scope(&env, &mint);
+            assert!(!PauseRegistry::is_scope_paused(&env, &mint));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Scope is paused - operation not allowed")]
+    fn test_require_not_paused_scope_panics() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestContract);
+        env.as_contract(&contract_id, || {
+            PauseRegistry::pause_scope(&env, &symbol_short!("mint"));
+            PauseRegistry::require_not_paused_scope(&env, &symbol_short!("mint"));
+        });
+    }
+
+    #[test]
+    fn test_scope_pause_follows_shared_registry() {
+        let env = Env::default();
+        let registry_id = env.register_contract(None, TestContract);
+        let contract_id = env.register_contract(None, TestContract);
+        let other_id = env.register_contract(None, TestContract);
+        let mint = symbol_short!("mint");
+
+        env.as_contract(&contract_id, || {
+            PauseRegistry::set_registry(&env, Some(registry_id.clone()));
+        });
+        env.as_contract(&registry_id, || PauseRegistry::pause_scope(&env, &mint));
+
+        // Opted-in contract sees the registry's pause; others do not
+        env.as_contract(&contract_id, || {
+            assert!(PauseRegistry::is_scope_paused(&env, &mint));
+            assert!(!PauseRegistry::is_scope_paused_locally(&env, &mint));
+        });
+        env.as_contract(&other_id, || {
+            assert!(!PauseRegistry::is_scope_paused(&env, &mint));
+        });
+    }
 }
@@ -51,6 +51,85 @@ impl Events {
         );
     }
 
+    /// Emit the standard commitment creation event
+    ///
+    /// Topics: `("Created", commitment_id, owner)`
+    /// Data: `(amount, rules, nft_token_id, timestamp)`
+    ///
+    /// `rules` is the creating contract's rules type, passed through as-is so
+    /// the payload keeps the layout indexers already decode.
+    pub fn emit_commitment_created<R>(
+        e: &Env,
+        commitment_id: &SorobanString,
+        owner: &Address,
+        amount: i128,
+        rules: &R,
+        nft_token_id: u32,
+    ) where
+        R: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        let rules: soroban_sdk::Val = rules.into_val(e);
+        Self::emit_created(
+            e,
+            commitment_id,
+            owner,
+            (amount, rules, nft_token_id, e.ledger().timestamp()),
+        );
+    }
+
+    /// Emit the standard commitment settlement event
+    ///
+    /// Topics: `("Settled", commitment_id)`
+    /// Data: `(settlement_amount, timestamp)`
+    pub fn emit_commitment_settled(e: &Env, commitment_id: &SorobanString, amount: i128) {
+        Self::emit_with_topics(
+            e,
+            (symbol_short!("Settled"), commitment_id.clone()),
+            (amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Emit the standard commitment value update event
+    ///
+    /// Topics: `("ValUpd", commitment_id)`
+    /// Data: `(old_value, new_value, violated, timestamp)`
+    pub fn emit_value_updated(
+        e: &Env,
+        commitment_id: &SorobanString,
+        old_value: i128,
+        new_value: i128,
+        violated: bool,
+    ) {
+        Self::emit_with_topics(
+            e,
+            (symbol_short!("ValUpd"), commitment_id.clone()),
+            (old_value, new_value, violated, e.ledger().timestamp()),
+        );
+    }
+
+    /// Emit the uniform commitment status transition event
+    ///
+    /// Emitted from every status change alongside the transition's own event.
+    ///
+    /// Topics: `("StatusChg", commitment_id)`
+    /// Data: `(old_status, new_status, timestamp)`
+    pub fn emit_status_changed(
+        e: &Env,
+        commitment_id: &SorobanString,
+        old_status: &SorobanString,
+        new_status: &SorobanString,
+    ) {
+        Self::emit_with_topics(
+            e,
+            (symbol_short!("StatusChg"), commitment_id.clone()),
+            (
+                old_status.clone(),
+                new_status.clone(),
+                e.ledger().timestamp(),
+            ),
+        );
+    }
+
     /// Emit an update event
     ///
     /// # Arguments
@@ -110,7 +189,96 @@ impl Events {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::Address as TestAddress;
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as TestAddress, Events as _, Ledger},
+        IntoVal, Val, Vec,
+    };
+
+    #[contract]
+    struct EventsTestContract;
+
+    #[contractimpl]
+    impl EventsTestContract {}
+
+    fn last_event(env: &Env) -> (Vec<Val>, Val) {
+        let (_, topics, data) = env.events().all().last().unwrap();
+        (topics, data)
+    }
+
+    #[test]
+    fn test_emit_commitment_created_schema() {
+        let env = Env::default();
+        env.ledger().with_mut(|l| l.timestamp = 1234);
+        let contract_id = env.register_contract(None, EventsTestContract);
+        let owner = <soroban_sdk::Address as TestAddress>::generate(&env);
+        let id = SorobanString::from_str(&env, "c_1");
+
+        env.as_contract(&contract_id, || {
+            Events::emit_commitment_created(&env, &id, &owner, 500, &symbol_short!("rules"), 7);
+        });
+
+        let (topics, data) = last_event(&env);
+        let expected: Vec<Val> = (symbol_short!("Created"), id, owner).into_val(&env);
+        assert_eq!(topics, expected);
+        let data: (i128, Symbol, u32, u64) = data.into_val(&env);
+        assert_eq!(data, (500, symbol_short!("rules"), 7, 1234));
+    }
+
+    #[test]
+    fn test_emit_commitment_settled_schema() {
+        let env = Env::default();
+        env.ledger().with_mut(|l| l.timestamp = 99);
+        let contract_id = env.register_contract(None, EventsTestContract);
+        let id = SorobanString::from_str(&env, "c_2");
+
+        env.as_contract(&contract_id, || {
+            Events::emit_commitment_settled(&env, &id, 1_000);
+        });
+
+        let (topics, data) = last_event(&env);
+        let expected: Vec<Val> = (symbol_short!("Settled"), id).into_val(&env);
+        assert_eq!(topics, expected);
+        let data: (i128, u64) = data.into_val(&env);
+        assert_eq!(data, (1_000, 99));
+    }
+
+    #[test]
+    fn test_emit_value_updated_schema() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, EventsTestContract);
+        let id = SorobanString::from_str(&env, "c_3");
+
+        env.as_contract(&contract_id, || {
+            Events::emit_value_updated(&env, &id, 1_000, 850, true);
+        });
+
+        let (topics, data) = last_event(&env);
+        let expected: Vec<Val> = (symbol_short!("ValUpd"), id).into_val(&env);
+        assert_eq!(topics, expected);
+        let data: (i128, i128, bool, u64) = data.into_val(&env);
+        assert_eq!(data, (1_000, 850, true, 0));
+    }
+
+    #[test]
+    fn test_emit_status_changed_schema() {
+        let env = Env::default();
+        env.ledger().with_mut(|l| l.timestamp = 42);
+        let contract_id = env.register_contract(None, EventsTestContract);
+        let id = SorobanString::from_str(&env, "c_4");
+        let active = SorobanString::from_str(&env, "active");
+        let settled = SorobanString::from_str(&env, "settled");
+
+        env.as_contract(&contract_id, || {
+            Events::emit_status_changed(&env, &id, &active, &settled);
+        });
+
+        let (topics, data) = last_event(&env);
+        let expected: Vec<Val> = (symbol_short!("StatusChg"), id).into_val(&env);
+        assert_eq!(topics, expected);
+        let data: (SorobanString, SorobanString, u64) = data.into_val(&env);
+        assert_eq!(data, (active, settled, 42));
+    }
 
     #[test]
     fn test_emit() {
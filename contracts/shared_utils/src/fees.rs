@@ -47,6 +47,31 @@ pub fn net_after_fee_bps(amount: i128, bps: u32) -> i128 {
     amount.checked_sub(fee).expect("Fees: underflow")
 }
 
+/// Fee calculators shared by settlement and penalty logic.
+pub struct Fees;
+
+impl Fees {
+    /// Basis-point fee clamped to `[min_fee, max_fee]`.
+    ///
+    /// The raw fee is `(amount * bps) / 10000`, rounded down. The floor is
+    /// applied first and the cap second, so `max_fee` wins if it is below
+    /// `min_fee`. A product too large for `i128` is clamped to the cap rather
+    /// than overflowing.
+    ///
+    /// # Panics
+    /// If `bps > 10000`.
+    pub fn calc_fee(amount: i128, bps: u32, min_fee: i128, max_fee: i128) -> i128 {
+        if bps > BPS_MAX {
+            panic!("Fees: bps must be 0-10000");
+        }
+        let raw = amount
+            .checked_mul(bps as i128)
+            .map(|scaled| scaled / BPS_SCALE as i128)
+            .unwrap_or(i128::MAX);
+        raw.max(min_fee).min(max_fee)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +113,32 @@ mod tests {
     fn test_fee_from_bps_invalid() {
         fee_from_bps(1000, 10001);
     }
+
+    #[test]
+    fn test_calc_fee_below_floor() {
+        assert_eq!(Fees::calc_fee(1000, 100, 50, 500), 50); // 1% = 10 -> floor 50
+        assert_eq!(Fees::calc_fee(0, 100, 50, 500), 50);
+    }
+
+    #[test]
+    fn test_calc_fee_within_range() {
+        assert_eq!(Fees::calc_fee(10000, 100, 50, 500), 100); // 1%
+    }
+
+    #[test]
+    fn test_calc_fee_above_cap() {
+        assert_eq!(Fees::calc_fee(1_000_000, 100, 50, 500), 500); // 1% = 10000 -> cap 500
+        assert_eq!(Fees::calc_fee(i128::MAX, 10000, 0, 500), 500); // overflow clamps to cap
+    }
+
+    #[test]
+    fn test_calc_fee_cap_wins_over_floor() {
+        assert_eq!(Fees::calc_fee(1000, 100, 500, 50), 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "bps must be 0-10000")]
+    fn test_calc_fee_invalid_bps() {
+        Fees::calc_fee(1000, 10001, 0, 100);
+    }
 }
@@ -19,6 +19,7 @@ pub mod emergency;
 pub mod error_codes;
 pub mod errors;
 pub mod events;
+pub mod fees;
 pub mod math;
 pub mod pausable;
 pub mod rate_limiting;
@@ -36,6 +37,7 @@ pub use emergency::EmergencyControl;
 pub use error_codes::*;
 pub use errors::*;
 pub use events::*;
+pub use fees::*;
 pub use math::*;
 pub use pausable::*;
 pub use rate_limiting::*;
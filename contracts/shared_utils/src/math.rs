@@ -1,5 +1,16 @@
 //! Math utilities for safe arithmetic operations and percentage calculations
 
+use crate::fees::BPS_SCALE;
+
+/// Failure of a checked math operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MathError {
+    /// Result does not fit in an i128
+    Overflow,
+    /// Divisor was zero
+    DivByZero,
+}
+
 /// Safe math operations to prevent overflow/underflow
 pub struct SafeMath;
 
@@ -27,6 +38,14 @@ impl SafeMath {
         a.checked_div(b).expect("Math: division overflow")
     }
 
+    /// Divide two i128 values, returning an error instead of panicking
+    pub fn checked_div(a: i128, b: i128) -> Result<i128, MathError> {
+        if b == 0 {
+            return Err(MathError::DivByZero);
+        }
+        a.checked_div(b).ok_or(MathError::Overflow)
+    }
+
     /// Calculate percentage: (value * percent) / 100
     ///
     /// # Arguments
@@ -48,6 +67,22 @@ impl SafeMath {
         Self::percent(value, percent)
     }
 
+    /// Calculate a basis-point share of a value: (value * bps) / 10000
+    ///
+    /// Unlike `percent_of`, overflow is returned rather than panicking or
+    /// being masked as zero, so callers can surface it as their own error.
+    ///
+    /// # Arguments
+    /// * `value` - The base value
+    /// * `bps` - The share in basis points (10000 = 100%)
+    ///
+    /// # Returns
+    /// The share, truncated toward zero, or `MathError::Overflow`
+    pub fn checked_percent_of(value: i128, bps: u32) -> Result<i128, MathError> {
+        let scaled = value.checked_mul(bps as i128).ok_or(MathError::Overflow)?;
+        Self::checked_div(scaled, BPS_SCALE as i128)
+    }
+
     /// Calculate what percentage `part` is of `whole`: (part * 100) / whole
     ///
     /// # Arguments
@@ -95,6 +130,26 @@ impl SafeMath {
         Self::percent_from(gain, initial)
     }
 
+    /// Signed profit-and-loss percentage: ((current - initial) * 100) / initial
+    ///
+    /// Positive for a gain, negative for a loss, zero at break-even. Unlike
+    /// `loss_percent` / `gain_percent`, a non-positive `initial` yields 0
+    /// instead of panicking, so callers need no zero-amount special case.
+    /// A drawdown is the negation of this value.
+    ///
+    /// # Arguments
+    /// * `initial` - The initial value
+    /// * `current` - The current value
+    ///
+    /// # Returns
+    /// The signed percentage as i128, truncated toward zero
+    pub fn pnl_percent(initial: i128, current: i128) -> i128 {
+        if initial <= 0 {
+            return 0;
+        }
+        Self::percent_from(Self::sub(current, initial), initial)
+    }
+
     /// Apply a percentage penalty: value - (value * penalty_percent / 100)
     ///
     /// # Arguments
@@ -183,6 +238,21 @@ mod tests {
         assert_eq!(SafeMath::gain_percent(1000, 1000), 0);
     }
 
+    #[test]
+    fn test_pnl_percent() {
+        // Gain
+        assert_eq!(SafeMath::pnl_percent(1000, 1100), 10);
+        // Loss
+        assert_eq!(SafeMath::pnl_percent(1000, 850), -15);
+        // Break-even
+        assert_eq!(SafeMath::pnl_percent(1000, 1000), 0);
+        // Truncates toward zero in both directions
+        assert_eq!(SafeMath::pnl_percent(1000, 1005), 0);
+        assert_eq!(SafeMath::pnl_percent(1000, 995), 0);
+        // No initial value means no meaningful PnL
+        assert_eq!(SafeMath::pnl_percent(0, 500), 0);
+    }
+
     #[test]
     fn test_apply_penalty() {
         assert_eq!(SafeMath::apply_penalty(1000, 10), 900);
@@ -196,4 +266,38 @@ mod tests {
         assert_eq!(SafeMath::penalty_amount(1000, 5), 50);
         assert_eq!(SafeMath::penalty_amount(1000, 0), 0);
     }
+
+    #[test]
+    fn test_checked_percent_of() {
+        assert_eq!(SafeMath::checked_percent_of(1000, 1000), Ok(100));
+        assert_eq!(SafeMath::checked_percent_of(1000, 10_000), Ok(1000));
+        assert_eq!(SafeMath::checked_percent_of(1000, 0), Ok(0));
+        assert_eq!(SafeMath::checked_percent_of(999, 1), Ok(0));
+        assert_eq!(SafeMath::checked_percent_of(-1000, 500), Ok(-50));
+    }
+
+    #[test]
+    fn test_checked_percent_of_overflow() {
+        assert_eq!(
+            SafeMath::checked_percent_of(i128::MAX, 2),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(
+            SafeMath::checked_percent_of(i128::MIN, 10_000),
+            Err(MathError::Overflow)
+        );
+        // Largest value that still fits at 100%
+        let max = i128::MAX / 10_000;
+        assert_eq!(SafeMath::checked_percent_of(max, 10_000), Ok(max));
+    }
+
+    #[test]
+    fn test_checked_div() {
+        assert_eq!(SafeMath::checked_div(100, 5), Ok(20));
+        assert_eq!(SafeMath::checked_div(100, 0), Err(MathError::DivByZero));
+        assert_eq!(
+            SafeMath::checked_div(i128::MIN, -1),
+            Err(MathError::Overflow)
+        );
+    }
 }
@@ -4,15 +4,20 @@
 //! that can be reused across contracts. It supports:
 //! - Per-address limits
 //! - Per-function limits
-//! - Time window based limits (fixed windows)
+//! - Global per-function limits shared by all callers
+//! - Time window based limits (fixed or sliding windows)
 //! - Optional exemption list
 //!
 //! Storage layout (instance storage, per contract):
 //! - (RL_CFG, function_symbol) -> (window_seconds: u64, max_calls: u32)
+//! - (RL_MODE, function_symbol) -> RateMode (absent means `Fixed`)
 //! - (RL_STATE, address, function_symbol) -> (window_start: u64, count: u32)
+//! - (RL_SLIDE, address, function_symbol) -> Vec<u64> of recent call times
 //! - (RL_EX, address) -> bool
+//! - (RL_GCFG, function_symbol) -> (window_seconds, max_calls) shared by all callers
+//! - (RL_GST, function_symbol) / (RL_GSLIDE, function_symbol) -> global window state
 
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Symbol, Val, Vec};
 
 use crate::time::TimeUtils;
 
@@ -26,6 +31,26 @@ mod keys {
     pub const RATE_LIMIT_STATE: Symbol = symbol_short!("RL_ST");
     // Exemption flag for an address
     pub const RATE_LIMIT_EXEMPT: Symbol = symbol_short!("RL_EX");
+    // Window mode for a function
+    pub const RATE_LIMIT_MODE: Symbol = symbol_short!("RL_MODE");
+    // Per-address per-function timestamps for sliding windows
+    pub const RATE_LIMIT_SLIDING: Symbol = symbol_short!("RL_SLIDE");
+    // Global (caller-independent) configuration and state for a function
+    pub const GLOBAL_LIMIT_CONFIG: Symbol = symbol_short!("RL_GCFG");
+    pub const GLOBAL_LIMIT_STATE: Symbol = symbol_short!("RL_GST");
+    pub const GLOBAL_LIMIT_SLIDING: Symbol = symbol_short!("RL_GSLIDE");
+}
+
+/// How calls are counted against `max_calls`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateMode {
+    /// Counter resets once `window_seconds` have passed since the window
+    /// opened; allows up to `2 * max_calls` around a window boundary.
+    Fixed = 0,
+    /// Counts calls in the trailing `window_seconds`; no boundary bursts.
+    /// Keeps up to `max_calls` timestamps per address.
+    Sliding = 1,
 }
 
 /// Rate limiting helper
@@ -50,6 +75,21 @@ impl RateLimiter {
             .set(&key, &(window_seconds, max_calls));
     }
 
+    /// Choose how calls to `function` are counted (default `Fixed`).
+    pub fn set_mode(e: &Env, function: &Symbol, mode: RateMode) {
+        let key = (keys::RATE_LIMIT_MODE, function.clone());
+        e.storage().instance().set(&key, &mode);
+    }
+
+    /// Get the window mode for `function`.
+    pub fn get_mode(e: &Env, function: &Symbol) -> RateMode {
+        let key = (keys::RATE_LIMIT_MODE, function.clone());
+        e.storage()
+            .instance()
+            .get::<_, RateMode>(&key)
+            .unwrap_or(RateMode::Fixed)
+    }
+
     /// Clear the rate limit configuration for a function.
     pub fn clear_limit(e: &Env, function: &Symbol) {
         let key = (keys::RATE_LIMIT_CONFIG, function.clone());
@@ -59,13 +99,21 @@ impl RateLimiter {
     /// Set or clear exemption for an address.
     ///
     /// When `exempt == true`, the address is not subject to rate limits.
+    /// Emits `RateLimitExemptSet` when the flag changes.
     pub fn set_exempt(e: &Env, address: &Address, exempt: bool) {
+        if Self::is_exempt(e, address) == exempt {
+            return;
+        }
         let key = (keys::RATE_LIMIT_EXEMPT, address.clone());
         if exempt {
             e.storage().instance().set(&key, &true);
         } else {
             e.storage().instance().remove(&key);
         }
+        e.events().publish(
+            (Symbol::new(e, "RateLimitExemptSet"), address.clone()),
+            exempt,
+        );
     }
 
     /// Check if an address is exempt from rate limits.
@@ -79,7 +127,8 @@ impl RateLimiter {
     /// Behavior:
     /// - If no config exists for `function`, this is a no-op.
     /// - If `address` is exempt, this is a no-op.
-    /// - Otherwise, maintains a fixed time window based on ledger timestamp.
+    /// - Otherwise, maintains a fixed or sliding time window (see `RateMode`)
+    ///   based on ledger timestamp.
     /// - Panics with `"Rate limit exceeded"` when limit is hit.
     pub fn check(e: &Env, address: &Address, function: &Symbol) {
         // Exempt addresses bypass rate limits
@@ -97,13 +146,70 @@ impl RateLimiter {
         };
 
         let now = TimeUtils::now(e);
+        if Self::get_mode(e, function) == RateMode::Sliding {
+            let state_key = (keys::RATE_LIMIT_SLIDING, address.clone(), function.clone());
+            Self::consume_sliding(e, &state_key, now, window_seconds, max_calls);
+        } else {
+            let state_key = (keys::RATE_LIMIT_STATE, address.clone(), function.clone());
+            Self::consume_fixed(e, &state_key, now, window_seconds, max_calls);
+        }
+    }
+
+    /// Configure a protocol-wide limit for `function`, shared by all callers.
+    ///
+    /// Independent of any per-address limit set with `set_limit`; both apply
+    /// when configured. Uses the function's `RateMode`.
+    pub fn set_global_limit(e: &Env, function: &Symbol, window_seconds: u64, max_calls: u32) {
+        if window_seconds == 0 || max_calls == 0 {
+            panic!("Invalid rate limit configuration");
+        }
 
-        // Load current state
-        let state_key = (keys::RATE_LIMIT_STATE, address.clone(), function.clone());
+        let key = (keys::GLOBAL_LIMIT_CONFIG, function.clone());
+        e.storage()
+            .instance()
+            .set(&key, &(window_seconds, max_calls));
+    }
+
+    /// Clear the global limit for a function.
+    pub fn clear_global_limit(e: &Env, function: &Symbol) {
+        let key = (keys::GLOBAL_LIMIT_CONFIG, function.clone());
+        e.storage().instance().remove(&key);
+    }
+
+    /// Enforce the global limit for `function`, regardless of who is calling.
+    ///
+    /// No-op if no global limit is configured. Exemptions do not apply.
+    /// Panics with `"Rate limit exceeded"` when the shared budget is spent.
+    pub fn check_global(e: &Env, function: &Symbol) {
+        let cfg_key = (keys::GLOBAL_LIMIT_CONFIG, function.clone());
+        let Some((window_seconds, max_calls)) =
+            e.storage().instance().get::<_, (u64, u32)>(&cfg_key)
+        else {
+            return;
+        };
+
+        let now = TimeUtils::now(e);
+        if Self::get_mode(e, function) == RateMode::Sliding {
+            let state_key = (keys::GLOBAL_LIMIT_SLIDING, function.clone());
+            Self::consume_sliding(e, &state_key, now, window_seconds, max_calls);
+        } else {
+            let state_key = (keys::GLOBAL_LIMIT_STATE, function.clone());
+            Self::consume_fixed(e, &state_key, now, window_seconds, max_calls);
+        }
+    }
+
+    /// Count one call against a fixed window stored at `state_key`.
+    fn consume_fixed<K: IntoVal<Env, Val>>(
+        e: &Env,
+        state_key: &K,
+        now: u64,
+        window_seconds: u64,
+        max_calls: u32,
+    ) {
         let (mut window_start, mut count) = e
             .storage()
             .instance()
-            .get::<_, (u64, u32)>(&state_key)
+            .get::<_, (u64, u32)>(state_key)
             .unwrap_or((now, 0u32));
 
         // Reset window if expired
@@ -121,7 +227,37 @@ impl RateLimiter {
         // Persist updated state
         e.storage()
             .instance()
-            .set(&state_key, &(window_start, new_count));
+            .set(state_key, &(window_start, new_count));
+    }
+
+    /// Count one call against a sliding window stored at `state_key`.
+    fn consume_sliding<K: IntoVal<Env, Val>>(
+        e: &Env,
+        state_key: &K,
+        now: u64,
+        window_seconds: u64,
+        max_calls: u32,
+    ) {
+        let calls = e
+            .storage()
+            .instance()
+            .get::<_, Vec<u64>>(state_key)
+            .unwrap_or(Vec::new(e));
+
+        // Keep only calls inside the trailing window
+        let mut recent = Vec::new(e);
+        for at in calls.iter() {
+            if now.saturating_sub(at) < window_seconds {
+                recent.push_back(at);
+            }
+        }
+
+        if recent.len() >= max_calls {
+            panic!("Rate limit exceeded");
+        }
+
+        recent.push_back(now);
+        e.storage().instance().set(state_key, &recent);
     }
 }
 
@@ -130,8 +266,8 @@ mod tests {
     use super::*;
     use soroban_sdk::{
         contract, contractimpl, symbol_short,
-        testutils::{Address as TestAddress, Ledger},
-        Address, Env, Symbol,
+        testutils::{Address as TestAddress, Events, Ledger},
+        Address, Env, Symbol, TryFromVal,
     };
 
     #[contract]
@@ -151,6 +287,82 @@ mod tests {
         pub fn set_exempt(e: Env, who: Address, exempt: bool) {
             RateLimiter::set_exempt(&e, &who, exempt);
         }
+
+        pub fn configure_mode(e: Env, function: Symbol, mode: RateMode) {
+            RateLimiter::set_mode(&e, &function, mode);
+        }
+
+        pub fn global_call(e: Env, caller: Address) {
+            let fn_symbol = symbol_short!("global");
+            RateLimiter::check(&e, &caller, &fn_symbol);
+            RateLimiter::check_global(&e, &fn_symbol);
+        }
+
+        pub fn configure_global(e: Env, function: Symbol, window_seconds: u64, max_calls: u32) {
+            RateLimiter::set_global_limit(&e, &function, window_seconds, max_calls);
+        }
+    }
+
+    #[test]
+    fn test_global_limit_shared_across_callers() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestRateLimitContract);
+        let client = TestRateLimitContractClient::new(&env, &contract_id);
+        let alice = <Address as TestAddress>::generate(&env);
+        let bob = <Address as TestAddress>::generate(&env);
+
+        client.configure_global(&symbol_short!("global"), &60u64, &2u32);
+
+        client.global_call(&alice);
+        client.global_call(&bob);
+        // Neither caller is over a per-address limit, but the shared budget is gone
+        assert!(client.try_global_call(&alice).is_err());
+        assert!(client
+            .try_global_call(&<Address as TestAddress>::generate(&env))
+            .is_err());
+
+        env.ledger().with_mut(|l| l.timestamp += 60);
+        client.global_call(&bob);
+    }
+
+    /// Two calls at the end of one window, then two at the start of the next.
+    fn boundary_burst(env: &Env, client: &TestRateLimitContractClient, caller: &Address) -> bool {
+        client.configure_limit(&symbol_short!("limited"), &60u64, &2u32);
+
+        env.ledger().with_mut(|l| l.timestamp = 1000);
+        client.limited_call(caller);
+        env.ledger().with_mut(|l| l.timestamp = 1059);
+        client.limited_call(caller);
+
+        env.ledger().with_mut(|l| l.timestamp = 1060);
+        client.limited_call(caller);
+        client.try_limited_call(caller).is_ok()
+    }
+
+    #[test]
+    fn test_fixed_mode_allows_boundary_burst() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestRateLimitContract);
+        let client = TestRateLimitContractClient::new(&env, &contract_id);
+        let caller = <Address as TestAddress>::generate(&env);
+
+        // Three calls inside 1059..=1060 despite a limit of 2 per minute
+        assert!(boundary_burst(&env, &client, &caller));
+    }
+
+    #[test]
+    fn test_sliding_mode_blocks_boundary_burst() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestRateLimitContract);
+        let client = TestRateLimitContractClient::new(&env, &contract_id);
+        let caller = <Address as TestAddress>::generate(&env);
+
+        client.configure_mode(&symbol_short!("limited"), &RateMode::Sliding);
+        assert!(!boundary_burst(&env, &client, &caller));
+
+        // Once the 1059 call leaves the trailing window there is room again
+        env.ledger().with_mut(|l| l.timestamp = 1119);
+        client.limited_call(&caller);
     }
 
     #[test]
@@ -229,4 +441,32 @@ mod tests {
         client.limited_call(&caller);
         client.limited_call(&caller);
     }
+
+    #[test]
+    fn test_set_exempt_emits_on_change_only() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestRateLimitContract);
+        let client = TestRateLimitContractClient::new(&env, &contract_id);
+        let caller = <Address as TestAddress>::generate(&env);
+
+        client.set_exempt(&caller, &true);
+        let events = env.events().all();
+        assert_eq!(events.len(), 1);
+        let (_, topics, data) = events.get(0).unwrap();
+        assert_eq!(
+            topics,
+            (Symbol::new(&env, "RateLimitExemptSet"), caller.clone()).into_val(&env)
+        );
+        assert!(bool::try_from_val(&env, &data).unwrap());
+
+        // Re-setting the same flag is silent
+        client.set_exempt(&caller, &true);
+        assert_eq!(env.events().all().len(), 1);
+
+        client.set_exempt(&caller, &false);
+        let events = env.events().all();
+        assert_eq!(events.len(), 2);
+        let (_, _, data) = events.get(1).unwrap();
+        assert!(!bool::try_from_val(&env, &data).unwrap());
+    }
 }
@@ -1,6 +1,13 @@
 //! Storage helper utilities for common storage patterns
 
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+/// Ledgers per day at the ~5s close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+/// Long-lived entries are bumped once fewer than 30 days of TTL remain...
+pub const TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+/// ...back up to ~120 days, which stays below the network's max entry TTL.
+pub const TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
 
 /// Storage key constants
 pub mod keys {
@@ -141,6 +148,87 @@ impl Storage {
     pub fn has(e: &Env, key: &Symbol) -> bool {
         e.storage().instance().has(key)
     }
+
+    /// Append `value` to the `Vec` index stored under `key` (persistent storage)
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `key` - The storage key of the index
+    /// * `value` - The value to append
+    pub fn push_to_index<K, T>(e: &Env, key: &K, value: T)
+    where
+        K: IntoVal<Env, Val>,
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let mut index = Self::read_index::<K, T>(e, key);
+        index.push_back(value);
+        e.storage().persistent().set(key, &index);
+    }
+
+    /// Remove the first occurrence of `value` from the index under `key`
+    ///
+    /// The index entry is deleted once it becomes empty.
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `key` - The storage key of the index
+    /// * `value` - The value to remove
+    ///
+    /// # Returns
+    /// `true` if `value` was found and removed
+    pub fn remove_from_index<K, T>(e: &Env, key: &K, value: T) -> bool
+    where
+        K: IntoVal<Env, Val>,
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let mut index = Self::read_index::<K, T>(e, key);
+        let Some(position) = index.first_index_of(value) else {
+            return false;
+        };
+        index.remove(position);
+
+        if index.is_empty() {
+            e.storage().persistent().remove(key);
+        } else {
+            e.storage().persistent().set(key, &index);
+        }
+        true
+    }
+
+    /// Read up to `limit` entries of the index under `key`, starting at `start`
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `key` - The storage key of the index
+    /// * `start` - Position of the first entry to return
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    /// The requested page; empty once `start` is past the end
+    pub fn paginate_index<K, T>(e: &Env, key: &K, start: u32, limit: u32) -> Vec<T>
+    where
+        K: IntoVal<Env, Val>,
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let index = Self::read_index::<K, T>(e, key);
+        let len = index.len();
+        if start >= len {
+            return Vec::new(e);
+        }
+        index.slice(start..start.saturating_add(limit).min(len))
+    }
+
+    /// Read the whole index under `key` (empty if absent)
+    pub fn read_index<K, T>(e: &Env, key: &K) -> Vec<T>
+    where
+        K: IntoVal<Env, Val>,
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        e.storage()
+            .persistent()
+            .get::<K, Vec<T>>(key)
+            .unwrap_or(Vec::new(e))
+    }
 }
 
 #[cfg(test)]
@@ -208,4 +296,70 @@ mod tests {
             Storage::require_not_initialized(&env);
         });
     }
+
+    fn index_key(env: &Env) -> (Symbol, Address) {
+        (
+            soroban_sdk::symbol_short!("IDX"),
+            <soroban_sdk::Address as soroban_sdk::testutils::Address>::generate(env),
+        )
+    }
+
+    #[test]
+    fn test_push_to_index() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestContract);
+        let key = index_key(&env);
+
+        env.as_contract(&contract_id, || {
+            Storage::push_to_index(&env, &key, 1u32);
+            Storage::push_to_index(&env, &key, 2u32);
+            Storage::push_to_index(&env, &key, 3u32);
+
+            let index: Vec<u32> = Storage::read_index(&env, &key);
+            assert_eq!(index, soroban_sdk::vec![&env, 1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn test_remove_middle_from_index() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestContract);
+        let key = index_key(&env);
+
+        env.as_contract(&contract_id, || {
+            for id in [10u32, 20, 30] {
+                Storage::push_to_index(&env, &key, id);
+            }
+
+            assert!(Storage::remove_from_index(&env, &key, 20u32));
+            assert!(!Storage::remove_from_index(&env, &key, 20u32));
+            let index: Vec<u32> = Storage::read_index(&env, &key);
+            assert_eq!(index, soroban_sdk::vec![&env, 10, 30]);
+
+            // Emptying the index deletes the entry
+            Storage::remove_from_index(&env, &key, 10u32);
+            Storage::remove_from_index(&env, &key, 30u32);
+            assert!(!env.storage().persistent().has(&key));
+        });
+    }
+
+    #[test]
+    fn test_paginate_index() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, TestContract);
+        let key = index_key(&env);
+
+        env.as_contract(&contract_id, || {
+            for id in 0u32..7 {
+                Storage::push_to_index(&env, &key, id);
+            }
+
+            let page: Vec<u32> = Storage::paginate_index(&env, &key, 0, 3);
+            assert_eq!(page, soroban_sdk::vec![&env, 0, 1, 2]);
+            let page: Vec<u32> = Storage::paginate_index(&env, &key, 6, 3);
+            assert_eq!(page, soroban_sdk::vec![&env, 6]);
+            let page: Vec<u32> = Storage::paginate_index(&env, &key, 7, 3);
+            assert!(page.is_empty());
+        });
+    }
 }
@@ -2,6 +2,22 @@
 
 use soroban_sdk::Env;
 
+/// Number of seconds in one day
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Weekday indices returned by [`TimeUtils::weekday`]; bit `1 << day` of a
+/// skip mask marks that weekday as a non-settlement day.
+pub const MONDAY: u32 = 0;
+pub const TUESDAY: u32 = 1;
+pub const WEDNESDAY: u32 = 2;
+pub const THURSDAY: u32 = 3;
+pub const FRIDAY: u32 = 4;
+pub const SATURDAY: u32 = 5;
+pub const SUNDAY: u32 = 6;
+
+/// Skip mask with every weekday set
+pub const ALL_WEEKDAYS_MASK: u32 = 0b111_1111;
+
 /// Time utility functions for working with timestamps and durations
 pub struct TimeUtils;
 
@@ -18,8 +34,8 @@ impl TimeUtils {
     ///
     /// # Returns
     /// Number of seconds
-    pub fn days_to_seconds(days: u32) -> u64 {
-        days as u64 * 24 * 60 * 60
+    pub const fn days_to_seconds(days: u32) -> u64 {
+        days as u64 * SECONDS_PER_DAY
     }
 
     /// Convert hours to seconds
@@ -58,6 +74,47 @@ impl TimeUtils {
         current_time + duration_seconds
     }
 
+    /// Day of the week for a unix timestamp (UTC), `MONDAY` = 0 to `SUNDAY` = 6
+    ///
+    /// The unix epoch (1970-01-01) fell on a Thursday.
+    pub const fn weekday(timestamp: u64) -> u32 {
+        ((timestamp / SECONDS_PER_DAY + THURSDAY as u64) % 7) as u32
+    }
+
+    /// Move `timestamp` forward a whole day at a time until it lands on a
+    /// weekday not set in `skip_mask`
+    ///
+    /// # Arguments
+    /// * `timestamp` - The candidate timestamp
+    /// * `skip_mask` - Bit `1 << weekday` set for each day to skip
+    ///
+    /// # Returns
+    /// The adjusted timestamp; unchanged if `skip_mask` covers every day
+    pub fn skip_weekdays(timestamp: u64, skip_mask: u32) -> u64 {
+        if skip_mask & ALL_WEEKDAYS_MASK == ALL_WEEKDAYS_MASK {
+            return timestamp;
+        }
+        let mut adjusted = timestamp;
+        while skip_mask & (1 << Self::weekday(adjusted)) != 0 {
+            adjusted += SECONDS_PER_DAY;
+        }
+        adjusted
+    }
+
+    /// Calculate expiration like [`TimeUtils::calculate_expiration`], then
+    /// roll it forward past any weekday set in `skip_mask`
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `duration_days` - Duration in days
+    /// * `skip_mask` - Bit `1 << weekday` set for each non-settlement day
+    ///
+    /// # Returns
+    /// Expiration timestamp falling on a settlement day
+    pub fn calculate_expiration_with_calendar(e: &Env, duration_days: u32, skip_mask: u32) -> u64 {
+        Self::skip_weekdays(Self::calculate_expiration(e, duration_days), skip_mask)
+    }
+
     /// Check if a timestamp has expired (current time >= expiration)
     ///
     /// # Arguments
@@ -115,8 +172,22 @@ impl TimeUtils {
     ///
     /// # Returns
     /// Number of days
-    pub fn seconds_to_days(seconds: u64) -> u32 {
-        (seconds / (24 * 60 * 60)) as u32
+    pub const fn seconds_to_days(seconds: u64) -> u32 {
+        (seconds / SECONDS_PER_DAY) as u32
+    }
+
+    /// Number of whole days between two timestamps (rounded down)
+    ///
+    /// The order of the arguments does not matter.
+    ///
+    /// # Arguments
+    /// * `a` - First timestamp
+    /// * `b` - Second timestamp
+    ///
+    /// # Returns
+    /// Number of whole days between `a` and `b`
+    pub const fn days_between(a: u64, b: u64) -> u64 {
+        a.abs_diff(b) / SECONDS_PER_DAY
     }
 }
 
@@ -193,5 +264,81 @@ mod tests {
         assert_eq!(TimeUtils::seconds_to_days(86400), 1);
         assert_eq!(TimeUtils::seconds_to_days(172800), 2);
         assert_eq!(TimeUtils::seconds_to_days(3600), 0); // Less than a day
+        assert_eq!(TimeUtils::seconds_to_days(0), 0);
+        assert_eq!(TimeUtils::seconds_to_days(86400 * 2 + 86399), 2);
+    }
+
+    #[test]
+    fn test_days_to_seconds_zero() {
+        assert_eq!(TimeUtils::days_to_seconds(0), 0);
+    }
+
+    #[test]
+    fn test_days_between() {
+        assert_eq!(TimeUtils::days_between(0, 86400), 1);
+        assert_eq!(TimeUtils::days_between(1000, 1000 + 86400 * 30), 30);
+        // Argument order does not matter
+        assert_eq!(TimeUtils::days_between(86400 * 3, 0), 3);
+    }
+
+    #[test]
+    fn test_days_between_zero_days() {
+        assert_eq!(TimeUtils::days_between(5000, 5000), 0);
+        assert_eq!(TimeUtils::days_between(0, 86399), 0);
+    }
+
+    #[test]
+    fn test_days_between_sub_day_remainder() {
+        // 2 days and 23 hours rounds down to 2 days
+        let start = 1_000_000;
+        let end = start + 86400 * 2 + 3600 * 23;
+        assert_eq!(TimeUtils::days_between(start, end), 2);
+        assert_eq!(TimeUtils::days_between(end, start), 2);
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(TimeUtils::weekday(0), THURSDAY); // 1970-01-01
+        assert_eq!(TimeUtils::weekday(2 * SECONDS_PER_DAY), SATURDAY);
+        assert_eq!(TimeUtils::weekday(4 * SECONDS_PER_DAY - 1), SUNDAY);
+        assert_eq!(TimeUtils::weekday(4 * SECONDS_PER_DAY), MONDAY);
+        assert_eq!(TimeUtils::weekday(1_704_067_200), MONDAY); // 2024-01-01
+    }
+
+    #[test]
+    fn test_skip_weekdays() {
+        let weekend = (1 << SATURDAY) | (1 << SUNDAY);
+        let saturday = 2 * SECONDS_PER_DAY + 3600;
+        assert_eq!(
+            TimeUtils::skip_weekdays(saturday, weekend),
+            saturday + 2 * SECONDS_PER_DAY
+        );
+        let friday = SECONDS_PER_DAY;
+        assert_eq!(TimeUtils::skip_weekdays(friday, weekend), friday);
+        assert_eq!(TimeUtils::skip_weekdays(saturday, 0), saturday);
+        assert_eq!(
+            TimeUtils::skip_weekdays(saturday, ALL_WEEKDAYS_MASK),
+            saturday
+        );
+    }
+
+    #[test]
+    fn test_calculate_expiration_with_calendar() {
+        let env = Env::default();
+        env.ledger().with_mut(|l| {
+            l.timestamp = 0;
+        });
+
+        // Day 30 after the epoch is a Saturday
+        let weekend = (1 << SATURDAY) | (1 << SUNDAY);
+        let expiration = TimeUtils::calculate_expiration_with_calendar(&env, 30, weekend);
+        assert_eq!(expiration, TimeUtils::days_to_seconds(32));
+        assert_eq!(TimeUtils::weekday(expiration), MONDAY);
+    }
+
+    #[test]
+    fn test_days_round_trip() {
+        let secs = TimeUtils::days_to_seconds(365);
+        assert_eq!(TimeUtils::seconds_to_days(secs), 365);
     }
 }
@@ -1,6 +1,6 @@
 //! Validation utilities for common input validation patterns
 
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{panic_with_error, Address, Env, String};
 
 /// Validation utility functions
 pub struct Validation;
@@ -87,6 +87,42 @@ impl Validation {
         // This function is a placeholder for future validation needs
     }
 
+    /// Validate that two addresses are not the same
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `a` - First address
+    /// * `b` - Second address
+    /// * `error` - Contract error to panic with if the addresses are equal
+    ///
+    /// # Panics
+    /// Panics with `error` if `a == b`
+    pub fn require_distinct<E>(e: &Env, a: &Address, b: &Address, error: E)
+    where
+        E: Into<soroban_sdk::Error>,
+    {
+        if a == b {
+            panic_with_error!(e, error);
+        }
+    }
+
+    /// Validate that an optional address has been set
+    ///
+    /// # Arguments
+    /// * `opt` - The optional address (e.g. read from storage)
+    ///
+    /// # Returns
+    /// The contained address
+    ///
+    /// # Panics
+    /// Panics with "Invalid address" if `opt` is `None`
+    pub fn require_initialized_address(opt: &Option<Address>) -> Address {
+        match opt {
+            Some(address) => address.clone(),
+            None => panic!("Invalid address: not initialized"),
+        }
+    }
+
     /// Validate commitment type is one of the allowed values
     ///
     /// # Arguments
@@ -166,6 +202,7 @@ impl Validation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use soroban_sdk::testutils::Address as _;
 
     #[test]
     fn test_require_positive() {
@@ -241,4 +278,33 @@ mod tests {
     fn test_require_in_range_fails_above() {
         Validation::require_in_range(101, 0, 100, "value");
     }
+
+    #[test]
+    fn test_require_distinct() {
+        let e = Env::default();
+        let a = Address::generate(&e);
+        let b = Address::generate(&e);
+        Validation::require_distinct(&e, &a, &b, soroban_sdk::Error::from_contract_error(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]
+    fn test_require_distinct_fails_equal() {
+        let e = Env::default();
+        let a = Address::generate(&e);
+        Validation::require_distinct(&e, &a, &a, soroban_sdk::Error::from_contract_error(7));
+    }
+
+    #[test]
+    fn test_require_initialized_address() {
+        let e = Env::default();
+        let a = Address::generate(&e);
+        assert_eq!(Validation::require_initialized_address(&Some(a.clone())), a);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid address")]
+    fn test_require_initialized_address_fails_none() {
+        Validation::require_initialized_address(&None);
+    }
 }
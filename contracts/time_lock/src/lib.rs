@@ -1,14 +1,22 @@
 #![no_std]
 
+use shared_utils::TimeUtils;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
+    Symbol, TryFromVal, Val, Vec,
 };
 
 /// Minimum delay for any timelock action (1 day in seconds)
-const MIN_DELAY: u64 = 86400;
+const MIN_DELAY: u64 = TimeUtils::days_to_seconds(1);
 
 /// Maximum delay allowed (30 days in seconds)
-const MAX_DELAY: u64 = 2592000;
+const MAX_DELAY: u64 = TimeUtils::days_to_seconds(30);
+
+/// Largest page returned by the paginated action getters
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Cap on the full-scan getters; they only look at the most recent actions
+const MAX_FULL_SCAN: u32 = 100;
 
 /// Different action types with their specific delay requirements
 #[contracttype]
@@ -21,13 +29,22 @@ pub enum ActionType {
 }
 
 impl ActionType {
-    /// Get the minimum delay for each action type
-    pub fn get_delay(&self) -> u64 {
+    /// Get the minimum delay for this action type, preferring an admin
+    /// override set via `set_action_delay` over the built-in default
+    pub fn get_delay(&self, env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&StorageKey::ActionDelay(*self))
+            .unwrap_or_else(|| self.default_delay())
+    }
+
+    /// Built-in minimum delay for each action type
+    pub fn default_delay(&self) -> u64 {
         match self {
-            ActionType::AdminChange => 172800,    // 2 days
-            ActionType::ParameterChange => 86400, // 1 day
-            ActionType::Upgrade => 259200,        // 3 days
-            ActionType::FeeChange => 86400,       // 1 day
+            ActionType::AdminChange => TimeUtils::days_to_seconds(2),
+            ActionType::ParameterChange => TimeUtils::days_to_seconds(1),
+            ActionType::Upgrade => TimeUtils::days_to_seconds(3),
+            ActionType::FeeChange => TimeUtils::days_to_seconds(1),
         }
     }
 }
@@ -39,6 +56,8 @@ pub struct QueuedAction {
     pub id: u64,
     pub action_type: ActionType,
     pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
     pub data: String,
     pub queued_at: u64,
     pub executable_at: u64,
@@ -61,6 +80,11 @@ pub enum Error {
     ActionAlreadyCancelled = 8,
     CannotCancelExecutedAction = 9,
     InvalidActionType = 10,
+    NotApprover = 11,
+    AlreadyApproved = 12,
+    InsufficientApprovals = 13,
+    InvalidThreshold = 14,
+    DelayChangeNotApproved = 15,
 }
 
 /// Storage keys
@@ -70,6 +94,15 @@ pub enum StorageKey {
     ActionCounter,
     Action(u64),
     ActionIds,
+    ActionDelay(ActionType),
+    /// Addresses allowed to approve `Upgrade` actions
+    Approvers,
+    /// Approvals an `Upgrade` action needs before execution (0 = none)
+    ApprovalThreshold,
+    /// Approvers that signed off on an action
+    Approvals(u64),
+    /// Executed delay-change actions already applied by `set_action_delay`
+    ConsumedAction(u64),
 }
 
 #[contract]
@@ -99,6 +132,8 @@ impl TimelockContract {
     /// # Arguments
     /// * `action_type` - Type of action being queued
     /// * `target` - Target address for the action
+    /// * `function` - Function to be called on `target`
+    /// * `args` - Arguments for `function`
     /// * `data` - Action data/parameters as string
     /// * `delay` - Custom delay in seconds (must be >= action type minimum)
     ///
@@ -108,6 +143,8 @@ impl TimelockContract {
         env: Env,
         action_type: ActionType,
         target: Address,
+        function: Symbol,
+        args: Vec<Val>,
         data: String,
         delay: u64,
     ) -> Result<u64, Error> {
@@ -115,7 +152,7 @@ impl TimelockContract {
         admin.require_auth();
 
         // Validate delay
-        let min_delay = action_type.get_delay();
+        let min_delay = action_type.get_delay(&env);
         if delay < min_delay {
             return Err(Error::DelayTooShort);
         }
@@ -142,6 +179,8 @@ impl TimelockContract {
             id: counter,
             action_type,
             target: target.clone(),
+            function: function.clone(),
+            args,
             data: data.clone(),
             queued_at: current_time,
             executable_at,
@@ -168,14 +207,15 @@ impl TimelockContract {
         // Emit event
         env.events().publish(
             (symbol_short!("queued"), counter),
-            (action_type, target, data, executable_at),
+            (action_type, target, function, data, executable_at),
         );
 
         Ok(counter)
     }
 
     /// Execute a queued action after the delay has passed
-    /// Anyone can execute a queued action once the delay has passed
+    /// Anyone can execute a queued action once the delay has passed;
+    /// `Upgrade` actions also need `get_threshold` approvals
     ///
     /// # Arguments
     /// * `action_id` - ID of the action to execute
@@ -202,6 +242,13 @@ impl TimelockContract {
             return Err(Error::DelayNotMet);
         }
 
+        // Upgrades additionally need the configured number of approvals
+        if action.action_type == ActionType::Upgrade
+            && count_valid_approvals(&env, action_id) < Self::get_threshold(env.clone())
+        {
+            return Err(Error::InsufficientApprovals);
+        }
+
         // Mark as executed
         action.executed = true;
         env.storage()
@@ -257,6 +304,32 @@ impl TimelockContract {
         Ok(())
     }
 
+    /// Cancel every pending action in one call (admin-only emergency stop)
+    ///
+    /// Only the IDs returned by `get_all_actions` are scanned; use
+    /// `cancel_all_pending_page` to reach older actions. Emits one
+    /// `AllCancelled` event instead of a `cancelled` event per action.
+    ///
+    /// # Returns
+    /// * Number of actions cancelled
+    pub fn cancel_all_pending(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        let ids = Self::get_all_actions(env.clone());
+        Ok(cancel_pending(&env, &admin, &ids))
+    }
+
+    /// Cancel every pending action among the IDs in `get_actions_page(start, limit)`
+    pub fn cancel_all_pending_page(
+        env: Env,
+        admin: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        let ids = page(&read_action_ids(&env), start, limit);
+        Ok(cancel_pending(&env, &admin, &ids))
+    }
+
     /// Get details of a queued action
     ///
     /// # Arguments
@@ -271,62 +344,113 @@ impl TimelockContract {
             .ok_or(Error::ActionNotFound)
     }
 
-    /// Get all queued action IDs
+    /// Get the timestamp from which an action may be executed
+    ///
+    /// # Arguments
+    /// * `action_id` - ID of the action
+    pub fn get_executable_at(env: Env, action_id: u64) -> Result<u64, Error> {
+        let action: QueuedAction = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Action(action_id))
+            .ok_or(Error::ActionNotFound)?;
+        Ok(action.executable_at)
+    }
+
+    /// Whether `execute_action` would succeed for an action right now
+    ///
+    /// False for unknown, executed or cancelled actions, before the delay has
+    /// passed, and for `Upgrade` actions still short of their approvals.
+    pub fn is_executable_now(env: Env, action_id: u64) -> bool {
+        let Some(action) = env
+            .storage()
+            .persistent()
+            .get::<_, QueuedAction>(&StorageKey::Action(action_id))
+        else {
+            return false;
+        };
+        if action.executed || action.cancelled {
+            return false;
+        }
+        if env.ledger().timestamp() < action.executable_at {
+            return false;
+        }
+        action.action_type != ActionType::Upgrade
+            || count_valid_approvals(&env, action_id) >= Self::get_threshold(env.clone())
+    }
+
+    /// Get the call an action will make: `(target, function, args)`
+    ///
+    /// Lets reviewers inspect exactly what was queued before the delay elapses.
+    pub fn get_action_call(env: Env, action_id: u64) -> Result<(Address, Symbol, Vec<Val>), Error> {
+        let action: QueuedAction = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Action(action_id))
+            .ok_or(Error::ActionNotFound)?;
+        Ok((action.target, action.function, action.args))
+    }
+
+    /// Get queued action IDs
+    ///
+    /// Returns at most the `MAX_FULL_SCAN` most recently queued IDs; use
+    /// `get_actions_page` to walk the full log.
     ///
     /// # Returns
     /// * Vector of action IDs
     pub fn get_all_actions(env: Env) -> Vec<u64> {
-        env.storage()
-            .instance()
-            .get(&StorageKey::ActionIds)
-            .unwrap_or(Vec::new(&env))
+        let all_ids = read_action_ids(&env);
+        let len = all_ids.len();
+        if len <= MAX_FULL_SCAN {
+            return all_ids;
+        }
+        all_ids.slice(len - MAX_FULL_SCAN..len)
+    }
+
+    /// Get a page of queued action IDs in queue order
+    ///
+    /// # Arguments
+    /// * `start` - Index of the first ID to return
+    /// * `limit` - Maximum number of IDs to return (capped at `MAX_PAGE_SIZE`)
+    ///
+    /// # Returns
+    /// * Vector of action IDs; empty once `start` is past the end
+    pub fn get_actions_page(env: Env, start: u32, limit: u32) -> Vec<u64> {
+        page(&read_action_ids(&env), start, limit)
     }
 
     /// Get pending actions (not executed and not cancelled)
     ///
+    /// Only the IDs returned by `get_all_actions` are scanned.
+    ///
     /// # Returns
     /// * Vector of pending action IDs
     pub fn get_pending_actions(env: Env) -> Vec<u64> {
-        let all_ids: Vec<u64> = Self::get_all_actions(env.clone());
-        let mut pending = Vec::new(&env);
-
-        for id in all_ids.iter() {
-            if let Some(action) = env
-                .storage()
-                .persistent()
-                .get::<StorageKey, QueuedAction>(&StorageKey::Action(id))
-            {
-                if !action.executed && !action.cancelled {
-                    pending.push_back(id);
-                }
-            }
-        }
+        let ids = Self::get_all_actions(env.clone());
+        filter_pending(&env, &ids)
+    }
 
-        pending
+    /// Get pending actions among the IDs in `get_actions_page(start, limit)`
+    pub fn get_pending_actions_page(env: Env, start: u32, limit: u32) -> Vec<u64> {
+        let ids = page(&read_action_ids(&env), start, limit);
+        filter_pending(&env, &ids)
     }
 
     /// Get executable actions (pending and past delay)
     ///
+    /// Only the IDs returned by `get_all_actions` are scanned.
+    ///
     /// # Returns
     /// * Vector of executable action IDs
     pub fn get_executable_actions(env: Env) -> Vec<u64> {
-        let pending = Self::get_pending_actions(env.clone());
-        let mut executable = Vec::new(&env);
-        let current_time = env.ledger().timestamp();
-
-        for id in pending.iter() {
-            if let Some(action) = env
-                .storage()
-                .persistent()
-                .get::<StorageKey, QueuedAction>(&StorageKey::Action(id))
-            {
-                if current_time >= action.executable_at {
-                    executable.push_back(id);
-                }
-            }
-        }
+        let ids = Self::get_all_actions(env.clone());
+        filter_executable(&env, &ids)
+    }
 
-        executable
+    /// Get executable actions among the IDs in `get_actions_page(start, limit)`
+    pub fn get_executable_actions_page(env: Env, start: u32, limit: u32) -> Vec<u64> {
+        let ids = page(&read_action_ids(&env), start, limit);
+        filter_executable(&env, &ids)
     }
 
     /// Get the current admin address
@@ -345,8 +469,156 @@ impl TimelockContract {
     /// # Returns
     /// * Minimum delay in seconds
     pub fn get_min_delay(env: Env, action_type: ActionType) -> u64 {
-        let _ = env;
-        action_type.get_delay()
+        action_type.get_delay(&env)
+    }
+
+    /// Override the minimum delay for an action type
+    ///
+    /// The change is itself timelocked: `action_id` must be an executed,
+    /// unused `ParameterChange` action calling `set_action_delay` on this
+    /// contract with args `[action_type, seconds]`.
+    ///
+    /// # Arguments
+    /// * `admin` - Current admin (must authorize)
+    /// * `action_type` - Type of action
+    /// * `seconds` - New minimum delay, within `MIN_DELAY..=MAX_DELAY`
+    /// * `action_id` - Executed timelock action approving this change
+    pub fn set_action_delay(
+        env: Env,
+        admin: Address,
+        action_type: ActionType,
+        seconds: u64,
+        action_id: u64,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        if seconds < MIN_DELAY {
+            return Err(Error::DelayTooShort);
+        }
+        if seconds > MAX_DELAY {
+            return Err(Error::DelayTooLong);
+        }
+        consume_delay_change(&env, action_id, action_type, seconds)?;
+
+        env.storage()
+            .instance()
+            .set(&StorageKey::ActionDelay(action_type), &seconds);
+
+        env.events()
+            .publish((symbol_short!("delay_set"), action_type), seconds);
+
+        Ok(())
+    }
+
+    /// Approve a queued action as one of the configured approvers
+    ///
+    /// Approvals only gate `Upgrade` actions, which need at least
+    /// `get_threshold` of them before `execute_action` proceeds.
+    ///
+    /// # Arguments
+    /// * `approver` - A configured approver (must authorize)
+    /// * `action_id` - ID of the pending action
+    pub fn approve_action(env: Env, approver: Address, action_id: u64) -> Result<(), Error> {
+        approver.require_auth();
+        if !Self::get_approvers(env.clone()).contains(&approver) {
+            return Err(Error::NotApprover);
+        }
+
+        let action: QueuedAction = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Action(action_id))
+            .ok_or(Error::ActionNotFound)?;
+        if action.executed {
+            return Err(Error::ActionAlreadyExecuted);
+        }
+        if action.cancelled {
+            return Err(Error::ActionCancelled);
+        }
+
+        let mut approvals = Self::get_approvals(env.clone(), action_id);
+        if approvals.contains(&approver) {
+            return Err(Error::AlreadyApproved);
+        }
+        approvals.push_back(approver.clone());
+        env.storage()
+            .persistent()
+            .set(&StorageKey::Approvals(action_id), &approvals);
+
+        env.events()
+            .publish((symbol_short!("approved"), action_id), approver);
+
+        Ok(())
+    }
+
+    /// Replace the approver set
+    ///
+    /// # Arguments
+    /// * `admin` - Current admin (must authorize)
+    /// * `approvers` - Distinct addresses; at least as many as the threshold
+    pub fn set_approvers(env: Env, admin: Address, approvers: Vec<Address>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        let mut distinct: Vec<Address> = Vec::new(&env);
+        for approver in approvers.iter() {
+            if !distinct.contains(&approver) {
+                distinct.push_back(approver);
+            }
+        }
+        if distinct.len() < Self::get_threshold(env.clone()) {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&StorageKey::Approvers, &distinct);
+        env.events()
+            .publish((symbol_short!("appr_set"),), distinct.len());
+
+        Ok(())
+    }
+
+    /// Set how many approvals an `Upgrade` action needs (0 disables the check)
+    ///
+    /// # Arguments
+    /// * `admin` - Current admin (must authorize)
+    /// * `threshold` - At most the number of configured approvers
+    pub fn set_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if threshold > Self::get_approvers(env.clone()).len() {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&StorageKey::ApprovalThreshold, &threshold);
+        env.events().publish((symbol_short!("thresh"),), threshold);
+
+        Ok(())
+    }
+
+    /// Get the configured approvers
+    pub fn get_approvers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&StorageKey::Approvers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the number of approvals an `Upgrade` action needs
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&StorageKey::ApprovalThreshold)
+            .unwrap_or(0)
+    }
+
+    /// Get the approvers that signed off on an action
+    pub fn get_approvals(env: Env, action_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Approvals(action_id))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get the maximum allowed delay
@@ -370,5 +642,134 @@ impl TimelockContract {
     }
 }
 
+fn read_action_ids(env: &Env) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&StorageKey::ActionIds)
+        .unwrap_or(Vec::new(env))
+}
+
+fn page(ids: &Vec<u64>, start: u32, limit: u32) -> Vec<u64> {
+    let len = ids.len();
+    if start >= len {
+        return Vec::new(ids.env());
+    }
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+    ids.slice(start..end)
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored_admin: Address = env.storage().instance().get(&StorageKey::Admin).unwrap();
+    if *admin != stored_admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Require `action_id` to be an executed, unused `ParameterChange` action
+/// calling `set_action_delay(action_type, seconds)` on this contract, and
+/// mark it consumed.
+fn consume_delay_change(
+    env: &Env,
+    action_id: u64,
+    action_type: ActionType,
+    seconds: u64,
+) -> Result<(), Error> {
+    let consumed_key = StorageKey::ConsumedAction(action_id);
+    if env.storage().persistent().has(&consumed_key) {
+        return Err(Error::DelayChangeNotApproved);
+    }
+    let action: QueuedAction = env
+        .storage()
+        .persistent()
+        .get(&StorageKey::Action(action_id))
+        .ok_or(Error::DelayChangeNotApproved)?;
+
+    let arg_type = action
+        .args
+        .get(0)
+        .and_then(|val| ActionType::try_from_val(env, &val).ok());
+    let arg_seconds = action
+        .args
+        .get(1)
+        .and_then(|val| u64::try_from_val(env, &val).ok());
+    if !action.executed
+        || action.cancelled
+        || action.action_type != ActionType::ParameterChange
+        || action.target != env.current_contract_address()
+        || action.function != Symbol::new(env, "set_action_delay")
+        || arg_type != Some(action_type)
+        || arg_seconds != Some(seconds)
+    {
+        return Err(Error::DelayChangeNotApproved);
+    }
+
+    env.storage().persistent().set(&consumed_key, &true);
+    Ok(())
+}
+
+/// Approvals on `action_id` from addresses still in the approver set
+fn count_valid_approvals(env: &Env, action_id: u64) -> u32 {
+    let approvers = TimelockContract::get_approvers(env.clone());
+    let approvals = TimelockContract::get_approvals(env.clone(), action_id);
+    approvals
+        .iter()
+        .filter(|approver| approvers.contains(approver))
+        .count() as u32
+}
+
+fn filter_pending(env: &Env, ids: &Vec<u64>) -> Vec<u64> {
+    let mut pending = Vec::new(env);
+    for id in ids.iter() {
+        if let Some(action) = env
+            .storage()
+            .persistent()
+            .get::<StorageKey, QueuedAction>(&StorageKey::Action(id))
+        {
+            if !action.executed && !action.cancelled {
+                pending.push_back(id);
+            }
+        }
+    }
+    pending
+}
+
+/// Mark each pending action in `ids` cancelled and emit one `AllCancelled`
+/// event carrying the count
+fn cancel_pending(env: &Env, admin: &Address, ids: &Vec<u64>) -> u32 {
+    let mut cancelled = 0u32;
+    for id in filter_pending(env, ids).iter() {
+        let key = StorageKey::Action(id);
+        if let Some(mut action) = env.storage().persistent().get::<_, QueuedAction>(&key) {
+            action.cancelled = true;
+            env.storage().persistent().set(&key, &action);
+            cancelled += 1;
+        }
+    }
+    env.events().publish(
+        (Symbol::new(env, "AllCancelled"),),
+        (admin.clone(), cancelled),
+    );
+    cancelled
+}
+
+fn filter_executable(env: &Env, ids: &Vec<u64>) -> Vec<u64> {
+    let mut executable = Vec::new(env);
+    let current_time = env.ledger().timestamp();
+    for id in ids.iter() {
+        if let Some(action) = env
+            .storage()
+            .persistent()
+            .get::<StorageKey, QueuedAction>(&StorageKey::Action(id))
+        {
+            if !action.executed && !action.cancelled && current_time >= action.executable_at {
+                executable.push_back(id);
+            }
+        }
+    }
+    executable
+}
+
 #[cfg(test)]
 mod test;
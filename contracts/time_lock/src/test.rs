@@ -2,8 +2,9 @@
 
 use super::*;
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Ledger},
-    Address, Env, String,
+    vec, Address, Env, IntoVal, String, Symbol,
 };
 
 fn create_test_env() -> (Env, Address, Address) {
@@ -49,7 +50,14 @@ fn test_queue_action_success() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64; // 1 day
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     assert_eq!(action_id, 1);
     assert_eq!(client.get_action_count(), 1);
@@ -76,9 +84,30 @@ fn test_queue_multiple_actions() {
     let data2 = String::from_str(&env, "action2");
     let data3 = String::from_str(&env, "action3");
 
-    let id1 = client.queue_action(&ActionType::ParameterChange, &target, &data1, &86400);
-    let id2 = client.queue_action(&ActionType::FeeChange, &target, &data2, &86400);
-    let id3 = client.queue_action(&ActionType::Upgrade, &target, &data3, &259200);
+    let id1 = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data1,
+        &86400,
+    );
+    let id2 = client.queue_action(
+        &ActionType::FeeChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data2,
+        &86400,
+    );
+    let id3 = client.queue_action(
+        &ActionType::Upgrade,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data3,
+        &259200,
+    );
 
     assert_eq!(id1, 1);
     assert_eq!(id2, 2);
@@ -104,6 +133,8 @@ fn test_delay_validation_too_short() {
     let result = client.try_queue_action(
         &ActionType::AdminChange,
         &target,
+        &symbol_short!("update"),
+        &vec![&env],
         &data,
         &86400, // 1 day - too short
     );
@@ -126,6 +157,8 @@ fn test_delay_validation_too_long() {
     let result = client.try_queue_action(
         &ActionType::ParameterChange,
         &target,
+        &symbol_short!("update"),
+        &vec![&env],
         &data,
         &2592001, // 30 days + 1 second - too long
     );
@@ -145,7 +178,14 @@ fn test_execute_action_success() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     // Fast forward time past the delay
     env.ledger().with_mut(|li| {
@@ -171,7 +211,14 @@ fn test_execute_action_before_delay() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     // Try to execute before delay
     let result = client.try_execute_action(&action_id);
@@ -190,7 +237,14 @@ fn test_execute_already_executed_action() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     // Fast forward and execute
     env.ledger().with_mut(|li| {
@@ -216,7 +270,14 @@ fn test_cancel_action_success() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     client.cancel_action(&action_id);
 
@@ -236,7 +297,14 @@ fn test_cancel_already_cancelled_action() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     client.cancel_action(&action_id);
 
@@ -257,7 +325,14 @@ fn test_cancel_executed_action() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     // Fast forward and execute
     env.ledger().with_mut(|li| {
@@ -283,7 +358,14 @@ fn test_execute_cancelled_action() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     client.cancel_action(&action_id);
 
@@ -309,9 +391,30 @@ fn test_get_pending_actions() {
     let data = String::from_str(&env, "test_data");
 
     // Queue 3 actions
-    let id1 = client.queue_action(&ActionType::ParameterChange, &target, &data, &86400);
-    let id2 = client.queue_action(&ActionType::FeeChange, &target, &data, &86400);
-    let id3 = client.queue_action(&ActionType::Upgrade, &target, &data, &259200);
+    let id1 = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    );
+    let id2 = client.queue_action(
+        &ActionType::FeeChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    );
+    let id3 = client.queue_action(
+        &ActionType::Upgrade,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &259200,
+    );
 
     let pending = client.get_pending_actions();
     assert_eq!(pending.len(), 3);
@@ -347,9 +450,30 @@ fn test_get_executable_actions() {
     let data = String::from_str(&env, "test_data");
 
     // Queue actions with different delays
-    let id1 = client.queue_action(&ActionType::ParameterChange, &target, &data, &86400); // 1 day
-    let id2 = client.queue_action(&ActionType::AdminChange, &target, &data, &172800); // 2 days
-    let id3 = client.queue_action(&ActionType::Upgrade, &target, &data, &259200); // 3 days
+    let id1 = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    ); // 1 day
+    let id2 = client.queue_action(
+        &ActionType::AdminChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &172800,
+    ); // 2 days
+    let id3 = client.queue_action(
+        &ActionType::Upgrade,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &259200,
+    ); // 3 days
 
     // Initially no actions are executable
     let executable = client.get_executable_actions();
@@ -425,10 +549,38 @@ fn test_complex_workflow() {
     let data = String::from_str(&env, "test_data");
 
     // Queue multiple actions of different types
-    let param_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &86400);
-    let fee_id = client.queue_action(&ActionType::FeeChange, &target, &data, &86400);
-    let admin_id = client.queue_action(&ActionType::AdminChange, &target, &data, &172800);
-    let upgrade_id = client.queue_action(&ActionType::Upgrade, &target, &data, &259200);
+    let param_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    );
+    let fee_id = client.queue_action(
+        &ActionType::FeeChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    );
+    let admin_id = client.queue_action(
+        &ActionType::AdminChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &172800,
+    );
+    let upgrade_id = client.queue_action(
+        &ActionType::Upgrade,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &259200,
+    );
 
     // Verify all are pending
     let pending = client.get_pending_actions();
@@ -491,7 +643,14 @@ fn test_edge_case_exact_delay_time() {
     let data = String::from_str(&env, "test_data");
     let delay = 86400u64;
 
-    let action_id = client.queue_action(&ActionType::ParameterChange, &target, &data, &delay);
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &delay,
+    );
 
     // Fast forward to exactly the delay time (not past it)
     env.ledger().with_mut(|li| {
@@ -511,3 +670,541 @@ fn test_max_delay_constant() {
 
     assert_eq!(client.get_max_delay(), 2592000); // 30 days
 }
+
+#[test]
+fn test_get_actions_page() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let data = String::from_str(&env, "paged");
+    for _ in 0..25 {
+        client.queue_action(
+            &ActionType::ParameterChange,
+            &target,
+            &symbol_short!("update"),
+            &vec![&env],
+            &data,
+            &86400,
+        );
+    }
+
+    let first = client.get_actions_page(&0, &10);
+    assert_eq!(first.len(), 10);
+    assert_eq!(first.get(0).unwrap(), 1);
+    assert_eq!(first.get(9).unwrap(), 10);
+
+    let second = client.get_actions_page(&10, &10);
+    assert_eq!(second.len(), 10);
+    assert_eq!(second.get(0).unwrap(), 11);
+
+    let last = client.get_actions_page(&20, &10);
+    assert_eq!(last.len(), 5);
+    assert_eq!(last.get(4).unwrap(), 25);
+
+    assert_eq!(client.get_actions_page(&25, &10).len(), 0);
+
+    // Range-limited scanners only consider IDs in the page
+    client.cancel_action(&3);
+    let pending = client.get_pending_actions_page(&0, &10);
+    assert_eq!(pending.len(), 9);
+    assert!(!pending.contains(3));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 86400;
+    });
+    let executable = client.get_executable_actions_page(&20, &10);
+    assert_eq!(executable.len(), 5);
+    assert_eq!(executable.get(0).unwrap(), 21);
+}
+
+#[test]
+fn test_get_actions_page_caps_limit() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let data = String::from_str(&env, "paged");
+    for _ in 0..(MAX_PAGE_SIZE + 5) {
+        client.queue_action(
+            &ActionType::ParameterChange,
+            &target,
+            &symbol_short!("update"),
+            &vec![&env],
+            &data,
+            &86400,
+        );
+    }
+
+    assert_eq!(client.get_actions_page(&0, &u32::MAX).len(), MAX_PAGE_SIZE);
+    assert_eq!(client.get_actions_page(&MAX_PAGE_SIZE, &u32::MAX).len(), 5);
+}
+
+// Queue and execute the self-targeted action approving a delay change
+fn approve_delay_change(
+    env: &Env,
+    client: &TimelockContractClient,
+    action_type: ActionType,
+    seconds: u64,
+) -> u64 {
+    let id = client.queue_action(
+        &ActionType::ParameterChange,
+        &client.address,
+        &Symbol::new(env, "set_action_delay"),
+        &vec![env, action_type.into_val(env), seconds.into_val(env)],
+        &String::from_str(env, "delay change"),
+        &86400,
+    );
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.execute_action(&id);
+    id
+}
+
+#[test]
+fn test_set_action_delay_override() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    // Default AdminChange minimum is 2 days
+    assert_eq!(client.get_min_delay(&ActionType::AdminChange), 172800);
+    let approval = approve_delay_change(&env, &client, ActionType::AdminChange, 86400);
+    client.set_action_delay(&admin, &ActionType::AdminChange, &86400, &approval);
+    assert_eq!(client.get_min_delay(&ActionType::AdminChange), 86400);
+
+    // A 1-day AdminChange is now accepted
+    let data = String::from_str(&env, "new_admin");
+    let id = client.queue_action(
+        &ActionType::AdminChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &86400,
+    );
+    assert_eq!(
+        client.get_action(&id).executable_at,
+        env.ledger().timestamp() + 86400
+    );
+
+    // Other types keep their defaults
+    assert_eq!(client.get_min_delay(&ActionType::Upgrade), 259200);
+}
+
+#[test]
+fn test_queue_action_below_overridden_delay() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let approval = approve_delay_change(&env, &client, ActionType::ParameterChange, 5 * 86400);
+    client.set_action_delay(
+        &admin,
+        &ActionType::ParameterChange,
+        &(5 * 86400),
+        &approval,
+    );
+
+    let data = String::from_str(&env, "param");
+    let result = client.try_queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &data,
+        &(2 * 86400),
+    );
+    assert_eq!(result, Err(Ok(Error::DelayTooShort)));
+}
+
+#[test]
+fn test_set_action_delay_bounds_and_auth() {
+    let (env, admin, _) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let result = client.try_set_action_delay(&admin, &ActionType::FeeChange, &(MIN_DELAY - 1), &0);
+    assert_eq!(result, Err(Ok(Error::DelayTooShort)));
+
+    let result = client.try_set_action_delay(&admin, &ActionType::FeeChange, &(MAX_DELAY + 1), &0);
+    assert_eq!(result, Err(Ok(Error::DelayTooLong)));
+
+    let stranger = Address::generate(&env);
+    let result = client.try_set_action_delay(&stranger, &ActionType::FeeChange, &MIN_DELAY, &0);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    assert_eq!(client.get_min_delay(&ActionType::FeeChange), 86400);
+}
+
+#[test]
+fn test_set_action_delay_requires_matching_executed_action() {
+    let (env, admin, _) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    // No approval at all
+    let result = client.try_set_action_delay(&admin, &ActionType::FeeChange, &MIN_DELAY, &1);
+    assert_eq!(result, Err(Ok(Error::DelayChangeNotApproved)));
+
+    // Queued but not yet executed
+    let pending = client.queue_action(
+        &ActionType::ParameterChange,
+        &contract_id,
+        &Symbol::new(&env, "set_action_delay"),
+        &vec![
+            &env,
+            ActionType::FeeChange.into_val(&env),
+            (2 * MIN_DELAY).into_val(&env),
+        ],
+        &String::from_str(&env, "delay change"),
+        &86400,
+    );
+    let result =
+        client.try_set_action_delay(&admin, &ActionType::FeeChange, &(2 * MIN_DELAY), &pending);
+    assert_eq!(result, Err(Ok(Error::DelayChangeNotApproved)));
+
+    let approval = approve_delay_change(&env, &client, ActionType::FeeChange, 2 * MIN_DELAY);
+    // The approval is bound to its action type and value
+    let result =
+        client.try_set_action_delay(&admin, &ActionType::Upgrade, &(2 * MIN_DELAY), &approval);
+    assert_eq!(result, Err(Ok(Error::DelayChangeNotApproved)));
+    let result = client.try_set_action_delay(&admin, &ActionType::FeeChange, &MIN_DELAY, &approval);
+    assert_eq!(result, Err(Ok(Error::DelayChangeNotApproved)));
+
+    client.set_action_delay(&admin, &ActionType::FeeChange, &(2 * MIN_DELAY), &approval);
+    assert_eq!(client.get_min_delay(&ActionType::FeeChange), 2 * MIN_DELAY);
+
+    // Each approval applies once
+    let result =
+        client.try_set_action_delay(&admin, &ActionType::FeeChange, &(2 * MIN_DELAY), &approval);
+    assert_eq!(result, Err(Ok(Error::DelayChangeNotApproved)));
+}
+
+#[test]
+fn test_get_action_call_matches_queued_call() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let new_fee: u32 = 250;
+    let args = vec![&env, admin.into_val(&env), new_fee.into_val(&env)];
+    let id = client.queue_action(
+        &ActionType::FeeChange,
+        &target,
+        &symbol_short!("set_fee"),
+        &args,
+        &String::from_str(&env, "raise fee to 2.5%"),
+        &86400,
+    );
+
+    let (call_target, function, call_args) = client.get_action_call(&id);
+    assert_eq!(call_target, target);
+    assert_eq!(function, symbol_short!("set_fee"));
+    assert_eq!(call_args.len(), 2);
+    let arg_admin: Address = call_args.get(0).unwrap().into_val(&env);
+    let arg_fee: u32 = call_args.get(1).unwrap().into_val(&env);
+    assert_eq!(arg_admin, admin);
+    assert_eq!(arg_fee, new_fee);
+
+    assert_eq!(
+        client.try_get_action_call(&999),
+        Err(Ok(Error::ActionNotFound))
+    );
+}
+
+fn setup_upgrade_with_approvers(
+    env: &Env,
+    admin: &Address,
+    target: &Address,
+) -> (TimelockContractClient<'static>, Vec<Address>, u64) {
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(env, &contract_id);
+    client.initialize(admin);
+    env.mock_all_auths();
+
+    let approvers = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.set_approvers(admin, &approvers);
+    client.set_threshold(admin, &2);
+
+    let delay = ActionType::Upgrade.default_delay();
+    let action_id = client.queue_action(
+        &ActionType::Upgrade,
+        target,
+        &symbol_short!("upgrade"),
+        &vec![env],
+        &String::from_str(env, "upgrade"),
+        &delay,
+    );
+    env.ledger().with_mut(|li| {
+        li.timestamp += delay;
+    });
+    (client, approvers, action_id)
+}
+
+#[test]
+fn test_upgrade_blocked_below_approval_threshold() {
+    let (env, admin, target) = create_test_env();
+    let (client, approvers, action_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    client.approve_action(&approvers.get(0).unwrap(), &action_id);
+    // The same approver cannot count twice
+    assert_eq!(
+        client.try_approve_action(&approvers.get(0).unwrap(), &action_id),
+        Err(Ok(Error::AlreadyApproved))
+    );
+    assert_eq!(
+        client.try_approve_action(&Address::generate(&env), &action_id),
+        Err(Ok(Error::NotApprover))
+    );
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    assert!(!client.get_action(&action_id).executed);
+}
+
+#[test]
+fn test_upgrade_executes_at_approval_threshold() {
+    let (env, admin, target) = create_test_env();
+    let (client, approvers, action_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    client.approve_action(&approvers.get(0).unwrap(), &action_id);
+    client.approve_action(&approvers.get(2).unwrap(), &action_id);
+    assert_eq!(client.get_approvals(&action_id).len(), 2);
+
+    client.execute_action(&action_id);
+    assert!(client.get_action(&action_id).executed);
+}
+
+#[test]
+fn test_removed_approver_no_longer_counts() {
+    let (env, admin, target) = create_test_env();
+    let (client, approvers, action_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    client.approve_action(&approvers.get(0).unwrap(), &action_id);
+    client.approve_action(&approvers.get(1).unwrap(), &action_id);
+    client.set_approvers(
+        &admin,
+        &vec![&env, approvers.get(1).unwrap(), approvers.get(2).unwrap()],
+    );
+
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    client.approve_action(&approvers.get(2).unwrap(), &action_id);
+    client.execute_action(&action_id);
+}
+
+#[test]
+fn test_approvals_only_gate_upgrades() {
+    let (env, admin, target) = create_test_env();
+    let (client, _approvers, _upgrade_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &String::from_str(&env, "param"),
+        &86400,
+    );
+    env.ledger().with_mut(|li| {
+        li.timestamp += 86400;
+    });
+    client.execute_action(&action_id);
+    assert!(client.get_action(&action_id).executed);
+}
+
+#[test]
+fn test_threshold_cannot_exceed_approvers() {
+    let (env, admin, target) = create_test_env();
+    let (client, approvers, _action_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    assert_eq!(
+        client.try_set_threshold(&admin, &4),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    // Duplicates collapse, leaving fewer approvers than the threshold of 2
+    let first = approvers.get(0).unwrap();
+    assert_eq!(
+        client.try_set_approvers(&admin, &vec![&env, first.clone(), first]),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    assert_eq!(
+        client.try_set_threshold(&Address::generate(&env), &1),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_threshold(), 2);
+    assert_eq!(client.get_approvers().len(), 3);
+}
+
+#[test]
+fn test_cancel_all_pending() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let data = String::from_str(&env, "emergency");
+    for _ in 0..4 {
+        client.queue_action(
+            &ActionType::ParameterChange,
+            &target,
+            &symbol_short!("update"),
+            &vec![&env],
+            &data,
+            &86400,
+        );
+    }
+
+    // One already executed and one already cancelled are left alone
+    env.ledger().with_mut(|li| {
+        li.timestamp += 86400;
+    });
+    client.execute_action(&1);
+    client.cancel_action(&2);
+
+    assert_eq!(client.cancel_all_pending(&admin), 2);
+    assert!(client.get_pending_actions().is_empty());
+    assert!(client.get_action(&3).cancelled);
+    assert!(client.get_action(&4).cancelled);
+    assert!(client.get_action(&1).executed);
+    assert!(!client.get_action(&1).cancelled);
+
+    // Nothing left to cancel
+    assert_eq!(client.cancel_all_pending(&admin), 0);
+}
+
+#[test]
+fn test_cancel_all_pending_page() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let data = String::from_str(&env, "paged");
+    for _ in 0..6 {
+        client.queue_action(
+            &ActionType::ParameterChange,
+            &target,
+            &symbol_short!("update"),
+            &vec![&env],
+            &data,
+            &86400,
+        );
+    }
+
+    assert_eq!(client.cancel_all_pending_page(&admin, &0, &4), 4);
+    let pending = client.get_pending_actions();
+    assert_eq!(pending.len(), 2);
+    assert!(pending.contains(5));
+    assert!(pending.contains(6));
+}
+
+#[test]
+fn test_cancel_all_pending_requires_admin() {
+    let (env, admin, _target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+
+    let result = client.try_cancel_all_pending(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_executable_at_before_and_after_delay() {
+    let (env, admin, target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+    });
+
+    let action_id = client.queue_action(
+        &ActionType::ParameterChange,
+        &target,
+        &symbol_short!("update"),
+        &vec![&env],
+        &String::from_str(&env, "countdown"),
+        &86400,
+    );
+
+    assert_eq!(client.get_executable_at(&action_id), 1_000 + 86400);
+    assert!(!client.is_executable_now(&action_id));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000 + 86400;
+    });
+    assert!(client.is_executable_now(&action_id));
+
+    client.execute_action(&action_id);
+    assert!(!client.is_executable_now(&action_id));
+}
+
+#[test]
+fn test_executable_at_unknown_action() {
+    let (env, admin, _target) = create_test_env();
+    let contract_id = env.register_contract(None, TimelockContract);
+    let client = TimelockContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_get_executable_at(&99),
+        Err(Ok(Error::ActionNotFound))
+    );
+    assert!(!client.is_executable_now(&99));
+}
+
+#[test]
+fn test_is_executable_now_waits_for_upgrade_approvals() {
+    let (env, admin, target) = create_test_env();
+    let (client, approvers, action_id) = setup_upgrade_with_approvers(&env, &admin, &target);
+
+    assert!(!client.is_executable_now(&action_id));
+    client.approve_action(&approvers.get(0).unwrap(), &action_id);
+    client.approve_action(&approvers.get(1).unwrap(), &action_id);
+    assert!(client.is_executable_now(&action_id));
+}
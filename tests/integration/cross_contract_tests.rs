@@ -261,7 +261,7 @@ fn test_commitment_settlement_calls_nft_settle() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // Verify NFT is no longer active
@@ -130,7 +130,7 @@ fn test_e2e_complete_commitment_lifecycle() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // ========== PHASE 5: VERIFICATION ==========
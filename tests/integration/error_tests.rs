@@ -520,7 +520,7 @@ fn test_error_double_settlement() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // Second settlement should fail (commitment not active)
@@ -528,7 +528,7 @@ fn test_error_double_settlement() {
         harness
             .env
             .as_contract(&harness.contracts.commitment_core, || {
-                CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+                CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
             })
     });
     assert!(result.is_err());
@@ -757,7 +757,7 @@ fn test_error_premature_settlement() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 }
 
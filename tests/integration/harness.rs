@@ -120,6 +120,11 @@ impl TestHarness {
                 accounts.admin.clone(),
                 commitment_nft.clone(),
             );
+            CommitmentCoreContract::add_allowed_asset(
+                env.clone(),
+                accounts.admin.clone(),
+                token_address.clone(),
+            );
         });
 
         // Initialize attestation_engine
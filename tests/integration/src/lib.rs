@@ -3,12 +3,16 @@
 
 #![cfg(test)]
 
-use attestation_engine::{AttestationEngineContract, AttestationEngineContractClient};
+use attestation_engine::{
+    AttestationEngineContract, AttestationEngineContractClient, AttestationError,
+};
 use commitment_core::{CommitmentCoreContract, CommitmentCoreContractClient, CommitmentRules};
-use commitment_nft::{CommitmentNFTContract, CommitmentNFTContractClient};
+use commitment_nft::{CommitmentNFTContract, CommitmentNFTContractClient, SettlementReason};
 use price_oracle::{PriceOracleContract, PriceOracleContractClient};
 use soroban_sdk::{
+    contract, contractimpl, symbol_short,
     testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
     Address, Env, Map, String,
 };
 
@@ -44,6 +48,7 @@ impl IntegrationTestFixture {
         let core_contract_id = env.register_contract(None, CommitmentCoreContract);
         let core_client = CommitmentCoreContractClient::new(&env, &core_contract_id);
         core_client.initialize(&admin, &nft_contract_id);
+        core_client.add_allowed_asset(&admin, &asset_address);
 
         // Deploy Attestation Engine contract
         let attestation_contract_id = env.register_contract(None, AttestationEngineContract);
@@ -194,7 +199,7 @@ fn test_settlement_flow_end_to_end() {
     });
 
     // Settle commitment
-    fixture.core_client.settle(&commitment_id);
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
 
     // Verify commitment is settled
     let settled_commitment = fixture.core_client.get_commitment(&commitment_id);
@@ -409,6 +414,661 @@ fn test_gas_batch_attestations() {
     assert_eq!(attestations.len(), 5);
 }
 
+// ============================================
+// Compliance-Gated Allocation Tests
+// ============================================
+
+/// Deploys NFT, core and engine with a real token and one funded commitment.
+/// Returns (fixture, commitment_id).
+fn setup_funded_fixture() -> (IntegrationTestFixture, String) {
+    let mut fixture = IntegrationTestFixture::setup();
+    let env = &fixture.env;
+
+    let token_admin = Address::generate(env);
+    let asset = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    StellarAssetClient::new(env, &asset).mint(&fixture.owner, &10_000);
+    fixture
+        .core_client
+        .add_allowed_asset(&fixture.admin, &asset);
+    fixture.asset_address = asset;
+    fixture
+        .nft_client
+        .set_core_contract(&fixture.core_client.address);
+
+    let rules = fixture.create_test_rules();
+    let commitment_id = fixture.core_client.create_commitment(
+        &fixture.owner,
+        &5_000,
+        &fixture.asset_address,
+        &rules,
+    );
+
+    fixture
+        .attestation_client
+        .add_verifier(&fixture.admin, &fixture.verifier);
+    fixture
+        .core_client
+        .set_attestation_engine(&fixture.admin, &fixture.attestation_client.address);
+
+    (fixture, commitment_id)
+}
+
+#[test]
+fn test_allocation_blocked_for_low_compliance_score() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    // High-severity violation drops the stored score to 70
+    let mut data = Map::new(env);
+    data.set(
+        String::from_str(env, "violation_type"),
+        String::from_str(env, "drawdown_breach"),
+    );
+    data.set(
+        String::from_str(env, "severity"),
+        String::from_str(env, "high"),
+    );
+    fixture.attestation_client.attest(
+        &fixture.verifier,
+        &commitment_id,
+        &String::from_str(env, "violation"),
+        &data,
+        &false,
+    );
+    assert_eq!(
+        fixture
+            .attestation_client
+            .calculate_compliance_score(&commitment_id),
+        70
+    );
+
+    fixture.core_client.set_min_alloc_score(&fixture.admin, &80);
+    let pool = Address::generate(env);
+    fixture
+        .core_client
+        .approve_pool(&fixture.owner, &commitment_id, &pool);
+    let result = fixture
+        .core_client
+        .try_allocate(&commitment_id, &pool, &1_000);
+    assert!(result.is_err());
+    assert!(fixture
+        .core_client
+        .get_allocations(&commitment_id)
+        .is_empty());
+
+    // Lowering the bar lets the allocation through
+    fixture.core_client.set_min_alloc_score(&fixture.admin, &60);
+    fixture.core_client.allocate(&commitment_id, &pool, &1_000);
+    assert_eq!(fixture.core_client.get_allocations(&commitment_id).len(), 1);
+}
+
+#[test]
+fn test_allocation_allowed_for_compliant_commitment() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    fixture.attestation_client.attest(
+        &fixture.verifier,
+        &commitment_id,
+        &String::from_str(env, "health_check"),
+        &Map::new(env),
+        &true,
+    );
+
+    fixture.core_client.set_min_alloc_score(&fixture.admin, &80);
+    let pool = Address::generate(env);
+    fixture
+        .core_client
+        .approve_pool(&fixture.owner, &commitment_id, &pool);
+    fixture.core_client.allocate(&commitment_id, &pool, &1_000);
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.current_value, 4_000);
+}
+
+/// Pool stand-in that issues `amount * rate_bps / 10000` shares per deposit.
+#[contract]
+pub struct MockSharePool;
+
+#[contractimpl]
+impl MockSharePool {
+    pub fn set_rate(e: Env, rate_bps: i128) {
+        e.storage()
+            .instance()
+            .set(&symbol_short!("rate"), &rate_bps);
+    }
+
+    pub fn deposit(e: Env, _depositor: Address, amount: i128) -> i128 {
+        let rate_bps: i128 = e
+            .storage()
+            .instance()
+            .get(&symbol_short!("rate"))
+            .unwrap_or(10_000);
+        amount * rate_bps / 10_000
+    }
+}
+
+#[test]
+fn test_allocate_with_min_out_against_share_pool() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let pool = env.register_contract(None, MockSharePool);
+    let pool_client = MockSharePoolClient::new(env, &pool);
+    let token = TokenClient::new(env, &fixture.asset_address);
+    fixture
+        .core_client
+        .approve_pool(&fixture.owner, &commitment_id, &pool);
+
+    // 2% haircut: 1_000 in -> 980 shares, below the 990 minimum
+    pool_client.set_rate(&9_800);
+    assert!(fixture
+        .core_client
+        .try_allocate_with_min_out(&commitment_id, &pool, &1_000, &990)
+        .is_err());
+    assert_eq!(token.balance(&pool), 0);
+    assert_eq!(
+        fixture
+            .core_client
+            .get_commitment(&commitment_id)
+            .current_value,
+        5_000
+    );
+
+    // Within tolerance the allocation goes through and reports the shares
+    pool_client.set_rate(&9_950);
+    let shares = fixture
+        .core_client
+        .allocate_with_min_out(&commitment_id, &pool, &1_000, &990);
+    assert_eq!(shares, 995);
+    assert_eq!(token.balance(&pool), 1_000);
+    assert_eq!(
+        fixture
+            .core_client
+            .get_commitment(&commitment_id)
+            .current_value,
+        4_000
+    );
+}
+
+/// Vault stand-in that records the settlement callbacks it receives.
+#[contract]
+pub struct MockPayoutHook;
+
+#[contractimpl]
+impl MockPayoutHook {
+    pub fn on_settlement(e: Env, commitment_id: String, amount: i128) {
+        e.storage()
+            .instance()
+            .set(&symbol_short!("last"), &(commitment_id, amount));
+    }
+
+    pub fn last_settlement(e: Env) -> Option<(String, i128)> {
+        e.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+#[test]
+fn test_settlement_routed_to_payout_hook() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let hook = env.register_contract(None, MockPayoutHook);
+    let hook_client = MockPayoutHookClient::new(env, &hook);
+    let token = TokenClient::new(env, &fixture.asset_address);
+
+    fixture
+        .core_client
+        .set_payout_hook(&commitment_id, &fixture.owner, &Some(hook.clone()));
+    assert_eq!(
+        fixture.core_client.get_payout_hook(&commitment_id),
+        Some(hook.clone())
+    );
+
+    let owner_before = token.balance(&fixture.owner);
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    // Proceeds went to the hook, which was told about them
+    assert_eq!(token.balance(&hook), 5_000);
+    assert_eq!(token.balance(&fixture.owner), owner_before);
+    assert_eq!(
+        hook_client.last_settlement(),
+        Some((commitment_id.clone(), 5_000))
+    );
+}
+
+#[test]
+fn test_settlement_without_payout_hook_pays_owner() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let token = TokenClient::new(env, &fixture.asset_address);
+    assert_eq!(fixture.core_client.get_payout_hook(&commitment_id), None);
+
+    let owner_before = token.balance(&fixture.owner);
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    assert_eq!(token.balance(&fixture.owner), owner_before + 5_000);
+}
+
+#[test]
+fn test_transferred_nft_holder_settles_and_receives_proceeds() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let token = TokenClient::new(env, &fixture.asset_address);
+    let buyer = fixture.user1.clone();
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    let token_id = commitment.nft_token_id;
+
+    // At maturity the NFT is released for trading and sold on
+    env.ledger().with_mut(|li| {
+        li.timestamp = commitment.expires_at;
+    });
+    fixture.nft_client.settle(&fixture.admin, &token_id);
+    fixture
+        .nft_client
+        .transfer(&fixture.owner, &buyer, &token_id);
+
+    // The original owner can no longer claim through the holder path
+    assert!(fixture
+        .core_client
+        .try_settle_as_nft_holder(&token_id, &fixture.owner)
+        .is_err());
+
+    let owner_before = token.balance(&fixture.owner);
+    fixture.core_client.settle_as_nft_holder(&token_id, &buyer);
+
+    assert_eq!(token.balance(&buyer), 5_000);
+    assert_eq!(token.balance(&fixture.owner), owner_before);
+    assert_eq!(
+        fixture.core_client.get_commitment(&commitment_id).status,
+        String::from_str(env, "settled")
+    );
+    assert_eq!(fixture.core_client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_plain_settle_after_nft_sale_pays_holder() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let token = TokenClient::new(env, &fixture.asset_address);
+    let buyer = fixture.user1.clone();
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = commitment.expires_at;
+    });
+    fixture
+        .nft_client
+        .settle(&fixture.admin, &commitment.nft_token_id);
+    fixture
+        .nft_client
+        .transfer(&fixture.owner, &buyer, &commitment.nft_token_id);
+
+    // The seller settling through the plain path still pays the buyer
+    let owner_before = token.balance(&fixture.owner);
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    assert_eq!(token.balance(&buyer), 5_000);
+    assert_eq!(token.balance(&fixture.owner), owner_before);
+}
+
+/// DEX stand-in paying out a fixed rate from its own `token_out` reserve.
+#[contract]
+pub struct MockSwapRouter;
+
+#[contractimpl]
+impl MockSwapRouter {
+    pub fn set_swap_rate(e: Env, rate_bps: i128) {
+        e.storage()
+            .instance()
+            .set(&symbol_short!("rate"), &rate_bps);
+    }
+
+    pub fn swap(
+        e: Env,
+        _token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        _min_out: i128,
+        recipient: Address,
+    ) -> i128 {
+        let rate_bps: i128 = e
+            .storage()
+            .instance()
+            .get(&symbol_short!("rate"))
+            .unwrap_or(10_000);
+        let amount_out = amount_in * rate_bps / 10_000;
+        TokenClient::new(&e, &token_out).transfer(
+            &e.current_contract_address(),
+            &recipient,
+            &amount_out,
+        );
+        amount_out
+    }
+}
+
+#[test]
+fn test_settlement_swapped_into_payout_asset() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let router = env.register_contract(None, MockSwapRouter);
+    let router_client = MockSwapRouterClient::new(env, &router);
+    let stable = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &stable).mint(&router, &10_000);
+    let stable_token = TokenClient::new(env, &stable);
+
+    fixture.core_client.set_swap_router(&fixture.admin, &router);
+    fixture.core_client.set_payout_asset(
+        &commitment_id,
+        &fixture.owner,
+        &Some(stable.clone()),
+        &4_900,
+    );
+
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+
+    // 10% worse than expected: below min_out, so settlement reverts
+    router_client.set_swap_rate(&9_000);
+    assert!(fixture
+        .core_client
+        .try_settle(&commitment_id, &fixture.owner)
+        .is_err());
+    assert_eq!(
+        fixture.core_client.get_commitment(&commitment_id).status,
+        String::from_str(env, "active")
+    );
+
+    router_client.set_swap_rate(&9_900);
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    // Owner is paid in the stablecoin; the router took the committed asset
+    assert_eq!(stable_token.balance(&fixture.owner), 4_950);
+    assert_eq!(
+        TokenClient::new(env, &fixture.asset_address).balance(&router),
+        5_000
+    );
+}
+
+#[test]
+fn test_update_value_reflected_on_nft() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    let updater = Address::generate(env);
+    fixture.core_client.add_updater(&fixture.admin, &updater);
+    fixture
+        .core_client
+        .update_value(&updater, &commitment_id, &4_800);
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    let nft = fixture.nft_client.get_metadata(&commitment.nft_token_id);
+    assert_eq!(nft.current_value, 4_800);
+}
+
+#[test]
+fn test_mark_violated_propagates_to_nft() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    let updater = Address::generate(env);
+    fixture.core_client.add_updater(&fixture.admin, &updater);
+    // 20% loss against a 10% limit marks the commitment violated in core
+    fixture
+        .core_client
+        .update_value(&updater, &commitment_id, &4_000);
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.status, String::from_str(env, "violated"));
+    assert!(fixture.nft_client.is_violated(&commitment.nft_token_id));
+    assert!(!fixture.nft_client.is_active(&commitment.nft_token_id));
+}
+
+#[test]
+fn test_violated_commitment_nft_records_violated_reason() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    let updater = Address::generate(env);
+    fixture.core_client.add_updater(&fixture.admin, &updater);
+    fixture
+        .core_client
+        .update_value(&updater, &commitment_id, &4_000);
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    let nft = fixture.nft_client.get_metadata(&commitment.nft_token_id);
+    assert_eq!(nft.settlement_reason, SettlementReason::Violated);
+}
+
+#[test]
+fn test_early_exit_settles_nft_before_expiry() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    fixture
+        .core_client
+        .early_exit(&commitment_id, &fixture.owner);
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.status, String::from_str(env, "early_exit"));
+    let nft = fixture.nft_client.get_metadata(&commitment.nft_token_id);
+    assert!(!nft.is_active);
+    assert_eq!(nft.settlement_reason, SettlementReason::EarlyExit);
+    // 5% penalty on 5_000
+    assert_eq!(
+        TokenClient::new(env, &fixture.asset_address).balance(&fixture.owner),
+        9_750
+    );
+}
+
+#[test]
+fn test_shared_commitment_splits_gain_between_contributors() {
+    let (fixture, _) = setup_funded_fixture();
+    let env = &fixture.env;
+    let token = TokenClient::new(env, &fixture.asset_address);
+    let partner = Address::generate(env);
+    StellarAssetClient::new(env, &fixture.asset_address).mint(&partner, &10_000);
+
+    // Owner already has 5_000 locked in the fixture commitment
+    let contributors = soroban_sdk::vec![
+        env,
+        (fixture.owner.clone(), 3_000),
+        (partner.clone(), 1_000)
+    ];
+    let rules = fixture.create_test_rules();
+    let commitment_id =
+        fixture
+            .core_client
+            .create_shared_commitment(&contributors, &fixture.asset_address, &rules);
+    assert_eq!(fixture.core_client.get_shares(&commitment_id), contributors);
+
+    // The commitment earns 20%, backed by yield sent to core
+    StellarAssetClient::new(env, &fixture.asset_address).mint(&fixture.core_client.address, &800);
+    let updater = Address::generate(env);
+    fixture.core_client.add_updater(&fixture.admin, &updater);
+    fixture
+        .core_client
+        .update_value(&updater, &commitment_id, &4_800);
+
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    assert_eq!(token.balance(&fixture.owner), 2_000 + 3_600);
+    assert_eq!(token.balance(&partner), 9_000 + 1_200);
+}
+
+#[test]
+fn test_settle_finalizes_attestation_metrics() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+
+    fixture
+        .attestation_client
+        .record_drawdown(&fixture.verifier, &commitment_id, &2);
+    let score = fixture
+        .attestation_client
+        .calculate_compliance_score(&commitment_id);
+
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+
+    // Settlement closed the metrics with the score at that point
+    assert!(fixture.attestation_client.is_finalized(&commitment_id));
+    assert_eq!(
+        fixture.attestation_client.get_final_score(&commitment_id),
+        Some(score)
+    );
+    assert_eq!(
+        fixture
+            .attestation_client
+            .try_record_drawdown(&fixture.verifier, &commitment_id, &5),
+        Err(Ok(AttestationError::MetricsFinalized))
+    );
+}
+
+#[test]
+fn test_settle_requires_min_attestations() {
+    let (fixture, commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    fixture.core_client.set_min_attestations(&fixture.admin, &2);
+
+    let health_check = || {
+        let mut data = Map::new(env);
+        data.set(
+            String::from_str(env, "initial_value"),
+            String::from_str(env, "5000"),
+        );
+        fixture.attestation_client.attest(
+            &fixture.verifier,
+            &commitment_id,
+            &String::from_str(env, "health_check"),
+            &data,
+            &true,
+        );
+    };
+    health_check();
+
+    let expires_at = fixture
+        .core_client
+        .get_commitment(&commitment_id)
+        .expires_at;
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at;
+    });
+    assert!(fixture
+        .core_client
+        .try_settle(&commitment_id, &fixture.owner)
+        .is_err());
+    assert_eq!(
+        fixture.core_client.get_commitment(&commitment_id).status,
+        String::from_str(env, "active")
+    );
+
+    health_check();
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
+    assert_eq!(
+        fixture.core_client.get_commitment(&commitment_id).status,
+        String::from_str(env, "settled")
+    );
+}
+
+#[test]
+fn test_oracle_crash_marks_commitment_violated() {
+    let mut fixture = IntegrationTestFixture::setup();
+    let env = &fixture.env;
+
+    let asset = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &asset).mint(&fixture.owner, &10_000);
+    fixture
+        .core_client
+        .add_allowed_asset(&fixture.admin, &asset);
+    fixture.asset_address = asset.clone();
+    fixture
+        .nft_client
+        .set_core_contract(&fixture.core_client.address);
+
+    let feeder = Address::generate(env);
+    let oracle_id = env.register_contract(None, PriceOracleContract);
+    let oracle_client = PriceOracleContractClient::new(env, &oracle_id);
+    oracle_client.initialize(&fixture.admin);
+    oracle_client.add_oracle(&fixture.admin, &feeder);
+    oracle_client.set_price(&feeder, &asset, &100_000000, &6, &0);
+    fixture
+        .core_client
+        .set_price_oracle(&fixture.admin, &oracle_id);
+
+    // Created at the 100.0 price, 10% max loss
+    let rules = fixture.create_test_rules();
+    let commitment_id =
+        fixture
+            .core_client
+            .create_commitment(&fixture.owner, &5_000, &asset, &rules);
+    let keeper = Address::generate(env);
+    fixture.core_client.add_updater(&fixture.admin, &keeper);
+
+    // A 3% dip is revalued but within limits
+    oracle_client.set_price(&feeder, &asset, &97_000000, &6, &0);
+    assert!(!fixture
+        .core_client
+        .check_and_mark_violation(&keeper, &commitment_id));
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.current_value, 4_850);
+    assert_eq!(commitment.status, String::from_str(env, "active"));
+    assert_eq!(fixture.core_client.get_total_value_locked(), 4_850);
+
+    // The crash to 80.0 breaches the limit in the same call
+    oracle_client.set_price(&feeder, &asset, &80_000000, &6, &0);
+    assert!(fixture
+        .core_client
+        .check_and_mark_violation(&keeper, &commitment_id));
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.current_value, 4_000);
+    assert_eq!(commitment.status, String::from_str(env, "violated"));
+    assert_eq!(fixture.core_client.get_total_value_locked(), 0);
+    assert!(fixture.nft_client.is_violated(&commitment.nft_token_id));
+
+    // Nothing left to check once the commitment is closed
+    assert!(fixture
+        .core_client
+        .try_check_and_mark_violation(&keeper, &commitment_id)
+        .is_err());
+}
+
 // ============================================
 // Oracle Integration Tests
 // ============================================
@@ -428,7 +1088,7 @@ fn test_oracle_integration_price_feed() {
     oracle_client.initialize(&admin);
     oracle_client.add_oracle(&admin, &oracle_feeder);
 
-    oracle_client.set_price(&oracle_feeder, &asset, &1_500_000000, &8);
+    oracle_client.set_price(&oracle_feeder, &asset, &1_500_000000, &8, &0);
     let data = oracle_client.get_price(&asset);
     assert_eq!(data.price, 1_500_000000);
     assert_eq!(data.decimals, 8);
@@ -450,7 +1110,7 @@ fn test_oracle_integration_validation_and_staleness() {
     oracle_client.add_oracle(&admin, &oracle_feeder);
     oracle_client.set_max_staleness(&admin, &300);
 
-    oracle_client.set_price(&oracle_feeder, &asset, &2_000_000000, &8);
+    oracle_client.set_price(&oracle_feeder, &asset, &2_000_000000, &8, &0);
     let data = oracle_client.get_price_valid(&asset, &None);
     assert_eq!(data.price, 2_000_000000);
 
@@ -476,8 +1136,124 @@ fn test_oracle_whitelist_only_can_set_price() {
     oracle_client.add_oracle(&admin, &oracle_feeder);
 
     assert!(oracle_client.is_oracle_whitelisted(&oracle_feeder));
-    oracle_client.set_price(&oracle_feeder, &asset, &100, &6);
+    oracle_client.set_price(&oracle_feeder, &asset, &100, &6, &0);
 
     oracle_client.remove_oracle(&admin, &oracle_feeder);
     assert!(!oracle_client.is_oracle_whitelisted(&oracle_feeder));
 }
+
+/// Oracle consumer that remembers the last price it was notified of, or
+/// rejects every callback once `set_failing(true)` is called.
+#[contract]
+pub struct MockPriceSubscriber;
+
+#[contractimpl]
+impl MockPriceSubscriber {
+    pub fn set_failing(e: Env, failing: bool) {
+        e.storage().instance().set(&symbol_short!("fail"), &failing);
+    }
+
+    pub fn on_price_update(e: Env, asset: Address, price: i128) {
+        let failing: bool = e
+            .storage()
+            .instance()
+            .get(&symbol_short!("fail"))
+            .unwrap_or(false);
+        if failing {
+            panic!("subscriber failure");
+        }
+        e.storage()
+            .instance()
+            .set(&symbol_short!("last"), &(asset, price));
+    }
+
+    pub fn last_price(e: Env) -> Option<(Address, i128)> {
+        e.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+#[test]
+fn test_oracle_notifies_price_subscribers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle_feeder = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let other_asset = Address::generate(&env);
+
+    let oracle_id = env.register_contract(None, PriceOracleContract);
+    let oracle_client = PriceOracleContractClient::new(&env, &oracle_id);
+    oracle_client.initialize(&admin);
+    oracle_client.add_oracle(&admin, &oracle_feeder);
+
+    let failing = env.register_contract(None, MockPriceSubscriber);
+    MockPriceSubscriberClient::new(&env, &failing).set_failing(&true);
+    let subscriber = env.register_contract(None, MockPriceSubscriber);
+    let subscriber_client = MockPriceSubscriberClient::new(&env, &subscriber);
+
+    // The failing subscriber is notified first and must not stop the others
+    oracle_client.subscribe(&admin, &failing, &asset);
+    oracle_client.subscribe(&admin, &subscriber, &asset);
+
+    oracle_client.set_price(&oracle_feeder, &asset, &1_250_000000, &8, &0);
+    assert_eq!(
+        subscriber_client.last_price(),
+        Some((asset.clone(), 1_250_000000))
+    );
+    assert_eq!(oracle_client.get_price(&asset).price, 1_250_000000);
+
+    // Other assets do not trigger the callback
+    oracle_client.set_price(&oracle_feeder, &other_asset, &7, &8, &0);
+    assert_eq!(
+        subscriber_client.last_price(),
+        Some((asset.clone(), 1_250_000000))
+    );
+
+    oracle_client.unsubscribe(&subscriber, &subscriber, &asset);
+    oracle_client.set_price(&oracle_feeder, &asset, &1_300_000000, &8, &0);
+    assert_eq!(
+        subscriber_client.last_price(),
+        Some((asset.clone(), 1_250_000000))
+    );
+}
+
+#[test]
+fn test_auto_allocation_on_create() {
+    let (fixture, _commitment_id) = setup_funded_fixture();
+    let env = &fixture.env;
+    let token = TokenClient::new(env, &fixture.asset_address);
+    let pool = Address::generate(env);
+
+    // Allocate 25% on creation, keep at least half in reserve
+    fixture
+        .core_client
+        .set_auto_allocation(&fixture.admin, &2_500, &5_000);
+    let commitment_id = fixture.core_client.create_commitment_auto_alloc(
+        &fixture.owner,
+        &4_000,
+        &fixture.asset_address,
+        &fixture.create_test_rules(),
+        &pool,
+    );
+
+    let commitment = fixture.core_client.get_commitment(&commitment_id);
+    assert_eq!(commitment.amount, 4_000);
+    assert_eq!(commitment.current_value, 3_000);
+    assert_eq!(token.balance(&pool), 1_000);
+    assert_eq!(
+        fixture.nft_client.owner_of(&commitment.nft_token_id),
+        fixture.owner
+    );
+    assert_eq!(
+        fixture
+            .core_client
+            .get_allocations(&commitment_id)
+            .get(0)
+            .unwrap()
+            .amount,
+        1_000
+    );
+    // Allocated funds are not a loss against the 10% limit
+    assert!(!fixture.core_client.check_violations(&commitment_id));
+}